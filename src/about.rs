@@ -0,0 +1,159 @@
+use crate::anim_dsl;
+use crate::assets::{self, ArtAsset};
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::responsive::LayoutMode;
+use crate::theme;
+use crate::utils::ProceduralAnimationWidget;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::time::Duration;
+
+/// Version, build info and links, below a small animated logo
+pub struct AboutScreen {
+    logo: ProceduralAnimationWidget,
+    exit_intended: bool,
+}
+
+impl AboutScreen {
+    const WIDTH: u16 = 60;
+    /// Widest info entry plus the border
+    const COMPACT_WIDTH: u16 = 54;
+
+    pub fn new() -> Self {
+        Self { logo: logo_animation(), exit_intended: false }
+    }
+
+    /// Logo above the info, with a cell of padding inside the border
+    fn full_size(&self) -> Size {
+        let info = Self::compact_size();
+        Size::new(Self::WIDTH, self.logo.get_height() + 1 + info.height + 2)
+    }
+
+    /// Just the info, without logo or padding
+    fn compact_size() -> Size {
+        #[allow(clippy::cast_possible_truncation)]
+        let height = Self::info_lines().len() as u16 + 2;
+        Size::new(Self::COMPACT_WIDTH, height)
+    }
+
+    fn info_lines() -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        let entry = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:>10}  "), Style::default().fg(theme.muted)),
+                Span::styled(format!("{value:<40}"), Style::default().fg(theme.text)),
+            ])
+        };
+
+        vec![
+            Line::from(Span::styled(
+                format!("binbreak v{}", env!("CARGO_PKG_VERSION")),
+                Style::default().fg(theme.accent).bold(),
+            )),
+            Line::from(Span::styled(
+                env!("CARGO_PKG_DESCRIPTION"),
+                Style::default().fg(theme.text),
+            )),
+            Line::from(""),
+            entry(
+                "build",
+                format!("{profile}, {}/{}", std::env::consts::OS, std::env::consts::ARCH),
+            ),
+            entry("author", env!("CARGO_PKG_AUTHORS").to_string()),
+            entry("license", env!("CARGO_PKG_LICENSE").to_string()),
+            entry("source", env!("CARGO_PKG_REPOSITORY").to_string()),
+            entry("docs", "https://docs.rs/binbreak".to_string()),
+            Line::from(""),
+            Line::from(Span::styled(
+                "made with ratatui and crossterm",
+                Style::default().fg(theme.muted),
+            )),
+        ]
+    }
+}
+
+fn logo_animation() -> ProceduralAnimationWidget {
+    let mut widget = assets::art(ArtAsset::About)
+        .animation(anim_dsl::DEFAULT_TITLE_COLORS, anim_dsl::DEFAULT_TITLE_CHARS, 30)
+        .with_pause_at_end(Duration::from_secs(1));
+    widget.set_highlight_color(theme::current().accent);
+    widget
+}
+
+impl WidgetRef for AboutScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let lines = Self::info_lines();
+        let compact = LayoutMode::for_area(area, self.full_size()).is_compact();
+        let size = if compact {
+            Self::compact_size()
+        } else {
+            self.full_size()
+        };
+
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        let block = Block::bordered().title(" About ").title_alignment(Center).fg(theme.muted);
+        let inner = block.inner(frame);
+        block.render(frame, buf);
+        if compact {
+            Paragraph::new(lines).alignment(Center).render(inner, buf);
+            return;
+        }
+
+        let inner = inner.inner(ratatui::layout::Margin::new(1, 1));
+        let logo_height = self.logo.get_height();
+
+        let [logo_area, _, info_area] = Layout::vertical([
+            Constraint::Length(logo_height),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        let [logo_area] = Layout::horizontal([Constraint::Length(self.logo.get_width())])
+            .flex(Flex::Center)
+            .areas(logo_area);
+        self.logo.render_to_buffer(logo_area, buf);
+        Paragraph::new(lines).alignment(Center).render(info_area, buf);
+    }
+}
+
+impl MainScreenWidget for AboutScreen {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if keybinds::is_exit(input) || keybinds::is_select(input) {
+            self.exit_intended = true;
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    fn is_animating(&self) -> bool {
+        self.logo.is_animating()
+    }
+
+    fn min_size(&self) -> Size {
+        Self::compact_size()
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(&[keybinds::EXIT], "back")]
+    }
+
+    fn config_changed(&mut self) {
+        self.logo = logo_animation();
+    }
+}
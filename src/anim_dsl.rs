@@ -0,0 +1,308 @@
+//! A tiny declarative language for procedural title animations.
+//!
+//! Specs look like function calls with named arguments, e.g.
+//! `diagonal_sweep(width=8, fg=highlight, bg=dim)` or `binary_flip`.
+//! Colors accept anything ratatui can parse (`green`, `#ff8800`, `42`) plus
+//! the two dynamic names `highlight` (the selected mode color) and `dim`
+//! (the theme's title base color).
+
+use crate::utils::{CharFn, ColorFn};
+use crate::{color, settings, theme};
+use ratatui::style::Color;
+use std::fmt;
+use std::str::FromStr;
+
+pub const DEFAULT_TITLE_COLORS: &str = "diagonal_sweep(width=8, fg=highlight, bg=dim)";
+pub const DEFAULT_TITLE_CHARS: &str = "binary_flip(width=8)";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AnimSpecError {
+    Syntax(String),
+    UnknownPreset(String),
+    UnknownArgument { preset: String, argument: String },
+    InvalidValue { argument: String, value: String },
+}
+
+impl fmt::Display for AnimSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(msg) => write!(f, "syntax error: {msg}"),
+            Self::UnknownPreset(name) => write!(f, "unknown preset '{name}'"),
+            Self::UnknownArgument { preset, argument } => {
+                write!(f, "preset '{preset}' has no argument '{argument}'")
+            },
+            Self::InvalidValue { argument, value } => {
+                write!(f, "invalid value '{value}' for argument '{argument}'")
+            },
+        }
+    }
+}
+
+/// A parsed `name(key=value, ...)` expression
+#[derive(Debug, PartialEq)]
+struct Call {
+    name: String,
+    args: Vec<(String, String)>,
+}
+
+impl Call {
+    fn parse(src: &str) -> Result<Self, AnimSpecError> {
+        let src = src.trim();
+        let (name, rest) = match src.find('(') {
+            Some(open) => (&src[..open], Some(&src[open + 1..])),
+            None => (src, None),
+        };
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(AnimSpecError::Syntax(format!("expected a preset name, found '{src}'")));
+        }
+
+        let mut args = Vec::new();
+        if let Some(rest) = rest {
+            let body = rest
+                .strip_suffix(')')
+                .ok_or_else(|| AnimSpecError::Syntax("missing closing ')'".to_string()))?;
+            for arg in body.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+                let (key, value) = arg.split_once('=').ok_or_else(|| {
+                    AnimSpecError::Syntax(format!("expected key=value, found '{arg}'"))
+                })?;
+                args.push((key.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        Ok(Self { name: name.to_lowercase(), args })
+    }
+
+    /// Reject arguments the preset doesn't understand, so typos don't pass silently
+    fn check_args(&self, allowed: &[&str]) -> Result<(), AnimSpecError> {
+        match self.args.iter().find(|(key, _)| !allowed.contains(&key.as_str())) {
+            Some((key, _)) => Err(AnimSpecError::UnknownArgument {
+                preset: self.name.clone(),
+                argument: key.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn arg(&self, key: &str) -> Option<&str> {
+        self.args.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn number(&self, key: &str, default: f32) -> Result<f32, AnimSpecError> {
+        match self.arg(key) {
+            None => Ok(default),
+            Some(value) => value.parse::<f32>().ok().filter(|n| *n > 0.0).ok_or_else(|| {
+                AnimSpecError::InvalidValue { argument: key.to_string(), value: value.to_string() }
+            }),
+        }
+    }
+
+    fn color(&self, key: &str, default: ColorRef) -> Result<ColorRef, AnimSpecError> {
+        match self.arg(key) {
+            None => Ok(default),
+            Some(value) => ColorRef::parse(value).ok_or_else(|| AnimSpecError::InvalidValue {
+                argument: key.to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ColorRef {
+    Highlight,
+    Dim,
+    Fixed(Color),
+}
+
+impl ColorRef {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "highlight" => Some(Self::Highlight),
+            "dim" => Some(Self::Dim),
+            other => Color::from_str(other)
+                .ok()
+                .map(|c| Self::Fixed(color::resolve(c, settings::get().color_depth))),
+        }
+    }
+
+    fn resolve(self, highlight: Color) -> Color {
+        match self {
+            Self::Highlight => highlight,
+            Self::Dim => theme::current().title_dim,
+            Self::Fixed(color) => color,
+        }
+    }
+}
+
+/// Build a color function for art of the given dimensions from a spec
+pub fn color_fn(spec: &str, width: usize, height: usize) -> Result<ColorFn, AnimSpecError> {
+    let call = Call::parse(spec)?;
+    match call.name.as_str() {
+        "solid" => {
+            call.check_args(&["fg"])?;
+            let fg = call.color("fg", ColorRef::Highlight)?;
+            Ok(Box::new(move |_, _, _, _, highlight| fg.resolve(highlight)))
+        },
+        "diagonal_sweep" | "horizontal_sweep" | "vertical_sweep" => {
+            call.check_args(&["width", "fg", "bg"])?;
+            let strip_width = call.number("width", 8.0)?;
+            let fg = call.color("fg", ColorRef::Highlight)?;
+            let bg = call.color("bg", ColorRef::Dim)?;
+            let position = sweep_position(&call.name);
+            let extent = position(width, height) as f32;
+            let start_offset = -strip_width;
+            let total_range = extent + 2.0 * strip_width;
+
+            Ok(Box::new(move |x, y, progress, _cycle, highlight| {
+                let offset = start_offset + progress * total_range;
+                let dist_from_strip = (position(x, y) as f32 - offset).abs();
+                if dist_from_strip < strip_width {
+                    fg.resolve(highlight)
+                } else {
+                    bg.resolve(highlight)
+                }
+            }))
+        },
+        "pulse" => {
+            call.check_args(&["fg", "bg"])?;
+            let fg = call.color("fg", ColorRef::Highlight)?;
+            let bg = call.color("bg", ColorRef::Dim)?;
+            Ok(Box::new(move |_, _, progress, _, highlight| {
+                if progress < 0.5 {
+                    fg.resolve(highlight)
+                } else {
+                    bg.resolve(highlight)
+                }
+            }))
+        },
+        _ => Err(AnimSpecError::UnknownPreset(call.name)),
+    }
+}
+
+/// Build an optional character function from a spec; `none` keeps the art unchanged
+pub fn char_fn(spec: &str, width: usize, height: usize) -> Result<Option<CharFn>, AnimSpecError> {
+    let call = Call::parse(spec)?;
+    match call.name.as_str() {
+        "none" => {
+            call.check_args(&[])?;
+            Ok(None)
+        },
+        "binary_flip" | "binary_reveal" => {
+            call.check_args(&["width"])?;
+            let reveal = call.name == "binary_reveal";
+            let strip_width = call.number("width", 8.0)?;
+            let start_offset = -strip_width;
+            let total_range = (width + height) as f32 + 2.0 * strip_width;
+
+            // Permanently replaces characters with '0' or '1' on the first pass,
+            // then reverses them back to the original on the second pass.
+            // binary_reveal only makes the second pass, every cycle.
+            Ok(Some(Box::new(move |x, y, progress, cycle, original_char| {
+                let offset = start_offset + progress * total_range;
+                let has_strip_passed = ((x + y) as f32) < offset;
+                let binary_char = binary_char_at(x, y);
+
+                // Even cycles: original -> binary, odd cycles: binary -> original
+                if (cycle.is_multiple_of(2) && !reveal) == has_strip_passed {
+                    binary_char
+                } else {
+                    original_char
+                }
+            })))
+        },
+        _ => Err(AnimSpecError::UnknownPreset(call.name)),
+    }
+}
+
+fn sweep_position(name: &str) -> fn(usize, usize) -> usize {
+    match name {
+        "horizontal_sweep" => |x, _| x,
+        "vertical_sweep" => |_, y| y,
+        _ => |x, y| x + y,
+    }
+}
+
+/// Stable pseudo-random '0' or '1' for a cell position
+fn binary_char_at(x: usize, y: usize) -> char {
+    let mut position_hash = x.wrapping_mul(2654435761);
+    position_hash ^= y.wrapping_mul(2246822519);
+    position_hash = position_hash.wrapping_mul(668265263);
+    position_hash ^= position_hash >> 15;
+
+    let mut binary_hash = position_hash.wrapping_mul(1597334677);
+    binary_hash ^= binary_hash >> 16;
+    if (binary_hash & 1) == 0 { '0' } else { '1' }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_call_with_arguments() {
+        let call = Call::parse(" diagonal_sweep( width = 8, fg=#FF8800 ) ");
+        assert_eq!(
+            call,
+            Ok(Call {
+                name: "diagonal_sweep".to_string(),
+                args: vec![
+                    ("width".to_string(), "8".to_string()),
+                    ("fg".to_string(), "#FF8800".to_string())
+                ],
+            })
+        );
+        assert_eq!(Call::parse("none").map(|c| c.args.len()), Ok(0));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert!(matches!(color_fn("sparkle", 10, 2), Err(AnimSpecError::UnknownPreset(_))));
+        assert!(matches!(color_fn("solid(fg=green", 10, 2), Err(AnimSpecError::Syntax(_))));
+        assert!(matches!(
+            color_fn("solid(speed=3)", 10, 2),
+            Err(AnimSpecError::UnknownArgument { .. })
+        ));
+        assert!(matches!(
+            color_fn("diagonal_sweep(width=-1)", 10, 2),
+            Err(AnimSpecError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn sweep_colors_follow_progress() {
+        let Ok(f) = color_fn("horizontal_sweep(width=2, fg=red, bg=blue)", 10, 1) else {
+            panic!("spec should parse");
+        };
+        // At the start the strip is left of the art, at the end right of it
+        assert_eq!(f(5, 0, 0.0, 0, Color::Green), Color::Blue);
+        assert_eq!(f(5, 0, 0.5, 0, Color::Green), Color::Red);
+        assert_eq!(f(5, 0, 1.0, 0, Color::Green), Color::Blue);
+
+        let Ok(solid) = color_fn("solid", 10, 1) else { panic!("spec should parse") };
+        assert_eq!(solid(0, 0, 0.3, 0, Color::Green), Color::Green);
+    }
+
+    #[test]
+    fn binary_flip_alternates_per_cycle() {
+        let Ok(Some(f)) = char_fn(DEFAULT_TITLE_CHARS, 4, 1) else {
+            panic!("spec should parse");
+        };
+        assert_eq!(f(0, 0, 0.0, 0, 'M'), 'M');
+        assert_ne!(f(0, 0, 1.0, 0, 'M'), 'M');
+        assert_ne!(f(0, 0, 0.0, 1, 'M'), 'M');
+        assert_eq!(f(0, 0, 1.0, 1, 'M'), 'M');
+        assert!(matches!(char_fn("none", 4, 1), Ok(None)));
+    }
+
+    #[test]
+    fn binary_reveal_ends_on_the_art_every_cycle() {
+        let Ok(Some(f)) = char_fn("binary_reveal", 4, 1) else {
+            panic!("spec should parse");
+        };
+        for cycle in 0..2 {
+            assert_ne!(f(0, 0, 0.0, cycle, 'M'), 'M');
+            assert_eq!(f(0, 0, 1.0, cycle, 'M'), 'M');
+        }
+    }
+}
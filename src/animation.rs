@@ -0,0 +1,179 @@
+use crate::settings;
+use crate::utils::ProceduralAnimationWidget;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::cell::Cell;
+use std::time::Duration;
+
+/// Time as every animation sees it. The app moves it on with its ticks, so all
+/// animations stay in step with each other and stop together while it is paused.
+#[derive(Copy, Clone, Default)]
+struct Clock {
+    now: Duration,
+    paused: bool,
+}
+
+thread_local! {
+    static CLOCK: Cell<Clock> = Cell::new(Clock::default());
+}
+
+/// Animation time since the app started, leaving out the time it was paused
+pub fn now() -> Duration {
+    CLOCK.with(Cell::get).now
+}
+
+/// Move animation time on, unless it is paused
+pub fn advance(dt: Duration) {
+    CLOCK.with(|clock| {
+        let mut current = clock.get();
+        if !current.paused {
+            current.now += dt;
+        }
+        clock.set(current);
+    });
+}
+
+/// Stop or restart every animation at once, e.g. while the terminal isn't focused
+pub fn set_paused(paused: bool) {
+    CLOCK.with(|clock| clock.set(Clock { paused, ..clock.get() }));
+}
+
+pub fn is_paused() -> bool {
+    CLOCK.with(Cell::get).paused
+}
+
+/// Animations played one after another, like an intro leading into a loop, so the
+/// screen showing them doesn't have to keep track of time itself
+pub struct AnimationSequence {
+    stages: Vec<ProceduralAnimationWidget>,
+    /// The last stage repeats instead of ending the sequence
+    loops: bool,
+    /// Animation time of the start
+    started: Duration,
+}
+
+impl Default for AnimationSequence {
+    fn default() -> Self {
+        Self { stages: Vec::new(), loops: false, started: now() }
+    }
+}
+
+impl AnimationSequence {
+    /// Play `animation` once, after the stages before it
+    pub fn then(mut self, animation: ProceduralAnimationWidget) -> Self {
+        self.stages.push(animation);
+        self
+    }
+
+    /// Play `animation` after the stages before it, over and over. Nothing can follow.
+    pub fn then_loop(mut self, animation: ProceduralAnimationWidget) -> Self {
+        self.stages.push(animation);
+        self.loops = true;
+        self
+    }
+
+    /// The stage shown `elapsed` after the start and how far into it, None once all played
+    fn stage_at(&self, mut elapsed: Duration) -> Option<(&ProceduralAnimationWidget, Duration)> {
+        let last = self.stages.len().checked_sub(1)?;
+        for (index, stage) in self.stages.iter().enumerate() {
+            if (index == last && self.loops) || elapsed < stage.cycle_duration() {
+                return Some((stage, elapsed));
+            }
+            elapsed -= stage.cycle_duration();
+        }
+        None
+    }
+
+    /// Every stage played, the last frame stays on screen
+    pub fn is_finished(&self) -> bool {
+        self.stage_at(now().saturating_sub(self.started)).is_none()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        !self.is_finished() && !settings::get().reduce_motion && !is_paused()
+    }
+
+    /// Size of the largest stage
+    pub fn size(&self) -> (u16, u16) {
+        self.stages.iter().fold((0, 0), |(width, height), stage| {
+            (width.max(stage.get_width()), height.max(stage.get_height()))
+        })
+    }
+
+    pub fn set_highlight_color(&mut self, color: Color) {
+        for stage in &mut self.stages {
+            stage.set_highlight_color(color);
+        }
+    }
+
+    pub fn render_to_buffer(&self, area: Rect, buf: &mut Buffer) {
+        match self.stage_at(now().saturating_sub(self.started)) {
+            Some((stage, elapsed)) => stage.render_after(elapsed, area, buf),
+            None => {
+                if let Some(last) = self.stages.last() {
+                    last.render_end(area, buf);
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(art: &str) -> ProceduralAnimationWidget {
+        // Two frames of 10 ms
+        ProceduralAnimationWidget::new(
+            art.to_string(),
+            2,
+            Duration::from_millis(10),
+            |_, _, _, _, c| c,
+        )
+    }
+
+    #[test]
+    fn the_clock_stands_still_while_paused() {
+        let start = now();
+        advance(Duration::from_millis(10));
+        set_paused(true);
+        advance(Duration::from_millis(10));
+        assert!(is_paused());
+        set_paused(false);
+        assert_eq!(now() - start, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn stages_play_in_order_and_the_loop_never_ends() {
+        let width_at = |sequence: &AnimationSequence, ms| {
+            sequence.stage_at(Duration::from_millis(ms)).map(|(stage, _)| stage.get_width())
+        };
+        let once = AnimationSequence::default().then(animation("a")).then(animation("bb"));
+        assert_eq!(
+            (width_at(&once, 5), width_at(&once, 25), width_at(&once, 40)),
+            (Some(1), Some(2), None)
+        );
+        assert_eq!(
+            once.stage_at(Duration::from_millis(25)).map(|(_, at)| at),
+            Some(Duration::from_millis(5))
+        );
+
+        let looping = AnimationSequence::default().then(animation("a")).then_loop(animation("bb"));
+        assert_eq!(width_at(&looping, 10_000), Some(2));
+        assert!(!looping.is_finished());
+        assert_eq!(looping.size(), (2, 1));
+        assert!(AnimationSequence::default().is_finished());
+    }
+
+    #[test]
+    fn a_finished_sequence_holds_its_last_frame() {
+        let mut sequence = AnimationSequence::default().then(animation("ab"));
+        sequence.set_highlight_color(Color::Red);
+        advance(Duration::from_secs(1));
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buf = Buffer::empty(area);
+        sequence.render_to_buffer(area, &mut buf);
+        assert_eq!((buf[(1, 0)].symbol(), buf[(1, 0)].fg), ("b", Color::Red));
+    }
+}
@@ -1,38 +1,57 @@
-use crate::binary_numbers::{BinaryNumbersGame, Bits};
+use crate::about::AboutScreen;
+use crate::animation;
+use crate::assets::{self, ArtAsset, ArtWatcher};
+use crate::binary_numbers::{BinaryNumbersGame, HighScores};
+use crate::cli::{Cli, PlayMode};
+use crate::config::{Config, ConfigWatcher};
+use crate::daily::DailyChallenge;
+use crate::effects::{Fade, MatrixRainWidget, MenuBackground, PlasmaWidget};
+use crate::events::Events;
+use crate::feedback::Alerts;
+use crate::hotseat::Hotseat;
 use crate::keybinds;
-use crate::main_screen_widget::MainScreenWidget;
-use crate::utils::ProceduralAnimationWidget;
-use crossterm::event;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use indoc::indoc;
+use crate::keybinds::KeyHint;
+use crate::leaderboard::LeaderboardScreen;
+use crate::log_viewer::LogViewer;
+use crate::mastery::{Mastery, MasteryScreen};
+use crate::menu_history::MenuHistory;
+use crate::message::Message;
+use crate::playlist::Playlist;
+use crate::responsive::LayoutMode;
+use crate::sandbox::SandboxScreen;
+use crate::screen::{Screen, ScreenStack, Transition};
+use crate::settings;
+use crate::stats::{self, StatsScreen};
+use crate::theme;
+use crate::timing::{FixedStep, FrameRate, FrameStats};
+use crate::toast::{self, Toasts};
+use crate::tutorial::TutorialScreen;
+use crate::utils::{
+    DebugOverlay, KeyHintFooter, Marquee, ProceduralAnimationWidget, ScrollList, TooSmall,
+    next_index, previous_index, progress_ring,
+};
+use crate::versus::{Race, Versus};
+use crate::worksheet::QuestionList;
+use binbreak::{Bits, NumberMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::Terminal;
+use ratatui::backend::Backend;
 use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Position, Rect, Size};
 use ratatui::prelude::{Color, Modifier, Span, Style};
-use ratatui::widgets::{List, ListItem, ListState};
-use std::cmp;
-use std::thread;
+use ratatui::widgets::{ListItem, ListState, Widget};
+use std::io::{self, Write};
 use std::time::{Duration, Instant};
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-pub enum NumberMode {
-    Unsigned,
-    Signed,
-}
-
-impl NumberMode {
-    pub const fn label(&self) -> &'static str {
-        match self {
-            Self::Unsigned => "UNSIGNED",
-            Self::Signed => "SIGNED",
-        }
-    }
-}
-
-/// Persistent application preferences that survive across menu/game transitions
+/// How the start menu opens: the defaults, or the choices made on the command line
 #[derive(Copy, Clone, Debug)]
 struct AppPreferences {
     last_selected_index: usize,
     last_number_mode: NumberMode,
+    last_practice: bool,
+    animation_paused: bool,
+    /// Fixed question seed from the command line, kept for every game of the session
+    seed: Option<u64>,
 }
 
 impl Default for AppPreferences {
@@ -40,85 +59,198 @@ impl Default for AppPreferences {
         Self {
             last_selected_index: 4, // Default to "byte 8 bit"
             last_number_mode: NumberMode::Unsigned,
+            last_practice: false,
+            animation_paused: false,
+            seed: None,
         }
     }
 }
 
 /// Get the color associated with a specific difficulty level / game mode
 pub fn get_mode_color(bits: &Bits) -> Color {
-    // Color scheme: progression from easy (green/cyan) to hard (yellow/red)
-    match bits {
-        Bits::Four => Color::Rgb(100, 255, 100),        // green
-        Bits::FourShift4 => Color::Rgb(100, 255, 180),  // cyan
-        Bits::FourShift8 => Color::Rgb(100, 220, 255),  // light blue
-        Bits::FourShift12 => Color::Rgb(100, 180, 255), // blue
-        Bits::Eight => Color::Rgb(150, 120, 255),       // royal blue
-        Bits::Twelve => Color::Rgb(200, 100, 255),      // purple
-        Bits::Sixteen => Color::Rgb(255, 80, 150),      // pink
+    theme::current().mode_color(bits)
+}
+
+/// Start the selected mode, or open the selected screen
+fn open_selected(state: &mut StartMenuState) -> Box<dyn Screen> {
+    let Some(bits) = state.selected_bits() else {
+        return Box::new(AboutScreen::new());
+    };
+    state.history.record_play(&bits);
+    let _ = state.history.save();
+    // Playing can move a favorite up, keep the started mode selected in the new order
+    state.sort_items();
+    if state.hotseat {
+        let hotseat = Hotseat::new(bits, state.number_mode, state.seed)
+            .with_practice(state.practice)
+            .with_coverage(state.coverage);
+        return Box::new(hotseat);
+    }
+    let mut game = BinaryNumbersGame::new(bits, state.number_mode)
+        .with_practice(state.practice)
+        .with_coverage(state.coverage)
+        .with_countdown();
+    if let Some(seed) = state.seed {
+        game = game.with_seed(seed);
     }
+    Box::new(game)
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-enum FpsMode {
-    RealTime,    // 30 FPS with polling
-    Performance, // Block until input for minimal CPU
+/// Start a playlist session with the practice and seed choices of the menu; the sections
+/// pick their own modes
+fn open_playlist(state: &StartMenuState, playlist: Playlist) -> Box<dyn Screen> {
+    let mut game = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned)
+        .with_practice(state.practice)
+        .with_playlist(playlist)
+        .with_countdown();
+    if let Some(seed) = state.seed {
+        game = game.with_seed(seed);
+    }
+    Box::new(game)
 }
 
-enum AppState {
-    Start(StartMenuState, AppPreferences),
-    Playing(BinaryNumbersGame, AppPreferences),
-    Exit,
+/// Start a run through the questions of a list, each in its own mode
+fn open_questions(state: &StartMenuState, questions: QuestionList) -> Box<dyn Screen> {
+    let mut game = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned)
+        .with_practice(state.practice)
+        .with_questions(questions)
+        .with_countdown();
+    if let Some(seed) = state.seed {
+        game = game.with_seed(seed);
+    }
+    Box::new(game)
 }
 
-fn handle_start_input(
-    state: &mut StartMenuState,
-    key: KeyEvent,
-    prefs: AppPreferences,
-) -> Option<(AppState, AppPreferences)> {
+/// Hovering highlights a menu item, clicking selects it and clicking it again opens it.
+/// The wheel moves the selection like the arrow keys.
+fn handle_start_mouse(state: &mut StartMenuState, mouse: MouseEvent) -> Transition {
+    let index = state.item_at(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Moved => state.hovered = index,
+        MouseEventKind::ScrollUp => state.select_previous(),
+        MouseEventKind::ScrollDown => state.select_next(),
+        MouseEventKind::Down(MouseButton::Left) => {
+            let Some(index) = index else {
+                return Transition::Stay;
+            };
+            if index == state.selected_index() {
+                return Transition::Push(open_selected(state));
+            }
+            state.list_state.select(Some(index));
+        },
+        _ => {},
+    }
+    Transition::Stay
+}
+
+fn handle_start_input(state: &mut StartMenuState, key: KeyEvent) -> Transition {
     match key {
         x if keybinds::is_up(x) => state.select_previous(),
         x if keybinds::is_down(x) => state.select_next(),
+        x if keybinds::pressed(x, keybinds::FIRST) => state.list_state.select(Some(0)),
+        x if keybinds::pressed(x, keybinds::LAST) => {
+            state.list_state.select(Some(state.items.len() - 1));
+        },
         x if keybinds::is_left(x) | keybinds::is_right(x) => state.toggle_number_mode(),
-        x if keybinds::is_select(x) => {
-            let bits = state.selected_bits();
-            let number_mode = state.number_mode;
-            // Update preferences with current selection
-            let updated_prefs = AppPreferences {
-                last_selected_index: state.selected_index(),
-                last_number_mode: state.number_mode,
-            };
-            return Some((
-                AppState::Playing(BinaryNumbersGame::new(bits, number_mode), updated_prefs),
-                updated_prefs,
-            ));
+        x if keybinds::is_select(x) => return Transition::Push(open_selected(state)),
+        x if keybinds::is_exit(x) => return Transition::Exit,
+        x if keybinds::choice_index(x).is_some() => {
+            // Digits jump straight into the game mode at that position
+            let index = keybinds::choice_index(x).unwrap_or(0);
+            if let Some(MenuItem::Play(..)) = state.items.get(index) {
+                state.list_state.select(Some(index));
+                return Transition::Push(open_selected(state));
+            }
+        },
+        x if keybinds::pressed(x, keybinds::ANIMATION) => state.toggle_animation(),
+        x if keybinds::pressed(x, keybinds::THEME) => state.cycle_theme(),
+        x if keybinds::pressed(x, keybinds::PRACTICE) => state.practice = !state.practice,
+        x if keybinds::pressed(x, keybinds::COVERAGE) => state.coverage = !state.coverage,
+        x if keybinds::pressed(x, keybinds::HOTSEAT) => state.hotseat = !state.hotseat,
+        x if keybinds::pressed(x, keybinds::KEY_PROFILE) => {
+            let current = settings::get();
+            settings::set(settings::Settings {
+                key_profile: current.key_profile.next(),
+                ..current
+            });
+        },
+        x if keybinds::pressed(x, keybinds::GUEST) => state.toggle_guest(),
+        x if keybinds::pressed(x, keybinds::FAVORITE) => state.toggle_favorite(),
+        x if keybinds::pressed(x, keybinds::MASTERY) => {
+            if let Some(bits) = state.selected_bits() {
+                return Transition::Push(Box::new(MasteryScreen::new(bits, state.number_mode)));
+            }
+        },
+        x if keybinds::pressed(x, keybinds::SANDBOX) => {
+            let bits = state.selected_bits().unwrap_or(Bits::Eight);
+            return Transition::Push(Box::new(SandboxScreen::new(&bits)));
+        },
+        x if keybinds::pressed(x, keybinds::STATS) => {
+            return Transition::Push(Box::new(StatsScreen::new()));
+        },
+        x if keybinds::pressed(x, keybinds::DAILY) => {
+            return Transition::Push(Box::new(DailyChallenge::new(stats::today())));
+        },
+        x if keybinds::pressed(x, keybinds::LEADERBOARD) => {
+            return Transition::Push(Box::new(LeaderboardScreen::new(stats::today())));
+        },
+        x if keybinds::pressed(x, keybinds::TUTORIAL) => {
+            return Transition::Push(Box::new(TutorialScreen::new()));
         },
-        x if keybinds::is_exit(x) => return Some((AppState::Exit, prefs)),
-        KeyEvent { code: KeyCode::Char('a' | 'A'), .. } => state.toggle_animation(),
         _ => {},
     }
-    None
+    Transition::Stay
 }
 
+/// Vertical spacing between the ASCII art and the menu list
+const MENU_SPACING: u16 = 3;
+/// Blank line plus the option hints below the menu list
+const MENU_OPTIONS_HEIGHT: u16 = 10;
+/// Rows the mode list keeps when it scrolls on a short terminal
+const MENU_MIN_LIST_ROWS: u16 = 4;
+
+/// "UNSIGNED" or "SIGNED  " (8 chars for alignment)
+const MENU_MODE_LABEL_WIDTH: u16 = 8;
+/// Favorite and recently played markers plus a space
+const MENU_TAGS_WIDTH: u16 = 3;
+/// Two spaces + "best" + space + five digit score
+const MENU_BEST_WIDTH: u16 = 12;
+
+/// Scrolled along the bottom of the start menu, one after the other
+const TIPS: &[&str] = &[
+    "Every 5 correct answers in a row earn a life back",
+    "Quick correct answers count towards mastering a mode, see the map with [m]",
+    "Practice mode [p] is untimed and lets you undo answers with [u]",
+    "Coverage [c] asks every value of a mode in turn instead of at random",
+    "In a game, [s] skips a question and [p] pauses",
+    "Favorite a mode with [f] to keep it at the top",
+    "Reskin the title banner by putting art/title.txt in the config directory",
+    "F11 shows the log, F12 frame timings",
+    "Flip bits freely and watch the value change in the sandbox [b]",
+    "See your answer times and accuracy over the last days with [s]",
+    "Take turns with a friend at the same questions: two players [v]",
+];
+
 fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer) {
-    // Get animation dimensions
+    // The banner is the first thing to go when space is short
+    let compact = LayoutMode::for_area(area, state.full_size()).is_compact();
     let ascii_width = state.animation.get_width();
-    let ascii_height = state.animation.get_height();
+    let (ascii_height, spacing) = if compact {
+        (0, 0)
+    } else {
+        (state.animation.get_height(), MENU_SPACING)
+    };
 
     let selected = state.selected_index();
-    let upper_labels: Vec<String> = state.items.iter().map(|(l, _)| l.to_uppercase()).collect();
+    let upper_labels: Vec<String> = state.items.iter().map(|i| i.label().to_uppercase()).collect();
+    // Short terminals scroll the list rather than dropping the options below it
+    let room = area.height.saturating_sub(ascii_height + spacing + MENU_OPTIONS_HEIGHT);
     #[allow(clippy::cast_possible_truncation)]
-    let max_len = upper_labels.iter().map(|s| s.len() as u16).max().unwrap_or(0);
+    let list_height = (upper_labels.len() as u16).min(room.max(MENU_MIN_LIST_ROWS));
+    let list_width =
+        state.list_width() + u16::from(ScrollList::overflows(upper_labels.len(), list_height));
 
-    // Calculate width for both columns: marker + space + label + spacing + mode
-    let mode_label_width = 8; // "UNSIGNED" or "SIGNED  " (8 chars for alignment)
-    let column_spacing = 4; // spaces between difficulty and mode columns
-    let list_width = 2 + max_len + column_spacing + mode_label_width; // marker + space + label + spacing + mode
-    #[allow(clippy::cast_possible_truncation)]
-    let list_height = upper_labels.len() as u16;
-
-    // Vertical spacing between ASCII art and list
-    let spacing: u16 = 3;
-    let total_height = ascii_height + spacing + list_height;
+    let total_height = ascii_height + spacing + list_height + MENU_OPTIONS_HEIGHT;
 
     // Center vertically & horizontally
     let start_y = area.y + area.height.saturating_sub(total_height) / 2;
@@ -137,234 +269,577 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
         list_height.min(area.height.saturating_sub(list_y - area.y)),
     );
 
+    render_menu_background(state, area, buf);
+
     // Get color for the selected menu item
-    let selected_color = get_mode_color(&state.items[selected].1);
+    let selected_color = state.items[selected].color();
 
     // Update animation color to match selected menu item
     state.animation.set_highlight_color(selected_color);
 
     // Render ASCII animation (handles paused state internally)
-    state.animation.render_to_buffer(ascii_area, buf);
+    if !compact {
+        state.animation.render_to_buffer(ascii_area, buf);
+    }
 
     let items: Vec<ListItem> = upper_labels
         .into_iter()
         .enumerate()
-        .map(|(i, label)| {
-            let is_selected = i == selected;
-            let marker = if is_selected { '»' } else { ' ' };
-            let padded_label = format!("{:<width$}", label, width = max_len as usize);
-
-            // Add number mode for selected item
-            let mode_display = if is_selected {
-                format!("{:>width$}", state.number_mode.label(), width = mode_label_width as usize)
-            } else {
-                " ".repeat(mode_label_width as usize)
-            };
+        .map(|(i, label)| menu_list_item(state, i, &label))
+        .collect();
+
+    let list = ScrollList::new(items);
+    ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    state.list_area = list_area;
 
-            let line = format!("{marker} {padded_label}    {mode_display}");
+    let options_area =
+        Rect::new(list_x, list_y + list_height + 1, list_width, MENU_OPTIONS_HEIGHT - 1);
+    render_menu_options(state, options_area.intersection(area), buf);
 
-            let item_color = get_mode_color(&state.items[i].1);
-            let mut style = Style::default().fg(item_color).add_modifier(Modifier::BOLD);
+    // Tips take the last row, if the menu leaves it free
+    let tips_row = area.bottom().saturating_sub(1);
+    if tips_row > options_area.bottom() {
+        let tips = keybinds::relabel(&TIPS.join("  ·  "));
+        Marquee::new(&tips)
+            .style(Style::default().fg(theme::current().muted))
+            .speed(10.0)
+            .gap(5)
+            .scrolling(!state.animation.is_paused())
+            .render(Rect::new(area.x, tips_row, area.width, 1), buf);
+    }
+}
 
-            // Make selected item extra prominent with background highlight
-            if is_selected {
-                style = style.bg(Color::Rgb(40, 40, 40));
+/// The background rests with the title animation
+fn render_menu_background(state: &mut StartMenuState, area: Rect, buf: &mut Buffer) {
+    if state.animation.is_paused() {
+        return;
+    }
+    let theme = theme::current();
+    match settings::get().menu_background {
+        MenuBackground::None => {},
+        MenuBackground::Rain => {
+            MatrixRainWidget::new(theme.muted, theme.title_dim).render(area, buf)
+        },
+        MenuBackground::Plasma => {
+            if state.plasma.as_ref().is_none_or(|plasma| plasma.size() != (area.width, area.height))
+            {
+                state.plasma =
+                    Some(PlasmaWidget::new(area.width, area.height, theme.title_dim, theme.muted));
+            }
+            if let Some(plasma) = &state.plasma {
+                plasma.render(area, buf);
             }
+        },
+    }
+}
 
-            ListItem::new(Span::styled(line, style))
-        })
-        .collect();
+/// A menu entry; game modes show the number mode when selected, and their mastery
+fn menu_list_item(state: &StartMenuState, i: usize, label: &str) -> ListItem<'static> {
+    let theme = theme::current();
+    let mode_label_width = MENU_MODE_LABEL_WIDTH as usize;
+    let is_selected = i == state.selected_index();
+    let is_hovered = state.hovered == Some(i);
+    let marker = match (is_selected, is_hovered) {
+        (true, _) => '»',
+        (false, true) => '›',
+        (false, false) => ' ',
+    };
+    let padded_label = format!("{:<width$}", label, width = state.max_label_len() as usize);
 
-    let list = List::new(items);
-    ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    let line = if let MenuItem::Play(_, bits) = &state.items[i] {
+        let favorite = if state.history.is_favorite(bits) { '★' } else { ' ' };
+        let recent = if state.history.is_last_played(bits) { '•' } else { ' ' };
+        let mode_display = if is_selected {
+            format!("{:>width$}", state.number_mode.label(), width = mode_label_width)
+        } else {
+            " ".repeat(mode_label_width)
+        };
+        let best = match state.best_score(bits) {
+            0 => "-".to_string(),
+            score => score.to_string(),
+        };
+        let mastery = state.mastery_ratio(bits);
+        let badge = format!("{} {:>3.0}%", progress_ring(mastery), mastery * 100.0);
+        format!(
+            "{marker} {favorite}{recent} {padded_label}    {mode_display}  best {best:>5}  {badge}"
+        )
+    } else {
+        format!("{marker}    {padded_label}")
+    };
+
+    let item_color = state.items[i].color();
+    let mut style = Style::default().fg(item_color).add_modifier(Modifier::BOLD);
+
+    if is_hovered && !is_selected {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+
+    // Make selected item extra prominent with background highlight
+    if is_selected {
+        style = style.bg(theme.menu_selected_bg);
+        if theme.monochrome {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+    }
+
+    ListItem::new(Span::styled(line, style))
 }
 
-fn handle_crossterm_events(app_state: &mut AppState) -> color_eyre::Result<()> {
-    if let Event::Key(key) = event::read()?
-        && key.kind == KeyEventKind::Press
-    {
-        match key.code {
-            // global exit via Ctrl+C
-            KeyCode::Char('c' | 'C') if key.modifiers == KeyModifiers::CONTROL => {
-                *app_state = AppState::Exit;
-            },
-
-            // state-specific input handling
-            _ => {
-                *app_state = match std::mem::replace(app_state, AppState::Exit) {
-                    AppState::Start(mut menu, prefs) => {
-                        if let Some((new_state, _)) = handle_start_input(&mut menu, key, prefs) {
-                            new_state
-                        } else {
-                            AppState::Start(menu, prefs)
-                        }
-                    },
-                    AppState::Playing(mut game, prefs) => {
-                        game.handle_input(key);
-                        AppState::Playing(game, prefs)
-                    },
-                    AppState::Exit => AppState::Exit,
-                }
-            },
+/// One muted hint line per menu toggle below the mode list
+fn render_menu_options(state: &StartMenuState, area: Rect, buf: &mut Buffer) {
+    let theme = theme::current();
+    let practice_label = if state.practice { "ON (untimed)" } else { "OFF" };
+    let coverage_label = if state.coverage {
+        "ON (every value in turn)"
+    } else {
+        "OFF"
+    };
+    let guest_label = if settings::get().guest {
+        "ON (nothing is saved)"
+    } else {
+        "OFF"
+    };
+    let option_lines = [
+        format!("[t] theme: {}", theme.name),
+        format!("[p] practice: {practice_label}"),
+        format!("[c] coverage: {coverage_label}"),
+        format!("[v] two players: {}", if state.hotseat { "ON (taking turns)" } else { "OFF" }),
+        format!("[g] guest: {guest_label}"),
+        "[f] favorite (moves to the top)".to_string(),
+        "[m] mastery map  [s] stats".to_string(),
+        "[d] daily challenge  [o] leaderboard".to_string(),
+        format!("[i] tutorial  [e] keys: {}", settings::get().key_profile.label()),
+    ];
+    for (i, option_line) in option_lines.into_iter().enumerate() {
+        #[allow(clippy::cast_possible_truncation)]
+        let option_y = area.y + i as u16;
+        if option_y < area.bottom() {
+            let option_area = Rect::new(area.x, option_y, area.width, 1);
+            ratatui::widgets::Widget::render(
+                Span::styled(keybinds::relabel(&option_line), Style::default().fg(theme.muted)),
+                option_area,
+                buf,
+            );
         }
     }
-    Ok(())
 }
 
-/// Determine the appropriate FPS mode based on the current game state
-fn get_fps_mode(game: &BinaryNumbersGame) -> FpsMode {
-    if game.is_active() {
-        FpsMode::RealTime // Timer running, needs continuous updates
+fn handle_message(screens: &mut ScreenStack, msg: Message) {
+    match msg {
+        // global exit via Ctrl+C
+        Message::Key(key)
+            if matches!(key.code, KeyCode::Char('c' | 'C'))
+                && key.modifiers == KeyModifiers::CONTROL =>
+        {
+            screens.apply(Transition::Exit);
+        },
+        Message::Resize(size) => screens.update(Message::Resize(below_footer(size))),
+        Message::Terminate => screens.apply(Transition::Exit),
+        // Decoration stands still while nobody looks, the game itself goes on
+        Message::Focus(focused) => {
+            animation::set_paused(!focused);
+            screens.update(msg);
+        },
+        Message::Key(key) => {
+            for key in keybinds::translate(key) {
+                screens.update(Message::Key(key));
+            }
+        },
+        Message::Tick(dt) => {
+            if let Some(key) = keybinds::time_out(dt) {
+                screens.update(Message::Key(key));
+            }
+            screens.update(msg);
+        },
+        msg => screens.update(msg),
+    }
+}
+
+/// The part of the terminal screens are drawn in, above the key hint footer
+const fn below_footer(terminal: Size) -> Size {
+    Size::new(terminal.width, terminal.height.saturating_sub(1))
+}
+
+/// Game logic advances in steps of this length, however long a frame takes
+const UPDATE_STEP: Duration = Duration::from_micros(16_667); // 60 updates a second
+/// Performance mode: while nothing moves, only wake up now and then to check for config edits
+const IDLE_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The start menu, with the mode picked on the command line or the tutorial
+/// on first launch opened over it
+fn initial_screens(cli: &Cli) -> io::Result<ScreenStack> {
+    let prefs = AppPreferences {
+        last_number_mode: cli.numbers,
+        last_practice: cli.mode == PlayMode::Practice,
+        animation_paused: cli.no_animation,
+        seed: cli.seed,
+        ..AppPreferences::default()
+    };
+    let mut menu = StartMenuState::new(prefs);
+    let first = if let Some(port) = cli.host {
+        let bits = cli.bits.clone().unwrap_or(Bits::Eight);
+        let race = Race { bits, number_mode: cli.numbers, seed: cli.seed };
+        Some(Box::new(Versus::host(port, race)?) as Box<dyn Screen>)
+    } else if let Some(address) = &cli.join {
+        Some(Box::new(Versus::join(address.clone())) as Box<dyn Screen>)
+    } else if let Some(playlist) = &cli.playlist {
+        Some(open_playlist(&menu, playlist.clone()))
+    } else if let Some(questions) = &cli.questions {
+        Some(open_questions(&menu, questions.clone()))
+    } else if let Some(bits) = &cli.bits {
+        let key = bits.high_score_key();
+        let index = menu.items.iter().position(|item| match item {
+            MenuItem::Play(_, item_bits) => item_bits.high_score_key() == key,
+            MenuItem::About => false,
+        });
+        menu.list_state.select(index);
+        Some(open_selected(&mut menu))
+    } else if TutorialScreen::should_autostart() {
+        Some(Box::new(TutorialScreen::new()) as Box<dyn Screen>)
     } else {
-        FpsMode::Performance // All other cases, block for minimal CPU
+        None
+    };
+    let mut screens = ScreenStack::default();
+    screens.push(Box::new(menu));
+    if let Some(screen) = first {
+        screens.push(screen);
     }
+    Ok(screens)
 }
 
-pub fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()> {
-    let prefs = AppPreferences::default();
-    let mut app_state = AppState::Start(StartMenuState::new(prefs), prefs);
-    let mut last_frame_time = Instant::now();
-    let target_frame_duration = std::time::Duration::from_millis(33); // ~30 FPS
-
-    while !matches!(app_state, AppState::Exit) {
-        let now = Instant::now();
-        let dt = now - last_frame_time;
-        last_frame_time = now;
-
-        // Advance game BEFORE drawing so stats are updated
-        if let AppState::Playing(game, prefs) = &mut app_state {
-            game.run(dt.as_secs_f64());
-            if game.is_exit_intended() {
-                app_state = AppState::Start(StartMenuState::new(*prefs), *prefs);
-                continue;
+/// Run the app in `terminal` until the last screen closes. Input comes from `events`, so
+/// the terminal needn't be the one the process was started in.
+pub fn run_app<B: Backend + Write>(
+    terminal: &mut Terminal<B>,
+    events: &mut Events,
+    cli: &Cli,
+) -> color_eyre::Result<()> {
+    theme::apply_config(&cli.apply_to(Config::load()));
+    let mut config_watcher = ConfigWatcher::new();
+    let mut art_watcher = settings::get().dev.then(ArtWatcher::new);
+
+    let mut screens = initial_screens(cli)?;
+    screens.update(Message::Resize(below_footer(terminal.size()?)));
+    let mut clock = FixedStep::new(UPDATE_STEP);
+    let mut redraw = true;
+    let mut panels = Panels::default();
+    let mut transition = Transitions::default();
+    let mut notices = Notices::default();
+
+    while !screens.is_empty() {
+        if redraw {
+            let started = Instant::now();
+            let overlays =
+                Overlays { panels: &panels, fade: transition.fade.as_ref(), notices: &notices };
+            let frame = draw(terminal, &mut screens, &overlays)?;
+            transition.frame_drawn(frame);
+            if let Some(stats) = &mut panels.stats {
+                stats.frame_drawn(started, Instant::now());
             }
+            redraw = false;
         }
 
-        terminal.draw(|f| match &mut app_state {
-            AppState::Start(menu, _) => render_start_screen(menu, f.area(), f.buffer_mut()),
-            AppState::Playing(game, _) => f.render_widget(&mut *game, f.area()),
-            AppState::Exit => {},
-        })?;
-
-        // handle input
-        if let AppState::Playing(game, _) = &app_state {
-            if get_fps_mode(game) == FpsMode::RealTime {
-                let poll_timeout = cmp::min(dt, target_frame_duration);
-                if event::poll(poll_timeout)? {
-                    handle_crossterm_events(&mut app_state)?;
+        // A held back vim key needs ticks to time out
+        let animating = screens.is_animating()
+            || transition.is_active()
+            || notices.is_active()
+            || keybinds::is_pending();
+        let tick_rate = if !animating {
+            IDLE_TICK_INTERVAL
+        } else if screens.is_idle_animation() {
+            settings::get().fps.min(FrameRate::IDLE).frame_duration()
+        } else {
+            settings::get().fps.frame_duration()
+        };
+        let shown = screens.visible_from();
+        let first = events.next(tick_rate)?;
+        // Everything that arrived while drawing is handled before the next frame
+        for msg in std::iter::once(Ok(first)).chain(events.pending()) {
+            let msg = msg?;
+            let Message::Tick(dt) = msg else {
+                if !matches!(msg, Message::Key(key) if panels.handle_key(key)) {
+                    handle_message(&mut screens, msg);
                 }
-            } else {
-                // performance mode: block thread until an input event occurs
-                handle_crossterm_events(&mut app_state)?;
+                redraw = true;
+                continue;
+            };
+            // Hot-apply config edits (e.g. theme colors) without restarting
+            if config_watcher.poll_changed() {
+                tracing::info!("config file changed, reloading");
+                theme::apply_config(&cli.apply_to(Config::load()));
+                screens.config_changed();
+                toast::show("Config reloaded");
+                redraw = true;
             }
-        } else if let AppState::Start(menu, _) = &app_state {
-            // For start menu, use real-time mode only if animation is running
-            if !menu.animation.is_paused() {
-                let poll_timeout = cmp::min(dt, target_frame_duration);
-                if event::poll(poll_timeout)? {
-                    handle_crossterm_events(&mut app_state)?;
-                }
-            } else {
-                // Animation paused, use performance mode to save CPU
-                handle_crossterm_events(&mut app_state)?;
+            if let Some(watcher) = &mut art_watcher
+                && watcher.poll_changed()
+            {
+                tracing::info!("art changed, reloading");
+                assets::reload();
+                screens.config_changed();
+                redraw = true;
+            }
+            // Still screens look the same after an update, only moving ones are redrawn.
+            // While the log is read the screens are held, and the log keeps tailing.
+            let steps = clock.advance(dt);
+            if panels.log.is_some() {
+                redraw = true;
+                continue;
+            }
+            redraw |= animating;
+            animation::advance(clock.step_duration() * steps);
+            for _ in 0..steps {
+                handle_message(&mut screens, Message::Tick(clock.step()));
+            }
+            if screens.is_empty() {
+                break;
             }
         }
-
-        // cap frame rate
-        let frame_duration = last_frame_time.elapsed();
-        if frame_duration < target_frame_duration {
-            thread::sleep(target_frame_duration - frame_duration);
+        if let Some(stats) = &mut panels.stats {
+            stats.event_latency = events.latency();
         }
+        transition.update(shown, &screens);
+        redraw |= notices.update(terminal.backend_mut());
     }
     Ok(())
 }
 
-fn ascii_animation() -> ProceduralAnimationWidget {
-    let art = indoc! {r#"
-         ,,        ,,              ,,
-        *MM        db             *MM      [a: toggle animation]     `7MM
-         MM                        MM                                  MM
-         MM,dMMb.`7MM  `7MMpMMMb.  MM,dMMb.`7Mb,od8 .gP"Ya   ,6"Yb.    MM  ,MP'
-         MM    `Mb MM    MM    MM  MM    `Mb MM' "',M'   Yb 8)   MM    MM ;Y
-         MM     M8 MM    MM    MM  MM     M8 MM    8M""""""  ,pm9MM    MM;Mm
-         MM.   ,M9 MM    MM    MM  MM.   ,M9 MM    YM.    , 8M   MM    MM `Mb.
-         P^YbmdP'.JMML..JMML  JMML.P^YbmdP'.JMML.   `Mbmmd' `Moo9^Yo..JMML. YA.
-    "#}
-    .to_string();
-
-    // Get dimensions for calculations
-    let art_lines: Vec<&str> = art.lines().collect();
-    let height = art_lines.len();
-    let width = art_lines.iter().map(|line| line.len()).max().unwrap_or(0);
-
-    let strip_width = 8.0;
-    let start_offset = -strip_width;
-    let end_offset = (width + height) as f32 + strip_width;
-    let total_range = end_offset - start_offset;
-
-    // Color function that calculates colors on-the-fly based on animation progress
-    let color_fn =
-        move |x: usize, y: usize, progress: f32, _cycle: usize, highlight_color: Color| -> Color {
-            let offset = start_offset + progress * total_range;
-            let diag_pos = (x + y) as f32;
-            let dist_from_strip = (diag_pos - offset).abs();
-
-            if dist_from_strip < strip_width {
-                highlight_color
+/// Fades between full screens, e.g. from the menu into a game
+#[derive(Default)]
+struct Transitions {
+    /// The last frame drawn, where a fade starts when the screen changes
+    last_frame: Buffer,
+    fade: Option<Fade>,
+}
+
+impl Transitions {
+    fn frame_drawn(&mut self, frame: &Buffer) {
+        if self.fade.is_none() {
+            self.last_frame.clone_from(frame);
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.fade.as_ref().is_some_and(Fade::is_active)
+    }
+
+    /// Start a fade if a full screen opened or closed since `shown` was on top
+    fn update(&mut self, shown: usize, screens: &ScreenStack) {
+        if !self.is_active() && !animation::is_paused() {
+            self.fade = None;
+        }
+        if screens.visible_from() != shown && !screens.is_empty() {
+            self.fade = Fade::new(std::mem::take(&mut self.last_frame));
+        }
+    }
+}
+
+/// Diagnostic panels drawn over every screen, opened with function keys
+#[derive(Default)]
+struct Panels {
+    /// Frame timings, collected while the debug overlay is open
+    stats: Option<FrameStats>,
+    log: Option<LogViewer>,
+}
+
+impl Panels {
+    /// Returns true if a panel took the key, the screens don't see it then
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if keybinds::pressed(key, keybinds::DEBUG) {
+            self.stats = if self.stats.is_some() {
+                None
             } else {
-                Color::DarkGray
+                Some(FrameStats::default())
+            };
+        } else if let Some(log) = &mut self.log {
+            if !log.handle_key(key) {
+                self.log = None;
             }
-        };
+        } else if keybinds::pressed(key, keybinds::LOG) {
+            self.log = Some(LogViewer::default());
+        } else {
+            return false;
+        }
+        true
+    }
+}
 
-    // Character function that permanently replaces characters with '0' or '1' on first pass,
-    // then reverses them back to original on second pass, creating an infinite loop
-    let char_fn =
-        move |x: usize, y: usize, progress: f32, cycle: usize, original_char: char| -> char {
-            let offset = start_offset + progress * total_range;
-            let diag_pos = (x + y) as f32;
-
-            // Hash function to determine if character is '0' or '1'
-            let mut position_hash = x.wrapping_mul(2654435761);
-            position_hash ^= y.wrapping_mul(2246822519);
-            position_hash = position_hash.wrapping_mul(668265263);
-            position_hash ^= position_hash >> 15;
-
-            let mut binary_hash = position_hash.wrapping_mul(1597334677);
-            binary_hash ^= binary_hash >> 16;
-            let binary_char = if (binary_hash & 1) == 0 { '0' } else { '1' };
-
-            // Even cycles (0, 2, 4...): transform original -> binary
-            // Odd cycles (1, 3, 5...): transform binary -> original
-            let is_forward_pass = cycle.is_multiple_of(2);
-
-            // Check if the strip has passed this character yet
-            let has_strip_passed = diag_pos < offset;
-
-            if is_forward_pass {
-                // Forward pass: if strip has passed, show binary; otherwise show original
-                if has_strip_passed { binary_char } else { original_char }
-            } else {
-                // Reverse pass: if strip has passed, show original; otherwise show binary
-                if has_strip_passed { original_char } else { binary_char }
+/// What is drawn over the screens: a fade between them, the panels and the notices
+struct Overlays<'a> {
+    panels: &'a Panels,
+    fade: Option<&'a Fade>,
+    notices: &'a Notices,
+}
+
+/// Toasts and the flashes of the bell, drawn over everything else
+#[derive(Default)]
+struct Notices {
+    toasts: Toasts,
+    alerts: Alerts,
+}
+
+impl Notices {
+    fn is_active(&self) -> bool {
+        self.toasts.is_active() || self.alerts.is_active()
+    }
+
+    /// Take the toasts and alerts given since the last call, and ring the bell of the
+    /// terminal written to by `out` if one asks for it. True if what is shown changed.
+    fn update(&mut self, out: &mut impl Write) -> bool {
+        let shown = self.is_active();
+        self.toasts.update();
+        if self.alerts.update() {
+            // Written past ratatui, which only knows about cells
+            let _ = out.write_all(b"\x07").and_then(|()| out.flush());
+        }
+        shown || self.is_active()
+    }
+}
+
+/// Returns the frame drawn
+fn draw<'a, B: Backend>(
+    terminal: &'a mut Terminal<B>,
+    screens: &mut ScreenStack,
+    overlays: &Overlays,
+) -> io::Result<&'a Buffer> {
+    let hints = screens.key_hints();
+    let frame = terminal.draw(|f| {
+        // Screens that don't fit are replaced by a resize hint, and a hidden run is held
+        let [area, footer] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(f.area());
+        if screens.is_too_small() {
+            let required = screens.min_size();
+            let required = Size::new(required.width, required.height + 1);
+            f.render_widget(TooSmall::new(required, f.area().as_size()), f.area());
+        } else {
+            screens.view(area, f.buffer_mut());
+            if let Some(fade) = overlays.fade {
+                f.render_widget(fade, area);
             }
-        };
+            f.render_widget(KeyHintFooter::new(&hints), footer);
+        }
+        if let Some(log) = &overlays.panels.log {
+            f.render_widget(log, f.area());
+        }
+        if let Some(stats) = &overlays.panels.stats {
+            f.render_widget(DebugOverlay::new(stats, screens.describe()), f.area());
+        }
+        f.render_widget(&overlays.notices.toasts, area);
+        f.render_widget(&overlays.notices.alerts, f.area());
+    })?;
+    Ok(frame.buffer)
+}
+
+fn ascii_animation() -> ProceduralAnimationWidget {
+    // The theme's animation specs apply unless the art brings its own
+    let theme = theme::current();
+    assets::art(ArtAsset::Title)
+        .animation(&theme.title_colors, &theme.title_chars, 50)
+        .with_pause_at_end(Duration::from_secs(2))
+        .precomputed(true)
+}
+
+/// An entry of the start menu list
+enum MenuItem {
+    Play(String, Bits),
+    About,
+}
+
+impl MenuItem {
+    fn label(&self) -> &str {
+        match self {
+            Self::Play(label, _) => label,
+            Self::About => "about",
+        }
+    }
 
-    ProceduralAnimationWidget::new(
-        art,
-        50, // 50 frames worth of timing
-        Duration::from_millis(50),
-        color_fn,
-    )
-    .with_char_fn(char_fn)
-    .with_pause_at_end(Duration::from_secs(2))
+    fn color(&self) -> Color {
+        match self {
+            Self::Play(_, bits) => get_mode_color(bits),
+            Self::About => theme::current().text,
+        }
+    }
 }
 
 // Start menu state
 struct StartMenuState {
-    items: Vec<(String, Bits)>,
+    items: Vec<MenuItem>,
     list_state: ListState,
     animation: ProceduralAnimationWidget,
+    /// Made for the area the menu is drawn in, when that is the background
+    plasma: Option<PlasmaWidget>,
     number_mode: NumberMode,
+    practice: bool,
+    /// Games deal from the whole value space evenly
+    coverage: bool,
+    /// Modes start as a match of two players taking turns
+    hotseat: bool,
+    seed: Option<u64>,
+    mastery: Mastery,
+    high_scores: HighScores,
+    history: MenuHistory,
+    /// Where the list was last drawn, for mouse hit-testing
+    list_area: Rect,
+    /// Item under the mouse pointer
+    hovered: Option<usize>,
+}
+
+impl Screen for StartMenuState {
+    fn update(&mut self, msg: Message) -> Transition {
+        match msg {
+            Message::Key(key) => handle_start_input(self, key),
+            Message::Mouse(mouse) => handle_start_mouse(self, mouse),
+            Message::Tick(_)
+            | Message::KeyRelease(_)
+            | Message::Paste(_)
+            | Message::Resize(_)
+            | Message::AnimationFinished
+            | Message::Focus(_)
+            | Message::Terminate => Transition::Stay,
+        }
+    }
+
+    fn view(&mut self, area: Rect, buf: &mut Buffer) {
+        render_start_screen(self, area, buf);
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{ANIMATION, DOWN, EXIT, LEFT, RIGHT, SELECT, UP};
+        vec![
+            KeyHint::new(&[UP, DOWN], "select"),
+            KeyHint::new(&[LEFT, RIGHT], "signed"),
+            KeyHint::new(&[SELECT], "start"),
+            KeyHint::new(&[ANIMATION], "animation"),
+            KeyHint::new(&[EXIT], "quit"),
+        ]
+    }
+
+    /// Just the menu and its options, without the banner, the list scrolled down to a
+    /// few rows and a scrollbar
+    fn min_size(&self) -> Size {
+        #[allow(clippy::cast_possible_truncation)]
+        let list_height = (self.items.len() as u16).min(MENU_MIN_LIST_ROWS);
+        Size::new(self.list_width() + 1, list_height + MENU_OPTIONS_HEIGHT)
+    }
+
+    fn is_animating(&self) -> bool {
+        self.animation.is_animating()
+            || (!self.animation.is_paused() && settings::get().menu_background.is_animating())
+    }
+
+    fn is_idle_animation(&self) -> bool {
+        true
+    }
+
+    /// Back from a game or the mastery map: show the scores and coverage it changed
+    fn resume(&mut self) {
+        self.mastery = Mastery::load();
+        self.high_scores = HighScores::load();
+    }
+
+    fn config_changed(&mut self) {
+        self.rebuild_animation();
+    }
 }
 
 impl StartMenuState {
@@ -372,52 +847,155 @@ impl StartMenuState {
         Self::with_preferences(prefs)
     }
 
+    fn max_label_len(&self) -> u16 {
+        #[allow(clippy::cast_possible_truncation)]
+        self.items.iter().map(|i| i.label().len() as u16).max().unwrap_or(0)
+    }
+
+    /// Marker + space + label + spacing + mode + mastery badge
+    fn list_width(&self) -> u16 {
+        let column_spacing = 4; // spaces between difficulty and mode columns
+        let badge_width = 8; // two spaces + ring + space + "100%"
+        2 + MENU_TAGS_WIDTH
+            + self.max_label_len()
+            + column_spacing
+            + MENU_MODE_LABEL_WIDTH
+            + MENU_BEST_WIDTH
+            + badge_width
+    }
+
+    /// The menu below the animated banner, with every item in view
+    fn full_size(&self) -> Size {
+        #[allow(clippy::cast_possible_truncation)]
+        let list_height = self.items.len() as u16;
+        Size::new(
+            self.list_width().max(self.animation.get_width()),
+            self.animation.get_height() + MENU_SPACING + list_height + MENU_OPTIONS_HEIGHT,
+        )
+    }
+
     fn with_preferences(prefs: AppPreferences) -> Self {
         let items = vec![
-            ("nibble_0    4 bit".to_string(), Bits::Four),
-            ("nibble_1    4 bit*16".to_string(), Bits::FourShift4),
-            ("nibble_2    4 bit*256".to_string(), Bits::FourShift8),
-            ("nibble_3    4 bit*4096".to_string(), Bits::FourShift12),
-            ("byte        8 bit".to_string(), Bits::Eight),
-            ("hexlet     12 bit".to_string(), Bits::Twelve),
-            ("word       16 bit".to_string(), Bits::Sixteen),
+            MenuItem::Play("nibble_0    4 bit".to_string(), Bits::Four),
+            MenuItem::Play("nibble_1    4 bit*16".to_string(), Bits::FourShift4),
+            MenuItem::Play("nibble_2    4 bit*256".to_string(), Bits::FourShift8),
+            MenuItem::Play("nibble_3    4 bit*4096".to_string(), Bits::FourShift12),
+            MenuItem::Play("byte        8 bit".to_string(), Bits::Eight),
+            MenuItem::Play("hexlet     12 bit".to_string(), Bits::Twelve),
+            MenuItem::Play("word       16 bit".to_string(), Bits::Sixteen),
+            MenuItem::About,
         ];
 
-        Self {
+        let mut state = Self {
             items,
-            list_state: ListState::default().with_selected(Some(prefs.last_selected_index)),
+            list_state: ListState::default(),
             animation: ascii_animation(),
+            plasma: None,
             number_mode: prefs.last_number_mode,
+            practice: prefs.last_practice,
+            coverage: false,
+            hotseat: false,
+            seed: prefs.seed,
+            mastery: Mastery::load(),
+            high_scores: HighScores::load(),
+            history: MenuHistory::load(),
+            list_area: Rect::default(),
+            hovered: None,
+        };
+        state.sort_items();
+        state.list_state.select(Some(prefs.last_selected_index));
+        if prefs.animation_paused {
+            state.animation.pause();
         }
+        state
     }
 
+    /// Favorites float to the top of the game modes, the selected item stays selected
+    fn sort_items(&mut self) {
+        let selected = self.items.get(self.selected_index()).map(MenuItem::label);
+        let selected = selected.map(str::to_string);
+        let modes = self.items.iter().take_while(|i| matches!(i, MenuItem::Play(..))).count();
+        self.items[..modes].sort_by_key(|item| match item {
+            MenuItem::Play(_, bits) => self.history.sort_key(bits),
+            MenuItem::About => (true, std::cmp::Reverse(0)),
+        });
+        if let Some(label) = selected {
+            let index = self.items.iter().position(|i| i.label() == label);
+            self.list_state.select(index);
+        }
+    }
+
+    fn toggle_favorite(&mut self) {
+        if let Some(bits) = self.selected_bits() {
+            self.history.toggle_favorite(&bits);
+            let _ = self.history.save();
+            self.sort_items();
+        }
+    }
+
+    /// The menu item drawn at a terminal position
+    fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.list_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = usize::from(row - self.list_area.y) + self.list_state.offset();
+        (index < self.items.len()).then_some(index)
+    }
+
+    /// Personal best of a mode in the current number mode, 0 if never played
+    fn best_score(&self, bits: &Bits) -> u32 {
+        let key = BinaryNumbersGame::compute_high_score_key(bits, self.number_mode);
+        self.high_scores.get(&key)
+    }
+    fn mastery_ratio(&self, bits: &Bits) -> f64 {
+        let key = BinaryNumbersGame::compute_high_score_key(bits, self.number_mode);
+        self.mastery.coverage(&key, bits.value_space()).ratio()
+    }
     fn selected_index(&self) -> usize {
         self.list_state.selected().unwrap_or(0)
     }
-    fn selected_bits(&self) -> Bits {
-        self.items[self.selected_index()].1.clone()
+    /// The selected game mode, None for entries that aren't one
+    fn selected_bits(&self) -> Option<Bits> {
+        match &self.items[self.selected_index()] {
+            MenuItem::Play(_, bits) => Some(bits.clone()),
+            MenuItem::About => None,
+        }
     }
     fn select_next(&mut self) {
-        let current = self.selected_index();
-        let next = if current + 1 >= self.items.len() {
-            current // stay at last item
-        } else {
-            current + 1
-        };
+        let next = next_index(self.selected_index(), self.items.len(), settings::get().wrap_menus);
         self.list_state.select(Some(next));
     }
     fn select_previous(&mut self) {
-        let current = self.selected_index();
-        let prev = if current == 0 {
-            0 // stay at first item
-        } else {
-            current - 1
-        };
+        let wrap = settings::get().wrap_menus;
+        let prev = previous_index(self.selected_index(), self.items.len(), wrap);
         self.list_state.select(Some(prev));
     }
     fn toggle_animation(&mut self) {
         self.animation.toggle_pause();
     }
+    fn cycle_theme(&mut self) {
+        theme::cycle();
+        self.rebuild_animation();
+    }
+    /// The title animation specs are part of the theme, so rebuild it after theme changes
+    fn rebuild_animation(&mut self) {
+        let was_paused = self.animation.is_paused();
+        self.animation = ascii_animation();
+        self.plasma = None;
+        if was_paused {
+            self.animation.pause();
+        }
+    }
+    /// Guest mode swaps the storage backend, so reload what the menu shows from it
+    fn toggle_guest(&mut self) {
+        let mut current = settings::get();
+        current.guest = !current.guest;
+        settings::set(current);
+        self.mastery = Mastery::load();
+        self.high_scores = HighScores::load();
+        self.history = MenuHistory::load();
+        self.sort_items();
+    }
     fn toggle_number_mode(&mut self) {
         self.number_mode = match self.number_mode {
             NumberMode::Unsigned => NumberMode::Signed,
@@ -425,3 +1003,24 @@ impl StartMenuState {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybinds::KeyProfile;
+
+    #[test]
+    fn a_lone_vim_g_toggles_guest_mode_once_it_times_out() {
+        let before = settings::get();
+        settings::set(settings::Settings { key_profile: KeyProfile::Vim, guest: false, ..before });
+        let mut screens = ScreenStack::default();
+        screens.push(Box::new(StartMenuState::new(AppPreferences::default())));
+        handle_message(&mut screens, Message::Key(KeyEvent::from(KeyCode::Char('g'))));
+        let waiting = settings::get().guest;
+        handle_message(&mut screens, Message::Tick(1.0));
+        let guest = settings::get().guest;
+        settings::set(before);
+        assert!(!waiting, "the g could still start a gg");
+        assert!(guest);
+    }
+}
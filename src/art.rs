@@ -0,0 +1,198 @@
+//! Art files: the characters of a banner and, optionally, how it animates.
+//!
+//! ```text
+//! # comments and blank lines may come before the first section
+//! --- art
+//!  ___   ___
+//! | _ ) | _ )
+//! --- colors
+//! diagonal_sweep(width=8, fg=highlight, bg=dim)
+//! --- chars
+//! binary_flip(width=8)
+//! ```
+//!
+//! Only `art` is required. `colors` and `chars` take the presets of the title
+//! animation (see `anim_dsl`) and win over the ones the screen would use.
+
+use crate::anim_dsl::{self, AnimSpecError};
+use crate::utils::{ColorFn, ProceduralAnimationWidget};
+use std::fmt;
+use std::time::Duration;
+
+/// Starts a section, followed by its name
+const SECTION_MARKER: &str = "--- ";
+/// Every art animation advances a frame this often
+const FRAME_DURATION: Duration = Duration::from_millis(50);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ArtError {
+    TextOutsideSection { line: usize },
+    UnknownSection { line: usize, name: String },
+    DuplicateSection { line: usize, name: String },
+    MissingArt,
+    InvalidSpec { line: usize, error: AnimSpecError },
+}
+
+impl fmt::Display for ArtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TextOutsideSection { line } => {
+                write!(f, "line {line}: text before the first '--- art' section")
+            },
+            Self::UnknownSection { line, name } => {
+                write!(f, "line {line}: unknown section '{name}', expected art, colors or chars")
+            },
+            Self::DuplicateSection { line, name } => {
+                write!(f, "line {line}: section '{name}' appears twice")
+            },
+            Self::MissingArt => write!(f, "no '--- art' section with any art in it"),
+            Self::InvalidSpec { line, error } => write!(f, "line {line}: {error}"),
+        }
+    }
+}
+
+/// A banner and the animation presets it brings along
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Art {
+    pub text: String,
+    pub colors: Option<String>,
+    pub chars: Option<String>,
+}
+
+impl Art {
+    pub fn parse(src: &str) -> Result<Self, ArtError> {
+        let mut text: Option<Vec<&str>> = None;
+        let mut colors = None;
+        let mut chars = None;
+        // The section lines are added to
+        let mut current: Option<&str> = None;
+
+        for (index, line) in src.lines().enumerate() {
+            let number = index + 1;
+            if let Some(name) = line.strip_prefix(SECTION_MARKER) {
+                let name = name.trim();
+                let taken = match name {
+                    "art" => text.replace(Vec::new()).is_some(),
+                    "colors" => colors.replace((number, String::new())).is_some(),
+                    "chars" => chars.replace((number, String::new())).is_some(),
+                    _ => {
+                        return Err(ArtError::UnknownSection {
+                            line: number,
+                            name: name.to_string(),
+                        });
+                    },
+                };
+                if taken {
+                    return Err(ArtError::DuplicateSection {
+                        line: number,
+                        name: name.to_string(),
+                    });
+                }
+                current = Some(name);
+                continue;
+            }
+            match (current, &mut text, &mut colors, &mut chars) {
+                (Some("art"), Some(lines), _, _) => lines.push(line),
+                (Some("colors"), _, Some(spec), _) | (Some("chars"), _, _, Some(spec)) => {
+                    let line = line.trim();
+                    if !line.is_empty() && !line.starts_with('#') {
+                        // The spec's own line, for errors
+                        if spec.1.is_empty() {
+                            spec.0 = number;
+                        }
+                        spec.1.push_str(line);
+                    }
+                },
+                _ if line.trim().is_empty() || line.starts_with('#') => {},
+                _ => return Err(ArtError::TextOutsideSection { line: number }),
+            }
+        }
+
+        let mut lines = text.ok_or(ArtError::MissingArt)?;
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            return Err(ArtError::MissingArt);
+        }
+        let art = Self {
+            text: lines.iter().map(|line| format!("{line}\n")).collect(),
+            colors: colors.as_ref().map(|(_, spec)| spec.clone()).filter(|spec| !spec.is_empty()),
+            chars: chars.as_ref().map(|(_, spec)| spec.clone()).filter(|spec| !spec.is_empty()),
+        };
+        art.check_specs(colors.map_or(0, |(line, _)| line), chars.map_or(0, |(line, _)| line))?;
+        Ok(art)
+    }
+
+    /// Shown in place of art that failed to load, so the problem is visible
+    pub fn error_placeholder(error: &ArtError) -> Self {
+        Self { text: format!("[broken art: {error}]\n"), colors: None, chars: None }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let width = self.text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        (width, self.text.lines().count())
+    }
+
+    fn check_specs(&self, colors_at: usize, chars_at: usize) -> Result<(), ArtError> {
+        let (width, height) = self.size();
+        if let Some(spec) = &self.colors {
+            let _ = anim_dsl::color_fn(spec, width, height)
+                .map_err(|error| ArtError::InvalidSpec { line: colors_at, error })?;
+        }
+        if let Some(spec) = &self.chars {
+            anim_dsl::char_fn(spec, width, height)
+                .map_err(|error| ArtError::InvalidSpec { line: chars_at, error })?;
+        }
+        Ok(())
+    }
+
+    /// Animate the art with its own presets, or else the given ones. A preset that
+    /// doesn't parse falls back to the built-in title animation.
+    pub fn animation(
+        &self,
+        colors: &str,
+        chars: &str,
+        num_frames: usize,
+    ) -> ProceduralAnimationWidget {
+        let (width, height) = self.size();
+        let colors = self.colors.as_deref().unwrap_or(colors);
+        let chars = self.chars.as_deref().unwrap_or(chars);
+        let color_fn: ColorFn = anim_dsl::color_fn(colors, width, height)
+            .or_else(|_| anim_dsl::color_fn(anim_dsl::DEFAULT_TITLE_COLORS, width, height))
+            .unwrap_or_else(|_| Box::new(|_, _, _, _, highlight_color| highlight_color));
+        let char_fn = anim_dsl::char_fn(chars, width, height)
+            .or_else(|_| anim_dsl::char_fn(anim_dsl::DEFAULT_TITLE_CHARS, width, height));
+
+        let mut widget =
+            ProceduralAnimationWidget::new(self.text.clone(), num_frames, FRAME_DURATION, color_fn);
+        if let Ok(Some(char_fn)) = char_fn {
+            widget = widget.with_char_fn(char_fn);
+        }
+        widget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_and_errors_point_at_their_line() {
+        let art = Art::parse("# logo\n--- art\n ab\n\n--- chars\nnone\n\n")
+            .unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(art.text, " ab\n");
+        assert_eq!((art.colors, art.chars.as_deref()), (None, Some("none")));
+
+        assert_eq!(Art::parse("logo\n--- art\nx"), Err(ArtError::TextOutsideSection { line: 1 }));
+        assert_eq!(
+            Art::parse("--- art\nx\n--- sound\n"),
+            Err(ArtError::UnknownSection { line: 3, name: "sound".to_string() })
+        );
+        assert_eq!(Art::parse("--- colors\nsolid\n"), Err(ArtError::MissingArt));
+        assert!(matches!(
+            Art::parse("--- art\nx\n--- colors\n# sweep\nsparkle\n"),
+            Err(ArtError::InvalidSpec { line: 5, error: AnimSpecError::UnknownPreset(_) })
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::art::Art;
+use crate::config::{self, Config};
+use crate::settings;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The art in the source tree, read at runtime in dev mode
+const SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/art");
+
+/// Art the game draws. Each one is compiled in and can be replaced by a file of the
+/// same name in the user's art directory.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ArtAsset {
+    Title,
+    About,
+    GameOver,
+}
+
+impl ArtAsset {
+    const ALL: [Self; 3] = [Self::Title, Self::About, Self::GameOver];
+
+    const fn file_name(self) -> &'static str {
+        match self {
+            Self::Title => "title.txt",
+            Self::About => "about.txt",
+            Self::GameOver => "game_over.txt",
+        }
+    }
+
+    const fn bundled(self) -> &'static str {
+        match self {
+            Self::Title => include_str!("../assets/art/title.txt"),
+            Self::About => include_str!("../assets/art/about.txt"),
+            Self::GameOver => include_str!("../assets/art/game_over.txt"),
+        }
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<ArtAsset, Rc<Art>>> = RefCell::new(HashMap::new());
+}
+
+/// Reskins go to `art` in the config directory
+pub fn dir() -> Option<PathBuf> {
+    Config::dir().map(|dir| dir.join("art"))
+}
+
+/// Where art files are looked for, the first one found wins. In dev mode the source
+/// tree comes after the config directory, so edits to `assets/art` show without a rebuild.
+fn dirs() -> Vec<PathBuf> {
+    let dev = settings::get().dev.then(|| PathBuf::from(SOURCE_DIR));
+    dir().into_iter().chain(dev).collect()
+}
+
+/// The art for `asset`, read once and then served from memory
+pub fn art(asset: ArtAsset) -> Rc<Art> {
+    if let Some(art) = CACHE.with(|cache| cache.borrow().get(&asset).cloned()) {
+        return art;
+    }
+    let art = Rc::new(load(&dirs(), asset));
+    CACHE.with(|cache| cache.borrow_mut().insert(asset, Rc::clone(&art)));
+    art
+}
+
+/// Forget the art read so far, the next request reads the files again
+pub fn reload() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// A file in one of `dirs` wins over the compiled-in art. One that can't be read or
+/// parsed is logged and skipped, so a broken reskin never stops the game.
+fn load(dirs: &[PathBuf], asset: ArtAsset) -> Art {
+    dirs.iter().find_map(|dir| load_file(&dir.join(asset.file_name()))).unwrap_or_else(|| {
+        Art::parse(asset.bundled()).unwrap_or_else(|err| Art::error_placeholder(&err))
+    })
+}
+
+fn load_file(path: &Path) -> Option<Art> {
+    match fs::read_to_string(path) {
+        Ok(src) => Art::parse(&src)
+            .inspect_err(|err| tracing::warn!(file = %path.display(), "broken art: {err}"))
+            .ok(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => {
+            tracing::warn!(file = %path.display(), "can't read art: {err}");
+            None
+        },
+    }
+}
+
+/// Notices when an art file is added, edited or removed, for reloading in dev mode
+pub struct ArtWatcher {
+    files: Vec<PathBuf>,
+    last_modified: Vec<Option<SystemTime>>,
+    last_check: Instant,
+}
+
+impl ArtWatcher {
+    const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+    pub fn new() -> Self {
+        let files: Vec<PathBuf> = dirs()
+            .iter()
+            .flat_map(|dir| ArtAsset::ALL.map(|asset| dir.join(asset.file_name())))
+            .collect();
+        let last_modified = Self::modified_times(&files);
+        Self { files, last_modified, last_check: Instant::now() }
+    }
+
+    /// Returns true once per change to any of the files
+    pub fn poll_changed(&mut self) -> bool {
+        if self.last_check.elapsed() < Self::CHECK_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+        let modified = Self::modified_times(&self.files);
+        if modified == self.last_modified {
+            return false;
+        }
+        self.last_modified = modified;
+        true
+    }
+
+    fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+        files.iter().map(|file| config::modified_time(file)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_art_parses() {
+        for asset in ArtAsset::ALL {
+            assert!(Art::parse(asset.bundled()).is_ok(), "{}", asset.file_name());
+        }
+    }
+
+    #[test]
+    fn files_in_the_art_directory_win_unless_broken() {
+        let dir = std::env::temp_dir().join(format!("binbreak-art-{}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        let _ = fs::write(dir.join("title.txt"), "--- art\nBB\n");
+        let _ = fs::write(dir.join("about.txt"), "no sections\n");
+
+        let dirs = [dir.clone(), PathBuf::from(SOURCE_DIR)];
+        assert_eq!(load(&dirs, ArtAsset::Title).text, "BB\n");
+        let bundled = Art::parse(ArtAsset::About.bundled()).ok();
+        assert_eq!(Some(load(&dirs, ArtAsset::About)), bundled, "the next directory's file");
+        assert_eq!(Some(load(&[], ArtAsset::About)), bundled);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
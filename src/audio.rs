@@ -0,0 +1,90 @@
+//! Sound effects for the cues of `feedback`. Built with the `sound` feature; without it,
+//! or with `sound = off` in the config, playing does nothing.
+
+use crate::feedback::Cue;
+use crate::settings;
+
+/// Start playing the sound of `cue` and return right away
+pub fn play(cue: Cue) {
+    if settings::get().sound {
+        player::play(cue);
+    }
+}
+
+#[cfg(feature = "sound")]
+mod player {
+    use crate::feedback::Cue;
+    use rodio::source::{Function, SignalGenerator, SineWave, Source};
+    use rodio::{OutputStream, Sink, cpal};
+    use std::sync::mpsc::{self, Sender};
+    use std::time::Duration;
+
+    /// Kept low, the sounds are meant to sit under whatever else is playing
+    const VOLUME: f32 = 0.2;
+
+    thread_local! {
+        /// The player thread, started with the first sound. The audio output can't move
+        /// between threads, so it lives on that one and sounds are sent to it.
+        static PLAYER: Option<Sender<Cue>> = start();
+    }
+
+    pub fn play(cue: Cue) {
+        PLAYER.with(|player| {
+            if let Some(player) = player {
+                let _ = player.send(cue);
+            }
+        });
+    }
+
+    fn start() -> Option<Sender<Cue>> {
+        let (sender, receiver) = mpsc::channel();
+        let spawned = std::thread::Builder::new().name("audio".into()).spawn(move || {
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(output) => output,
+                Err(err) => {
+                    tracing::warn!("no audio output, playing without sound: {err}");
+                    return;
+                },
+            };
+            for cue in receiver {
+                let Ok(sink) = Sink::try_new(&handle) else { continue };
+                for &(frequency, millis) in notes(cue) {
+                    let duration = Duration::from_millis(millis);
+                    if matches!(cue, Cue::Wrong | Cue::Timeout) {
+                        let buzz = SignalGenerator::new(
+                            cpal::SampleRate(48_000),
+                            frequency,
+                            Function::Square,
+                        );
+                        sink.append(buzz.take_duration(duration).amplify(VOLUME / 2.0));
+                    } else {
+                        sink.append(
+                            SineWave::new(frequency).take_duration(duration).amplify(VOLUME),
+                        );
+                    }
+                }
+                // Plays on without blocking the next sound
+                sink.detach();
+            }
+        });
+        spawned.map_err(|err| tracing::warn!("can't start the audio thread: {err}")).ok()?;
+        Some(sender)
+    }
+
+    /// Tones played one after another, in Hz and milliseconds
+    const fn notes(cue: Cue) -> &'static [(f32, u64)] {
+        match cue {
+            Cue::Correct => &[(880.0, 80), (1318.5, 140)],
+            Cue::Wrong | Cue::Timeout => &[(110.0, 280)],
+            Cue::Tick => &[(1760.0, 20)],
+            Cue::Record => &[(523.25, 110), (659.25, 110), (783.99, 110), (1046.5, 320)],
+        }
+    }
+}
+
+#[cfg(not(feature = "sound"))]
+mod player {
+    use crate::feedback::Cue;
+
+    pub const fn play(_cue: Cue) {}
+}
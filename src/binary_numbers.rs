@@ -1,170 +1,351 @@
-use crate::app::{NumberMode, get_mode_color};
+use crate::anim_dsl;
+use crate::animation::AnimationSequence;
+use crate::app::get_mode_color;
+use crate::art::Art;
+use crate::assets::{self, ArtAsset};
+use crate::cheat_sheet::CheatSheet;
+use crate::effects::{Easing, Flash, ParticleField, Shake};
+use crate::feedback::{self, Cue};
+use crate::game_mode::GameMode;
 use crate::keybinds;
+use crate::keybinds::KeyHint;
 use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
-use crate::utils::{When, center};
-use crossterm::event::{KeyCode, KeyEvent};
-use rand::Rng;
-use rand::prelude::SliceRandom;
+use crate::mastery::{MASTERY_SPEED_SECS, Mastery};
+use crate::playlist::Playlist;
+use crate::responsive::LayoutMode;
+use crate::review::MistakeReview;
+use crate::run_history::RunHistory;
+use crate::settings;
+use crate::stats::{self, Stats};
+use crate::storage;
+use crate::summary::{Answer, RunSummary};
+use crate::theme;
+use crate::toast;
+use crate::utils::{
+    BigText, Dialog, DialogFocus, Gradient, LedRow, ProgressBorder, SevenSegment, TimerBar,
+    Typewriter, When, binary_spans, center, vertically_center,
+};
+use crate::worksheet::QuestionList;
+use binbreak::scoring;
+use binbreak::{Bits, Dealer, NumberMode, Question, Run};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use ratatui::buffer::Buffer;
-use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Margin, Position, Rect, Size};
 use ratatui::prelude::Alignment::Center;
 use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
 use ratatui::style::Modifier;
 use ratatui::text::Span;
 use ratatui::widgets::BorderType::Double;
-use ratatui::widgets::{Block, BorderType, Paragraph};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Write as _;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::time::Duration;
 
 struct StatsSnapshot {
     score: u32,
     streak: u32,
     max_streak: u32,
     rounds: u32,
-    lives: u32,
     bits: Bits,
     number_mode: NumberMode,
     hearts: String,
-    game_state: GameState,
     prev_high_score: u32,
     new_high_score: bool,
+    practice: bool,
+    can_undo: bool,
+    /// Number of the question and questions in all of a playlist or question list
+    playlist: Option<(usize, usize)>,
+}
+
+impl BinaryNumbersGame {
+    /// Content is 65x21, surrounded by a one-cell progress border and a margin
+    const FULL_SIZE: Size = Size::new(69, 23);
+    /// Stacked content of 50x17 in the border, without a margin
+    const COMPACT_SIZE: Size = Size::new(52, 19);
 }
 
 impl WidgetRef for BinaryNumbersGame {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        let [game_column] = Layout::horizontal([Constraint::Length(65)])
+        let (frame_size, margin) = if LayoutMode::for_area(area, Self::FULL_SIZE).is_compact() {
+            (Self::COMPACT_SIZE, 0)
+        } else {
+            (Size::new(Self::FULL_SIZE.width - 2, Self::FULL_SIZE.height), 1)
+        };
+        // An open cheat sheet goes right of the frame if there is room, the recent answers
+        // left of it if there is room for them too, all centered together
+        let history = RunHistory::new(&self.answers, &self.bits);
+        let sheet_width = self.cheat_sheet.as_ref().map_or(0, |sheet| sheet.size().width);
+        let room = area.width.saturating_sub(frame_size.width + 2 * margin);
+        let beside = sheet_width > 0 && room > sheet_width;
+        let room = if beside { room - sheet_width - 1 } else { room };
+        let history_width = history.size().width;
+        let show_history = room > history_width;
+        let [history_column, _, frame_column, _, sheet_column] = Layout::horizontal([
+            Constraint::Length(if show_history { history_width } else { 0 }),
+            Constraint::Length(u16::from(show_history)),
+            Constraint::Length(frame_size.width),
+            Constraint::Length(u16::from(beside)),
+            Constraint::Length(if beside { sheet_width } else { 0 }),
+        ])
+        .flex(Flex::Center)
+        .horizontal_margin(margin)
+        .areas(area);
+        if show_history {
+            let [history_area] = Layout::vertical([Constraint::Length(history.size().height)])
+                .flex(Flex::Center)
+                .areas(history_column);
+            history.render(history_area, buf);
+        }
+        let [frame] = Layout::vertical([Constraint::Length(frame_size.height)])
             .flex(Flex::Center)
-            .horizontal_margin(1)
-            .areas(area);
-
-        self.puzzle.render_ref(game_column, buf);
+            .areas(frame_column);
+        let frame = self.shake.apply(frame, area);
+
+        let (ratio, color) = self.run_progress();
+        ProgressBorder::new(ratio, color, theme::current().muted).render(frame, buf);
+
+        let game_column = frame.inner(Margin::new(1, 1));
+        if let Some(label) = self.countdown_label() {
+            // Keep the first question hidden until the countdown is over
+            let theme = theme::current();
+            BigText::new(label, theme.accent)
+                .with_gradient(Gradient::diagonal(&theme.highlight_stops()))
+                .render_centered(game_column, buf);
+        } else {
+            match (&self.review, &self.summary) {
+                (Some(review), _) => review.render(game_column, buf),
+                (None, Some(summary)) => {
+                    render_summary(summary, self.game_over_banner.as_ref(), game_column, buf);
+                },
+                (None, None) => {
+                    self.puzzle.render_in(self, game_column, buf);
+                    if let Some(sheet) = &self.cheat_sheet {
+                        let column = if beside { sheet_column } else { game_column };
+                        render_cheat_sheet(sheet, column, buf);
+                    }
+                },
+            }
+        }
+        self.flash.render(game_column, buf);
+        self.confetti.render(area, buf);
+
+        match self.overlay {
+            Some(Overlay::ConfirmQuit) => Dialog::confirm("Quit run?")
+                .body(&["Progress will be lost."])
+                .focus(self.overlay_focus)
+                .render(frame, buf),
+            Some(Overlay::Paused) => {
+                Dialog::new("Paused", Overlay::Paused.choices(self.restart))
+                    .focus(self.overlay_focus)
+                    .render(frame, buf);
+            },
+            None => {},
+        }
     }
 }
 
-impl WidgetRef for BinaryNumbersPuzzle {
-    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+impl BinaryNumbersPuzzle {
+    /// Draw the puzzle around the question, which the game mode draws itself
+    fn render_in(&self, mode: &dyn GameMode, area: Rect, buf: &mut Buffer) {
+        let layout = LayoutMode::for_area(area, Size::new(65, 21));
         let [middle] =
             Layout::horizontal([Constraint::Percentage(100)]).flex(Flex::Center).areas(area);
 
+        // Compact drops the instructions (the footer has them) and stacks status and timer
+        let heights = if layout.is_compact() {
+            [3, 3, 3, 8, 0]
+        } else {
+            [4, 5, 3, 4, 5]
+        };
         let [stats_area, current_number_area, suggestions_area, progress_bar_area, result_area] =
-            Layout::vertical([
-                Constraint::Length(4),
-                Constraint::Length(5),
-                Constraint::Length(3),
-                Constraint::Length(4),
-                Constraint::Length(5),
-            ])
-            .flex(Flex::Center)
-            .horizontal_margin(0)
-            .areas(middle);
+            Layout::vertical(heights.map(Constraint::Length))
+                .flex(Flex::Center)
+                .horizontal_margin(0)
+                .areas(middle);
 
-        self.render_stats_area(stats_area, buf);
+        self.render_stats_area(stats_area, buf, layout);
 
-        if let Some(stats) = &self.stats_snapshot
-            && stats.game_state == GameState::GameOver
-        {
-            render_game_over(
-                stats,
-                current_number_area,
-                suggestions_area,
-                progress_bar_area,
-                result_area,
-                buf,
-            );
+        mode.render_question(current_number_area, buf);
+        self.render_timer_bar(current_number_area, buf);
+        self.render_suggestions(suggestions_area, buf);
+        self.render_status_and_timer(progress_bar_area, buf, layout);
+        if layout.is_compact() {
             return;
         }
-
-        self.render_current_number(current_number_area, buf);
-        self.render_suggestions(suggestions_area, buf);
-        self.render_status_and_timer(progress_bar_area, buf);
-        self.render_instructions(result_area, buf);
+        match self.guess_result {
+            Some(result) => self.render_answer(result, result_area, buf),
+            None => self.render_instructions(result_area, buf),
+        }
     }
 }
 
 impl BinaryNumbersPuzzle {
-    fn render_stats_area(&self, area: Rect, buf: &mut Buffer) {
-        Block::bordered().title_alignment(Center).dark_gray().render(area, buf);
+    fn render_stats_area(&self, area: Rect, buf: &mut Buffer, layout: LayoutMode) {
+        let theme = theme::current();
+        if !layout.is_compact() {
+            Block::bordered().title_alignment(Center).fg(theme.muted).render(area, buf);
+        }
 
         if let Some(stats) = &self.stats_snapshot {
             let high_label = if stats.new_high_score {
-                let style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
+                let style =
+                    Style::default().fg(theme.correct_highlight).add_modifier(Modifier::BOLD);
                 Span::styled(format!("Hi-Score: {}*  ", stats.score), style)
             } else {
-                let style = Style::default().fg(Color::DarkGray);
+                let style = Style::default().fg(theme.muted);
                 Span::styled(format!("Hi-Score: {}  ", stats.prev_high_score), style)
             };
 
             let mode_color = get_mode_color(&stats.bits);
-            let mode_label = format!("{} {}", stats.bits.label(), stats.number_mode.label());
-            let line1 = Line::from(vec![
-                Span::styled(format!("Mode: {}  ", mode_label), Style::default().fg(mode_color)),
-                high_label,
-            ]);
-
-            let line2 = Line::from(vec![
-                Span::styled(
-                    format!("Score: {}  ", stats.score),
-                    Style::default().fg(Color::Green),
-                ),
+            let mut mode_label = format!("{} {}", stats.bits.label(), stats.number_mode.label());
+            if stats.practice {
+                mode_label.push_str(" PRACTICE");
+            }
+            if let Some((question, total)) = stats.playlist {
+                let _ = write!(mode_label, " {question}/{total}");
+            }
+            let mode_span =
+                Span::styled(format!("Mode: {}  ", mode_label), Style::default().fg(mode_color));
+            let guest_label = if layout.is_compact() {
+                "GUEST"
+            } else {
+                "GUEST (not saved)"
+            };
+            let guest_span = settings::get()
+                .guest
+                .then(|| Span::styled(guest_label, Style::default().fg(theme.accent)));
+            let score_span =
+                Span::styled(format!("Score: {}  ", stats.score), Style::default().fg(theme.score));
+            let progress_spans = vec![
                 Span::styled(
                     format!("Streak: {}  ", stats.streak),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.streak),
                 ),
                 Span::styled(
                     format!("Max: {}  ", stats.max_streak),
-                    Style::default().fg(Color::Blue),
+                    Style::default().fg(theme.max_streak),
                 ),
                 Span::styled(
                     format!("Rounds: {}  ", stats.rounds),
-                    Style::default().fg(Color::Magenta),
+                    Style::default().fg(theme.rounds),
+                ),
+                Span::styled(
+                    format!("Lives: {}  ", stats.hearts),
+                    Style::default().fg(theme.lives),
                 ),
-                Span::styled(format!("Lives: {}  ", stats.hearts), Style::default().fg(Color::Red)),
-            ]);
+            ];
+
+            let lines: Vec<Line> = if layout.is_compact() {
+                vec![
+                    Line::from_iter([Some(mode_span), guest_span].into_iter().flatten()),
+                    Line::from(vec![score_span, high_label]),
+                    Line::from(progress_spans),
+                ]
+            } else {
+                let mut progress_spans = progress_spans;
+                progress_spans.insert(0, score_span);
+                vec![
+                    Line::from_iter(
+                        [Some(mode_span), Some(high_label), guest_span].into_iter().flatten(),
+                    ),
+                    Line::from(progress_spans),
+                ]
+            };
 
             #[allow(clippy::cast_possible_truncation)]
-            let widest = line1.width().max(line2.width()) as u16;
-            Paragraph::new(vec![line1, line2])
-                .alignment(Center)
-                .render(center(area, Constraint::Length(widest)), buf);
+            let widest = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+            // Compact stats fill their whole area, there is no border to center within
+            let stats_area = if layout.is_compact() {
+                let [column] =
+                    Layout::horizontal([Constraint::Length(widest)]).flex(Flex::Center).areas(area);
+                column
+            } else {
+                center(area, Constraint::Length(widest))
+            };
+            Paragraph::new(lines).alignment(Center).render(stats_area, buf);
         }
     }
 
     fn render_current_number(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
         let [inner] =
             Layout::horizontal([Constraint::Percentage(100)]).flex(Flex::Center).areas(area);
 
         Block::bordered()
             .border_type(Double)
-            .border_style(Style::default().dark_gray())
+            .border_style(Style::default().fg(theme.muted))
             .render(inner, buf);
 
-        let binary_string = self.current_to_binary_string();
         let scale_suffix = match self.bits {
             Bits::FourShift4 => Some(" x16"),
             Bits::FourShift8 => Some(" x256"),
             Bits::FourShift12 => Some(" x4096"),
             _ => None,
         };
-        let mut spans = vec![Span::raw(binary_string)];
+        let nibble_colors = [theme.mode_color(&self.bits), theme.accent];
+        let mut spans = binary_spans(&self.question.nibbles(), nibble_colors);
         if let Some(sfx) = scale_suffix {
-            spans.push(Span::styled(sfx, Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(sfx, Style::default().fg(theme.muted)));
         }
         #[allow(clippy::cast_possible_truncation)]
         let total_width = spans.iter().map(ratatui::prelude::Span::width).sum::<usize>() as u16;
         let lines: Vec<Line> = vec![Line::from(spans)];
-        Paragraph::new(lines)
-            .alignment(Center)
-            .render(center(inner, Constraint::Length(total_width)), buf);
+
+        // With room to spare the bits light up underneath, labeled if they fit
+        let content = Block::bordered().inner(inner);
+        let shift = self.bits.scale_factor().trailing_zeros();
+        let leds = [true, false]
+            .map(|labels| {
+                LedRow::new(self.question.pattern, self.bits.to_int())
+                    .first_bit(shift)
+                    .colors(nibble_colors[0], nibble_colors[1])
+                    .labels(labels)
+            })
+            .into_iter()
+            .find(|leds| leds.height() < content.height && leds.width() <= content.width);
+        let Some(leds) = leds else {
+            Paragraph::new(lines)
+                .alignment(Center)
+                .render(center(inner, Constraint::Length(total_width)), buf);
+            return;
+        };
+        let [number_row, led_row] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(leds.height())])
+                .flex(Flex::Center)
+                .areas(content);
+        Paragraph::new(lines).alignment(Center).render(number_row, buf);
+        let [led_cell] = Layout::horizontal([Constraint::Length(leds.width())])
+            .flex(Flex::Center)
+            .areas(led_row);
+        leds.render(led_cell, buf);
+    }
+
+    /// Time left along the bottom edge of the question, or in practice the time taken
+    /// so far against the pace that counts towards mastery
+    fn render_timer_bar(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let (ratio, color) = if self.timed {
+            let ratio = self.time_left / self.time_total;
+            (ratio, theme.timer_color(ratio))
+        } else {
+            (self.elapsed / MASTERY_SPEED_SECS, theme.mode_color(&self.bits))
+        };
+        let edge =
+            Rect::new(area.x + 1, area.bottom().saturating_sub(1), area.width.saturating_sub(2), 1);
+        TimerBar::new(ratio, color).render(edge, buf);
     }
 
     fn render_suggestions(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
         let suggestions = self.suggestions();
         let suggestions_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![Constraint::Min(6); suggestions.len()])
             .split(area);
+        self.suggestion_areas.replace(suggestions_layout.to_vec());
 
         for (i, suggestion) in suggestions.iter().enumerate() {
             let item_is_selected = self.selected_suggestion == Some(*suggestion);
@@ -180,56 +361,104 @@ impl BinaryNumbersPuzzle {
 
             let border_color = if item_is_selected {
                 match self.guess_result {
-                    Some(GuessResult::Correct) => Color::Green,
-                    Some(GuessResult::Incorrect) => Color::Red,
-                    Some(GuessResult::Timeout) => Color::Yellow,
-                    None => Color::LightCyan,
+                    Some(GuessResult::Correct) => theme.correct,
+                    Some(GuessResult::Incorrect) => theme.incorrect,
+                    Some(GuessResult::Timeout) => theme.timeout,
+                    None => theme.selection,
                 }
             } else {
-                Color::DarkGray
+                theme.muted
             };
 
-            Block::bordered().border_type(border_type).fg(border_color).render(area, buf);
+            // Numbered for answering with the number keys
+            let block = Block::bordered().border_type(border_type).fg(border_color);
+            let block = if i < 9 {
+                block.title(format!("{}", i + 1))
+            } else {
+                block
+            };
+            block.render(area, buf);
 
-            let suggestion_str = format!("{suggestion}");
+            // Without colors, mark the selection explicitly
+            let suggestion_str = if theme.monochrome && item_is_selected {
+                format!("> {suggestion} <")
+            } else {
+                format!("{suggestion}")
+            };
 
             #[allow(clippy::cast_possible_truncation)]
             Paragraph::new(suggestion_str.to_string())
-                .white()
-                .when(show_correct_number && is_correct_number, |p| p.light_green().underlined())
+                .fg(theme.text)
+                .when(show_correct_number && is_correct_number, |p| {
+                    p.fg(theme.correct_highlight).underlined()
+                })
                 .alignment(Center)
                 .render(center(area, Constraint::Length(suggestion_str.len() as u16)), buf);
         }
     }
 
-    fn render_status_and_timer(&self, area: Rect, buf: &mut Buffer) {
-        let [left, right] = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .areas(area);
+    fn render_status_and_timer(&self, area: Rect, buf: &mut Buffer, layout: LayoutMode) {
+        let [left, right] = if layout.is_compact() {
+            Layout::vertical([Constraint::Length(4), Constraint::Length(4)]).areas(area)
+        } else {
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area)
+        };
 
         self.render_status(left, buf);
         self.render_timer(right, buf);
     }
 
     fn render_status(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
         Block::bordered()
-            .dark_gray()
+            .fg(theme.muted)
             .title("Status")
             .title_alignment(Center)
-            .title_style(Style::default().white())
+            .title_style(Style::default().fg(theme.text))
             .render(area, buf);
 
+        if self.hints_used > 0
+            && self.hints_shown != HintsShown::Hidden
+            && self.guess_result.is_none()
+        {
+            let text: Vec<Line> = (0..self.hints_used)
+                .filter_map(|level| self.question.hint(level))
+                .map(|hint| Line::from(hint.fg(theme.accent)))
+                .collect();
+            #[allow(clippy::cast_possible_truncation)]
+            let widest = text.iter().map(Line::width).max().unwrap_or(0) as u16;
+            Paragraph::new(text)
+                .alignment(Center)
+                .render(center(area, Constraint::Length(widest)), buf);
+            return;
+        }
+
+        if self.undo_notice && self.guess_result.is_none() {
+            let text = vec![
+                Line::from("<- answer undone".fg(theme.accent)),
+                Line::from("try again".fg(theme.accent)),
+            ];
+            Paragraph::new(text)
+                .alignment(Center)
+                .render(center(area, Constraint::Length(16)), buf);
+            return;
+        }
+
         if let Some(result) = &self.guess_result {
             let (icon, line1_text, color) = match result {
-                GuessResult::Correct => (":)", "success", Color::Green),
-                GuessResult::Incorrect => (":(", "incorrect", Color::Red),
-                GuessResult::Timeout => (":(", "time's up", Color::Yellow),
+                GuessResult::Correct => (":)", "success", theme.correct),
+                GuessResult::Incorrect => (":(", "incorrect", theme.incorrect),
+                GuessResult::Timeout => (":(", "time's up", theme.timeout),
             };
 
             let gained_line = match result {
+                GuessResult::Correct if self.hints_used > 0 => {
+                    format!("gained {} points, hinted", self.last_points_awarded)
+                },
                 GuessResult::Correct => format!("gained {} points", self.last_points_awarded),
+                GuessResult::Incorrect if !self.timed => "no lives lost".to_string(),
                 GuessResult::Incorrect => "lost a life".to_string(),
+                GuessResult::Timeout if !self.timed => "skipped".to_string(),
                 GuessResult::Timeout => "timeout".to_string(),
             };
 
@@ -247,49 +476,102 @@ impl BinaryNumbersPuzzle {
     }
 
     fn render_timer(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
         let ratio = self.time_left / self.time_total;
-        let gauge_color = if ratio > 0.6 {
-            Color::Green
-        } else if ratio > 0.3 {
-            Color::Yellow
-        } else {
-            Color::Red
-        };
+        let gauge_color = theme.timer_color(ratio);
 
-        let time_block = Block::bordered()
-            .dark_gray()
-            .title("Time Remaining")
-            .title_style(Style::default().white())
-            .title_alignment(Center);
+        if !self.timed {
+            let time_block = Self::timer_block(Block::bordered());
+            let inner_time = time_block.inner(area);
+            time_block.render(area, buf);
+            Paragraph::new(Line::from(Span::styled("practice: untimed", theme.muted)))
+                .alignment(Center)
+                .render(vertically_center(inner_time), buf);
+            return;
+        }
+
+        // Seconds and hundredths, the colon blinks off for the second half of each second
+        // unless motion is reduced
+        let time_left = self.time_left.max(0.0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let hundredths = (time_left * 100.0) as u32;
+        let text = format!("{:02}:{:02}", hundredths / 100, hundredths % 100);
+        let colon = settings::get().reduce_motion || time_left.ceil() - time_left < 0.5;
+        let digits = SevenSegment::new(&text).style(gauge_color).colon(colon);
+
+        // The digits take the row of the bottom border and replace the gauge, the frame
+        // around the game shows the remaining time as well
+        let time_block = Self::timer_block(Block::new().borders(Borders::TOP));
         let inner_time = time_block.inner(area);
+        if inner_time.height < SevenSegment::HEIGHT || inner_time.width < digits.width() {
+            Self::timer_block(Block::bordered()).render(area, buf);
+            Paragraph::new(Line::from(Span::styled(
+                format!("{:.2} seconds left", self.time_left),
+                Style::default().fg(gauge_color),
+            )))
+            .alignment(Center)
+            .render(vertically_center(Block::bordered().inner(area)), buf);
+            return;
+        }
         time_block.render(area, buf);
+        let [row] = Layout::vertical([Constraint::Length(SevenSegment::HEIGHT)])
+            .flex(Flex::Center)
+            .areas(inner_time);
+        let [cell] =
+            Layout::horizontal([Constraint::Length(digits.width())]).flex(Flex::Center).areas(row);
+        digits.render(cell, buf);
+    }
 
-        let [gauge_line, time_line] =
-            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner_time);
-
-        render_ascii_gauge(gauge_line, buf, ratio, gauge_color);
+    fn timer_block(block: Block) -> Block {
+        let theme = theme::current();
+        block
+            .fg(theme.muted)
+            .title("Time Remaining")
+            .title_style(Style::default().fg(theme.text))
+            .title_alignment(Center)
+    }
 
-        Paragraph::new(Line::from(Span::styled(
-            format!("{:.2} seconds left", self.time_left),
-            Style::default().fg(gauge_color),
-        )))
-        .alignment(Center)
-        .render(time_line, buf);
+    /// The decimal value in big digits once the question is resolved, in the result's color
+    fn render_answer(&self, result: GuessResult, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let color = match result {
+            GuessResult::Correct => theme.correct,
+            GuessResult::Incorrect => theme.incorrect,
+            GuessResult::Timeout => theme.timeout,
+        };
+        if result != GuessResult::Correct && settings::get().explain_mistakes {
+            // How the bits add up to the right answer, in place of the answer alone
+            let [area] = Layout::vertical([Constraint::Length(2)]).flex(Flex::Center).areas(area);
+            Paragraph::new(Line::styled(self.question.breakdown(), Style::default().fg(color)))
+                .alignment(Center)
+                .wrap(Wrap { trim: true })
+                .render(area, buf);
+            return;
+        }
+        BigText::new(&self.question.answer.to_string(), color).render_centered(area, buf);
     }
 
     fn render_instructions(&self, area: Rect, buf: &mut Buffer) {
-        Block::bordered().dark_gray().render(area, buf);
-
-        let instruction_spans: Vec<Span> = [
-            hotkey_span("Left Right", "select  "),
-            hotkey_span("Enter", "confirm  "),
-            hotkey_span("S", "skip  "),
-            hotkey_span("Esc", "exit"),
-        ]
-        .iter()
-        .flatten()
-        .cloned()
-        .collect();
+        Block::bordered().fg(theme::current().muted).render(area, buf);
+
+        use keybinds::{EXIT, LEFT, RIGHT, SELECT, SKIP, UNDO};
+        let can_undo = self.stats_snapshot.as_ref().is_some_and(|s| s.can_undo);
+        let mut hotkeys = vec![
+            KeyHint::new(&[LEFT, RIGHT], "select"),
+            KeyHint::new(&[SELECT], "confirm"),
+            KeyHint::new(&[SKIP], "skip"),
+        ];
+        if can_undo {
+            hotkeys.push(KeyHint::new(&[UNDO], "undo"));
+        }
+        hotkeys.push(KeyHint::new(&[EXIT], "exit"));
+        let mut instruction_spans = Vec::new();
+        for (index, hint) in hotkeys.iter().enumerate() {
+            if index > 0 {
+                instruction_spans.push(Span::raw("  "));
+            }
+            instruction_spans.extend(hotkey_span(hint));
+        }
 
         Paragraph::new(vec![Line::from(instruction_spans)])
             .alignment(Center)
@@ -297,73 +579,105 @@ impl BinaryNumbersPuzzle {
     }
 }
 
-fn hotkey_span<'a>(key: &'a str, description: &str) -> Vec<Span<'a>> {
+/// `<key> action`, with the keys bound in the layout of the config, like the footer
+fn hotkey_span(hint: &KeyHint) -> Vec<Span<'static>> {
+    let theme = theme::current();
     vec![
-        Span::styled("<", Style::default().fg(Color::White)),
-        Span::styled(key, Style::default().fg(Color::LightCyan)),
-        Span::styled(format!("> {description}"), Style::default().fg(Color::White)),
+        Span::styled("<", Style::default().fg(theme.text)),
+        Span::styled(hint.keys(), Style::default().fg(theme.hotkey)),
+        Span::styled(format!("> {}", hint.action), Style::default().fg(theme.text)),
     ]
 }
 
-fn render_game_over(
-    stats: &StatsSnapshot,
-    current_number_area: Rect,
-    suggestions_area: Rect,
-    progress_bar_area: Rect,
-    result_area: Rect,
+/// The cheat sheet vertically centered at the right of `column`: its own column beside the
+/// game, or the game itself where the screen is too narrow
+fn render_cheat_sheet(sheet: &CheatSheet, column: Rect, buf: &mut Buffer) {
+    let size = sheet.size();
+    let [row] =
+        Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+    let [cell] = Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::End).areas(row);
+    sheet.render(cell, buf);
+}
+
+/// The summary of a finished run in place of the puzzle, below the banner of a lost run
+/// where there is room for both
+fn render_summary(
+    summary: &RunSummary,
+    banner: Option<&GameOverBanner>,
+    area: Rect,
     buf: &mut Buffer,
 ) {
-    let combined_rect = Rect {
-        x: current_number_area.x,
-        y: current_number_area.y,
-        width: current_number_area.width,
-        height: current_number_area.height
-            + suggestions_area.height
-            + progress_bar_area.height
-            + result_area.height,
-    };
-    Block::bordered().border_style(Style::default().fg(Color::DarkGray)).render(combined_rect, buf);
-
-    let mut lines = vec![
-        Line::from(Span::styled(
-            format!("Final Score: {}", stats.score),
-            Style::default().fg(Color::Green),
-        )),
-        Line::from(Span::styled(
-            format!("Previous High: {}", stats.prev_high_score),
-            Style::default().fg(Color::Yellow),
-        )),
-        Line::from(Span::styled(
-            format!("Rounds Played: {}", stats.rounds),
-            Style::default().fg(Color::Magenta),
-        )),
-        Line::from(Span::styled(
-            format!("Max Streak: {}", stats.max_streak),
-            Style::default().fg(Color::Cyan),
-        )),
-    ];
-    if stats.new_high_score {
-        lines.insert(
-            1,
-            Line::from(Span::styled(
-                "NEW HIGH SCORE!",
-                Style::default().fg(Color::LightGreen).bold(),
-            )),
+    let block = Block::bordered()
+        .title(" Run Summary ")
+        .title_alignment(Center)
+        .border_style(Style::default().fg(theme::current().muted));
+    let mut inner = block.inner(area);
+    block.render(area, buf);
+    let mut typewriter = None;
+    if let Some(banner) = banner {
+        let (width, height) = banner.animation.size();
+        if inner.height > summary.height() + height {
+            let [banner_row, rest] =
+                Layout::vertical([Constraint::Length(height + 1), Constraint::Fill(1)])
+                    .areas(inner);
+            let [banner_area] = Layout::horizontal([Constraint::Length(width)])
+                .flex(Flex::Center)
+                .areas(banner_row);
+            banner.animation.render_to_buffer(banner_area, buf);
+            if !banner.summary_shown {
+                return;
+            }
+            inner = rest;
+        }
+        typewriter = Some(&banner.summary);
+    }
+    summary.render(typewriter, inner, buf);
+}
+
+/// Banner above the summary of a lost run. Binary digits resolve into it first; once
+/// they have, the summary appears and the banner's own animation loops.
+struct GameOverBanner {
+    animation: AnimationSequence,
+    summary_shown: bool,
+    /// Types out the summary once it is shown
+    summary: Typewriter,
+}
+
+impl GameOverBanner {
+    fn new() -> Self {
+        let art = assets::art(ArtAsset::GameOver);
+        let reveal = Art { colors: None, chars: None, ..(*art).clone() };
+        let mut animation = AnimationSequence::default().then(reveal.animation(
+            "solid",
+            "binary_reveal(width=8)",
+            20,
+        ));
+        animation.set_highlight_color(theme::current().incorrect);
+        // Without motion there is no reveal to wait for
+        let summary_shown = !animation.is_animating();
+        Self { animation, summary_shown, summary: Self::typewriter() }
+    }
+
+    fn typewriter() -> Typewriter {
+        Typewriter::new(80.0)
+    }
+
+    /// Something stopped moving; the summary waits for the end of the reveal, not a pause.
+    /// Returns true if this showed the summary.
+    fn reveal_finished(&mut self) -> bool {
+        if self.summary_shown || !self.animation.is_finished() {
+            return false;
+        }
+        let art = assets::art(ArtAsset::GameOver);
+        self.animation = AnimationSequence::default().then_loop(
+            art.animation(anim_dsl::DEFAULT_TITLE_COLORS, "none", 40)
+                .with_pause_at_end(Duration::from_secs(2)),
         );
+        self.animation.set_highlight_color(theme::current().incorrect);
+        self.summary_shown = true;
+        self.summary = Self::typewriter();
+        true
     }
-    if stats.lives == 0 {
-        lines.push(Line::from(Span::styled(
-            "You lost all your lives.",
-            Style::default().fg(Color::Red),
-        )));
-    }
-    lines.push(Line::from(Span::styled(
-        "Press Enter to restart or Esc to exit",
-        Style::default().fg(Color::Yellow),
-    )));
-    Paragraph::new(lines)
-        .alignment(Center)
-        .render(center(combined_rect, Constraint::Length(48)), buf);
 }
 
 pub struct BinaryNumbersGame {
@@ -371,17 +685,112 @@ pub struct BinaryNumbersGame {
     bits: Bits,
     number_mode: NumberMode,
     exit_intended: bool,
-    score: u32,
-    streak: u32,
-    rounds: u32,
+    run: Run,
     puzzle_resolved: bool,
-    lives: u32,
-    max_lives: u32,
     game_state: GameState,
-    max_streak: u32,
     high_scores: HighScores,
     prev_high_score_for_display: u32,
     new_high_score_reached: bool,
+    practice: bool,
+    answer_history: Vec<AnsweredRound>,
+    /// Every question of the run with the answer given, skips and timeouts included
+    answers: Vec<Answer>,
+    /// Shown in place of the puzzle once the run is over
+    summary: Option<RunSummary>,
+    /// Opened from the summary, shown in its place
+    review: Option<MistakeReview>,
+    /// Open next to the puzzle until toggled off again
+    cheat_sheet: Option<CheatSheet>,
+    mastery: Mastery,
+    stats: Stats,
+    /// Dialog shown over the game; the timer is paused while one is open
+    overlay: Option<Overlay>,
+    /// The button of the dialog that Enter presses
+    overlay_focus: DialogFocus,
+    /// Animated banner above the summary of a lost run
+    game_over_banner: Option<GameOverBanner>,
+    /// Thrown on streak milestones and when a run ends with a new high score
+    confetti: ParticleField,
+    /// Jolts the game sideways after a wrong answer
+    shake: Shake,
+    /// Tints the game green or red after each answer
+    flash: Flash,
+    /// Length of the 3-2-1-GO countdown before each run, 0 for none
+    countdown_secs: f64,
+    /// Seconds left of the current countdown
+    countdown: f64,
+    /// Source of every question; seeded runs replay the same sequence on each restart.
+    /// Boxed, the generator state would otherwise double the size of the game
+    rng: Box<StdRng>,
+    /// Keeps recent answers from coming up again
+    dealer: Dealer,
+    /// Whether the config's dealing settings apply
+    dealing: Dealing,
+    restart: Restart,
+    /// Modes to play one after another, the run ends after the last one
+    playlist: Option<Playlist>,
+    /// Questions from a file, asked in order instead of dealt; the run ends after the last
+    questions: Option<QuestionList>,
+    seed: Option<u64>,
+}
+
+/// Seconds each step of the pre-run countdown is shown
+const COUNTDOWN_STEP_SECS: f64 = 0.7;
+
+/// Seconds at the end of a question that tick with sound on
+const LAST_SECONDS: f64 = 3.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Overlay {
+    ConfirmQuit,
+    Paused,
+}
+
+impl Overlay {
+    const fn choices(self, restart: Restart) -> &'static [KeyHint] {
+        match (self, restart) {
+            (Self::ConfirmQuit, _) => Dialog::CONFIRM_CHOICES,
+            (Self::Paused, Restart::Allowed) => PAUSE_CHOICES,
+            (Self::Paused, Restart::Never) => PAUSE_CHOICES_WITHOUT_RESTART,
+        }
+    }
+}
+
+const PAUSE_CHOICES: &[KeyHint] = &[
+    KeyHint::new(&[keybinds::PAUSE], "resume"),
+    KeyHint::new(&[keybinds::RESTART], "restart"),
+    KeyHint::new(&[keybinds::EXIT], "quit"),
+];
+
+const PAUSE_CHOICES_WITHOUT_RESTART: &[KeyHint] =
+    &[KeyHint::new(&[keybinds::PAUSE], "resume"), KeyHint::new(&[keybinds::EXIT], "quit")];
+
+/// Everything needed to take back a submitted answer in practice mode:
+/// the stats before the round was scored and the question that was asked.
+struct AnsweredRound {
+    run: Run,
+    question: Question,
+    /// The stats before the answer was counted
+    stats: Stats,
+    /// The mastery key of the mode the answer mastered its value in for the first time;
+    /// a playlist may have moved on to another mode by the time it is undone
+    mastered: Option<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Dealing {
+    /// By the dealing settings of the config
+    Configured,
+    /// By the built-in defaults, the same for every player
+    Standard,
+}
+
+/// Whether a run can be started over, from the pause dialog or its summary
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Restart {
+    Allowed,
+    /// The run is played once, like a race or the daily challenge
+    Never,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -395,10 +804,26 @@ enum GameState {
 impl MainScreenWidget for BinaryNumbersGame {
     fn run(&mut self, dt: f64) {
         self.refresh_stats_snapshot();
-        if self.game_state == GameState::GameOver {
+        if self.game_state == GameState::GameOver || self.overlay.is_some() {
+            return;
+        }
+        if self.countdown > 0.0 {
+            let step = self.countdown_label();
+            self.countdown -= dt;
+            if self.countdown_label() != step {
+                feedback::give(Cue::Tick);
+            }
             return;
         }
+        let seconds_left = self.puzzle.time_left.ceil();
         self.puzzle.run(dt);
+        // The last seconds of a question tick away
+        if self.puzzle.guess_result.is_none()
+            && self.puzzle.time_left.ceil() < seconds_left
+            && seconds_left <= LAST_SECONDS
+        {
+            feedback::give(Cue::Tick);
+        }
         if self.puzzle.guess_result.is_some() && !self.puzzle_resolved {
             self.finalize_round();
         }
@@ -408,9 +833,122 @@ impl MainScreenWidget for BinaryNumbersGame {
     fn handle_input(&mut self, input: KeyEvent) {
         self.handle_game_input(input);
     }
+    fn handle_key_release(&mut self, input: KeyEvent) {
+        if keybinds::pressed(input, keybinds::PEEK)
+            && self.puzzle.hints_shown == HintsShown::Peeking
+        {
+            self.puzzle.hints_shown = HintsShown::Hidden;
+        }
+    }
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        self.handle_game_mouse(mouse);
+    }
+    fn handle_paste(&mut self, text: &str) {
+        self.handle_game_paste(text);
+    }
     fn is_exit_intended(&self) -> bool {
         self.exit_intended
     }
+    fn min_size(&self) -> Size {
+        Self::COMPACT_SIZE
+    }
+    /// Timer running or the game over banner moving, needs continuous updates
+    fn is_animating(&self) -> bool {
+        self.is_active()
+            || self.game_over_banner.as_ref().is_some_and(|banner| banner.animation.is_animating())
+            || self.review.as_ref().is_some_and(MistakeReview::is_animating)
+            || self.confetti.is_active()
+            || self.shake.is_active()
+            || self.flash.is_active()
+    }
+    fn animation_finished(&mut self) {
+        let revealed = self.game_over_banner.as_mut().is_some_and(GameOverBanner::reveal_finished);
+        if revealed && self.new_high_score_reached {
+            self.celebrate();
+        }
+    }
+    /// High scores and mastery are written as they change; a closed app records where the
+    /// run stopped and makes sure a new best score made it to disk
+    fn save(&mut self) {
+        if !self.run_in_progress() {
+            return;
+        }
+        tracing::info!(
+            score = self.run.score,
+            rounds = self.run.rounds,
+            lives = self.run.lives,
+            "run interrupted"
+        );
+        if self.new_high_score_reached {
+            let _ = self.high_scores.save();
+        }
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{
+            CANCEL, CHEAT_SHEET, CONFIRM, EXIT, HINT, LEFT, PAUSE, PEEK, RIGHT, SELECT, SKIP, UNDO,
+        };
+        if self.countdown > 0.0 {
+            return vec![KeyHint::new(&[EXIT], "menu")];
+        }
+        match self.overlay {
+            Some(Overlay::ConfirmQuit) => {
+                return vec![
+                    KeyHint::new(&[CONFIRM], "quit"),
+                    KeyHint::new(&[CANCEL], "keep playing"),
+                ];
+            },
+            Some(Overlay::Paused) => return Overlay::Paused.choices(self.restart).to_vec(),
+            None => {},
+        }
+        let mut hints = match self.game_state {
+            GameState::GameOver => return self.summary_key_hints(),
+            GameState::PendingGameOver => vec![KeyHint::new(&[SELECT], "summary")],
+            GameState::Result if self.run_finished() => {
+                vec![KeyHint::new(&[SELECT], "summary")]
+            },
+            GameState::Result => vec![KeyHint::new(&[SELECT], "next")],
+            GameState::Active => vec![
+                KeyHint::new(&[LEFT, RIGHT], "choose"),
+                KeyHint::new(&[SELECT], "confirm"),
+                KeyHint::new(&[SKIP], "skip"),
+                KeyHint::new(&[HINT], "hint"),
+                KeyHint::new(&[PAUSE], "pause"),
+            ],
+        };
+        if self.game_state == GameState::Active && settings::get().key_releases {
+            hints.insert(4, KeyHint::new(&[PEEK], "hold to peek"));
+        }
+        if self.cheat_sheet_allowed() && self.game_state != GameState::PendingGameOver {
+            hints.push(KeyHint::new(&[CHEAT_SHEET], "bits"));
+        }
+        if self.practice && !self.answer_history.is_empty() {
+            hints.push(KeyHint::new(&[UNDO], "undo"));
+        }
+        hints.push(KeyHint::new(&[EXIT], "menu"));
+        hints
+    }
+}
+
+impl GameMode for BinaryNumbersGame {
+    fn next_question(&mut self) -> Question {
+        match self.questions.as_ref().and_then(|list| list.get(self.answers.len())) {
+            Some(listed) => listed.question.clone(),
+            None => self.dealer.deal(&mut *self.rng),
+        }
+    }
+
+    fn is_correct(&self, guess: i32) -> bool {
+        self.puzzle.is_correct_guess(guess)
+    }
+
+    fn render_question(&self, area: Rect, buf: &mut Buffer) {
+        self.puzzle.render_current_number(area, buf);
+    }
+
+    fn is_active(&self) -> bool {
+        self.game_state == GameState::Active
+    }
 }
 
 impl BinaryNumbersGame {
@@ -421,33 +959,187 @@ impl BinaryNumbersGame {
         let hs = HighScores::load();
         let high_score_key = Self::compute_high_score_key(&bits, number_mode);
         let starting_prev = hs.get(&high_score_key);
+        let mut rng = Box::new(StdRng::from_os_rng());
+        let mut dealer = Dealer::new(bits.clone(), number_mode)
+            .no_repeat(settings::get().no_repeat)
+            .choices(settings::get().choices.of(&bits))
+            .distractors(settings::get().distractors)
+            .filter(settings::value_filter());
         let mut game = Self {
             bits: bits.clone(),
             number_mode,
-            puzzle: Self::init_puzzle(bits, number_mode, 0),
+            puzzle: Self::init_puzzle(bits, &mut dealer, &mut *rng),
             exit_intended: false,
-            score: 0,
-            streak: 0,
-            rounds: 0,
+            run: Run::new(max_lives),
             puzzle_resolved: false,
-            lives: max_lives.min(3),
-            max_lives,
             game_state: GameState::Active,
-            max_streak: 0,
             high_scores: hs,
             prev_high_score_for_display: starting_prev,
             new_high_score_reached: false,
+            practice: false,
+            answer_history: Vec::new(),
+            answers: Vec::new(),
+            summary: None,
+            review: None,
+            cheat_sheet: None,
+            mastery: Mastery::load(),
+            stats: Stats::load(),
+            overlay: None,
+            overlay_focus: DialogFocus::default(),
+            game_over_banner: None,
+            confetti: ParticleField::confetti(),
+            shake: Shake::default(),
+            flash: Flash::new(Duration::from_millis(300), Easing::EaseOut),
+            countdown_secs: 0.0,
+            countdown: 0.0,
+            rng,
+            dealer,
+            dealing: Dealing::Configured,
+            restart: Restart::Allowed,
+            playlist: None,
+            questions: None,
+            seed: None,
         };
         // Initialize stats snapshot immediately so stats display on first render
         game.refresh_stats_snapshot();
+        game.warn_if_filtered_out();
         game
     }
 
-    pub fn init_puzzle(bits: Bits, number_mode: NumberMode, streak: u32) -> BinaryNumbersPuzzle {
-        BinaryNumbersPuzzle::new(bits, number_mode, streak)
+    /// The dealer ignores a value filter that leaves nothing of the mode to ask, say so
+    fn warn_if_filtered_out(&self) {
+        let filter = settings::value_filter();
+        if self.dealing == Dealing::Configured
+            && filter.is_active()
+            && self.questions.is_none()
+            && filter.patterns(&self.bits, self.number_mode).is_none()
+        {
+            toast::show("only_values and skip_values leave no number here, asking all");
+        }
+    }
+
+    /// Untimed practice: no lives are lost, high scores aren't recorded and answers can be undone
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self.restart_questions();
+        self.refresh_stats_snapshot();
+        self
+    }
+
+    /// Deal from the whole value space evenly instead of at random
+    pub fn with_coverage(mut self, coverage: bool) -> Self {
+        self.dealer = self.dealer.coverage(coverage);
+        self.restart_questions();
+        self
+    }
+
+    /// Play the sections of a playlist back to back as one run, ending after the last.
+    /// Such runs mix modes, so they don't count towards high scores.
+    pub fn with_playlist(mut self, playlist: Playlist) -> Self {
+        self.playlist = Some(playlist);
+        self.restart_questions();
+        self.refresh_stats_snapshot();
+        self
+    }
+
+    /// Ask the questions of a list in order, ending the run after the last. Like playlists
+    /// they can mix modes, so they don't count towards high scores either.
+    pub fn with_questions(mut self, questions: QuestionList) -> Self {
+        self.questions = Some(questions);
+        self.restart_questions();
+        self.refresh_stats_snapshot();
+        self
+    }
+
+    /// Generate questions from a fixed seed, so a run can be replayed or raced
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.restart_questions();
+        self
+    }
+
+    /// Deal as the game does out of the box, ignoring `choices`, `distractors`,
+    /// `no_repeat` and the value filter of the config, so a run shared with other
+    /// players, like a race or the daily challenge, asks each of them the same
+    pub fn with_standard_questions(mut self) -> Self {
+        self.dealing = Dealing::Standard;
+        self.dealer = Dealer::new(self.bits.clone(), self.number_mode);
+        self.restart_questions();
+        self
+    }
+
+    /// Whether the run can be started over; runs played once, like a race, can't
+    pub const fn with_restart(mut self, allowed: bool) -> Self {
+        self.restart = if allowed { Restart::Allowed } else { Restart::Never };
+        self
+    }
+
+    /// Rewind a seeded question sequence and deal its first puzzle
+    fn restart_questions(&mut self) {
+        if let Some(seed) = self.seed {
+            *self.rng = StdRng::seed_from_u64(seed);
+            self.dealer.reset();
+        }
+        self.puzzle = self.next_puzzle(0);
+    }
+
+    /// Show a 3-2-1-GO countdown before each timed run, so reacting to the
+    /// screen switch doesn't eat into the first question's time
+    pub fn with_countdown(mut self) -> Self {
+        if !self.practice {
+            self.countdown_secs = 4.0 * COUNTDOWN_STEP_SECS;
+            self.countdown = self.countdown_secs;
+        }
+        self
+    }
+
+    /// The countdown step to show, None once the run has started
+    fn countdown_label(&self) -> Option<&'static str> {
+        match self.countdown / COUNTDOWN_STEP_SECS {
+            r if r > 3.0 => Some("3"),
+            r if r > 2.0 => Some("2"),
+            r if r > 1.0 => Some("1"),
+            r if r > 0.0 => Some("GO!"),
+            _ => None,
+        }
+    }
+
+    pub fn init_puzzle(bits: Bits, dealer: &mut Dealer, rng: &mut impl Rng) -> BinaryNumbersPuzzle {
+        BinaryNumbersPuzzle::from_question(bits, dealer.deal(rng), 0)
+    }
+
+    fn next_puzzle(&mut self, streak: u32) -> BinaryNumbersPuzzle {
+        // Each section of a playlist, or question of a list, switches to its own mode
+        let index = self.answers.len();
+        let mode = match (&self.playlist, &self.questions) {
+            (Some(playlist), _) => playlist.section_at(index).map(|s| (&s.bits, s.number_mode)),
+            (_, Some(list)) => list.get(index).map(|q| (&q.question.bits, q.number_mode)),
+            (None, None) => None,
+        };
+        if let Some((bits, number_mode)) = mode
+            && (*bits != self.bits || number_mode != self.number_mode)
+        {
+            self.bits = bits.clone();
+            self.number_mode = number_mode;
+            let choices = match self.dealing {
+                Dealing::Configured => settings::get().choices.of(&self.bits),
+                Dealing::Standard => self.bits.suggestion_count(),
+            };
+            self.dealer.set_mode(self.bits.clone(), self.number_mode, choices);
+            self.warn_if_filtered_out();
+            self.cheat_sheet = self
+                .cheat_sheet
+                .take()
+                .map(|_| CheatSheet::new(&self.bits, self.number_mode))
+                .filter(|_| self.cheat_sheet_allowed());
+        }
+        let mut puzzle =
+            BinaryNumbersPuzzle::from_question(self.bits.clone(), self.next_question(), streak);
+        puzzle.cheat_sheet_seen = self.cheat_sheet.is_some();
+        if self.practice { puzzle.untimed() } else { puzzle }
     }
 
-    fn compute_high_score_key(bits: &Bits, number_mode: NumberMode) -> String {
+    pub fn compute_high_score_key(bits: &Bits, number_mode: NumberMode) -> String {
         let bits_key = bits.high_score_key();
         let mode_suffix = match number_mode {
             NumberMode::Unsigned => "u",
@@ -456,58 +1148,94 @@ impl BinaryNumbersGame {
         format!("{}{}", bits_key, mode_suffix)
     }
 
-    pub fn is_active(&self) -> bool {
-        self.game_state == GameState::Active
+    /// Progress shown by the border around the game, with its fill color:
+    /// time used on the current question, or questions answered in blocks of ten
+    /// when untimed
+    fn run_progress(&self) -> (f64, Color) {
+        let theme = theme::current();
+        if self.practice {
+            let answered = self.run.rounds % 10;
+            (f64::from(answered) / 10.0, theme.mode_color(&self.bits))
+        } else {
+            let time_ratio = (self.puzzle.time_left / self.puzzle.time_total).clamp(0.0, 1.0);
+            (1.0 - time_ratio, theme.timer_color(time_ratio))
+        }
     }
 }
 
 impl BinaryNumbersGame {
     pub fn lives_hearts(&self) -> String {
-        let full_count = self.lives.min(self.max_lives) as usize;
+        let full_count = self.run.lives.min(self.run.max_lives) as usize;
         let full = "♥".repeat(full_count);
-        let empty_count = self.max_lives.saturating_sub(self.lives) as usize;
+        let empty_count = self.run.max_lives.saturating_sub(self.run.lives) as usize;
         let empty = "·".repeat(empty_count);
         format!("{full}{empty}")
     }
 
     fn finalize_round(&mut self) {
         if let Some(result) = self.puzzle.guess_result {
-            self.rounds += 1;
+            if self.practice {
+                self.answer_history.push(AnsweredRound {
+                    run: self.run.clone(),
+                    question: self.puzzle.question.clone(),
+                    stats: self.stats.clone(),
+                    mastered: None,
+                });
+            }
+            self.record_answer(result);
+            let theme = theme::current();
+            self.flash.start(if result == GuessResult::Correct {
+                theme.correct
+            } else {
+                theme.incorrect
+            });
+            feedback::give(match result {
+                GuessResult::Correct => Cue::Correct,
+                GuessResult::Incorrect => Cue::Wrong,
+                GuessResult::Timeout => Cue::Timeout,
+            });
             match result {
                 GuessResult::Correct => {
-                    self.streak += 1;
-                    if self.streak > self.max_streak {
-                        self.max_streak = self.streak;
-                    }
-                    let streak_bonus = (self.streak - 1) * 2;
-                    let points = 10 + streak_bonus;
-                    self.score += points;
-                    self.puzzle.last_points_awarded = points;
-                    if self.streak.is_multiple_of(5) && self.lives < self.max_lives {
-                        self.lives += 1;
+                    self.puzzle.last_points_awarded =
+                        self.run.correct_with_hints(self.puzzle.hints_used);
+                    self.record_mastery();
+                    if self.run.streak.is_multiple_of(scoring::LIFE_EVERY_STREAK) {
+                        self.celebrate();
                     }
                 },
                 GuessResult::Incorrect | GuessResult::Timeout => {
-                    self.streak = 0;
-                    self.puzzle.last_points_awarded = 0;
-                    if self.lives > 0 {
-                        self.lives -= 1;
+                    if result == GuessResult::Incorrect {
+                        self.shake.start();
                     }
+                    self.run.incorrect(!self.practice);
+                    self.puzzle.last_points_awarded = 0;
                 },
             }
-            // high score update
+            // high score update (practice runs don't count)
             let bits_key = Self::compute_high_score_key(&self.bits, self.number_mode);
+            tracing::info!(
+                mode = bits_key,
+                answer = self.puzzle.question.answer,
+                result = ?result,
+                score = self.run.score,
+                lives = self.run.lives,
+                "answer"
+            );
             let prev = self.high_scores.get(&bits_key);
-            if self.score > prev {
+            if self.run.score > prev && !self.practice && self.run_length().is_none() {
                 if !self.new_high_score_reached {
                     self.prev_high_score_for_display = prev;
+                    if prev > 0 {
+                        toast::show("New personal best!");
+                        feedback::give(Cue::Record);
+                    }
                 }
-                self.high_scores.update(&bits_key, self.score);
+                self.high_scores.update(&bits_key, self.run.score);
                 self.new_high_score_reached = true;
                 let _ = self.high_scores.save();
             }
             // set state after round resolution
-            if self.lives == 0 {
+            if self.run.is_over() {
                 self.game_state = GameState::PendingGameOver; // defer summary until Enter
             } else {
                 self.game_state = GameState::Result;
@@ -517,8 +1245,27 @@ impl BinaryNumbersGame {
     }
 
     pub fn handle_game_input(&mut self, input: KeyEvent) {
+        if self.countdown > 0.0 {
+            // Only leaving is possible until the run starts
+            self.exit_intended = keybinds::is_exit(input);
+            return;
+        }
+        if let Some(overlay) = self.overlay {
+            let Some(key) = self.overlay_focus.handle_input(input, overlay.choices(self.restart))
+            else {
+                return;
+            };
+            return match overlay {
+                Overlay::ConfirmQuit => self.handle_confirm_quit_input(key),
+                Overlay::Paused => self.handle_pause_input(key),
+            };
+        }
         if keybinds::is_exit(input) {
-            self.exit_intended = true;
+            if self.run_in_progress() {
+                self.open_overlay(Some(Overlay::ConfirmQuit));
+            } else {
+                self.exit_intended = true;
+            }
             return;
         }
 
@@ -526,99 +1273,370 @@ impl BinaryNumbersGame {
             self.handle_game_over_input(input);
             return;
         }
+        if keybinds::pressed(input, keybinds::PAUSE) {
+            self.open_overlay(Some(Overlay::Paused));
+            return;
+        }
+        if keybinds::pressed(input, keybinds::CHEAT_SHEET) && self.cheat_sheet_allowed() {
+            self.toggle_cheat_sheet();
+            return;
+        }
         match self.puzzle.guess_result {
             None => self.handle_no_result_yet(input),
             Some(_) => self.handle_result_available(input),
         }
     }
 
-    fn handle_game_over_input(&mut self, key: KeyEvent) {
-        match key {
-            x if keybinds::is_select(x) => {
-                self.reset_game_state();
-            },
-            x if keybinds::is_exit(x) => {
-                self.exit_intended = true;
-            },
-            _ => {},
-        }
+    /// A burst of confetti from just above the question
+    fn celebrate(&mut self) {
+        let theme = theme::current();
+        let colors = [theme.accent, theme.correct_highlight, theme.streak, theme.max_streak];
+        self.confetti.burst(0.0, -4.0, 40, &colors, &mut rand::rng());
     }
 
-    fn reset_game_state(&mut self) {
-        self.score = 0;
-        self.streak = 0;
-        self.rounds = 0;
-        self.lives = self.max_lives.min(3);
-        self.game_state = GameState::Active;
-        self.max_streak = 0;
-        let high_score_key = Self::compute_high_score_key(&self.bits, self.number_mode);
-        self.prev_high_score_for_display = self.high_scores.get(&high_score_key);
-        self.new_high_score_reached = false;
-        self.puzzle = Self::init_puzzle(self.bits.clone(), self.number_mode, 0);
-        self.puzzle_resolved = false;
-        self.refresh_stats_snapshot();
+    /// Keys of the summary, or of the mistake review opened from it
+    fn summary_key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{EXIT, LEFT, RESTART, REVIEW, RIGHT, SELECT};
+        if self.review.is_some() {
+            return vec![
+                KeyHint::new(&[LEFT, RIGHT], "step"),
+                KeyHint::new(&[SELECT], "show me"),
+                KeyHint::new(&[EXIT], "summary"),
+            ];
+        }
+        let mut hints = Vec::new();
+        if self.restart == Restart::Allowed {
+            hints.push(KeyHint::new(&[RESTART], "play again"));
+        }
+        if self.answers.iter().any(|answer| !answer.is_correct()) {
+            hints.push(KeyHint::new(&[REVIEW], "mistakes"));
+        }
+        hints.push(KeyHint::new(&[EXIT], "change mode"));
+        hints
+    }
+
+    /// Keep the answer for the summary and the stats, which count practice runs too
+    fn record_answer(&mut self, result: GuessResult) {
+        self.answers.push(Answer {
+            question: self.puzzle.question.clone(),
+            guess: self.puzzle.selected_suggestion.filter(|_| result != GuessResult::Timeout),
+            secs: self.puzzle.elapsed,
+        });
+        let times: Vec<f64> = self.answers.iter().map(|answer| answer.secs).collect();
+        let cheated = self.cheat_sheet.is_some() || self.puzzle.cheat_sheet_seen;
+        self.stats.record(stats::today(), result == GuessResult::Correct, cheated, &times);
+        let _ = self.stats.save();
+    }
+
+    fn cheat_sheet_allowed(&self) -> bool {
+        settings::get().cheat_sheet.contains(&self.bits)
+    }
+
+    /// Show or hide the bit values next to the puzzle; answers given with them count
+    /// as such in the stats
+    fn toggle_cheat_sheet(&mut self) {
+        if self.cheat_sheet.take().is_none() {
+            self.cheat_sheet = Some(CheatSheet::new(&self.bits, self.number_mode));
+            self.puzzle.cheat_sheet_seen = true;
+        }
+    }
+
+    /// Questions in a run of a playlist or a question list, None for endless runs
+    fn run_length(&self) -> Option<usize> {
+        match (&self.playlist, &self.questions) {
+            (Some(playlist), _) => Some(playlist.total()),
+            (_, Some(list)) => Some(list.total()),
+            (None, None) => None,
+        }
+    }
+
+    /// Every question of the playlist or list was answered
+    fn run_finished(&self) -> bool {
+        self.run_length().is_some_and(|length| self.answers.len() >= length)
+    }
+
+    /// End the run and show its summary in place of the puzzle
+    fn show_summary(&mut self, banner: Option<GameOverBanner>) {
+        self.overlay = None;
+        self.game_state = GameState::GameOver;
+        self.game_over_banner = banner;
+        self.summary = Some(RunSummary::new(
+            &self.run,
+            &self.answers,
+            self.prev_high_score_for_display,
+            self.new_high_score_reached,
+        ));
+    }
+
+    /// Questions answered so far, skips and timeouts included
+    pub fn answered(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub const fn score(&self) -> u32 {
+        self.run.score
+    }
+
+    /// The run ended and its summary is shown
+    pub fn is_over(&self) -> bool {
+        self.game_state == GameState::GameOver
+    }
+
+    /// The summary of a run that is over, taken out of the game
+    pub const fn take_summary(&mut self) -> Option<RunSummary> {
+        self.summary.take()
+    }
+
+    /// Correct answers given quickly enough count towards mastering the mode
+    fn record_mastery(&mut self) {
+        if self.puzzle.elapsed > MASTERY_SPEED_SECS {
+            return;
+        }
+        let key = Self::compute_high_score_key(&self.bits, self.number_mode);
+        let value = self.puzzle.question.pattern as usize;
+        if self.mastery.record(&key, self.bits.value_space(), value) {
+            let _ = self.mastery.save();
+            // Practice answers can be undone, and with them what they mastered
+            if let Some(round) = self.answer_history.last_mut() {
+                round.mastered = Some(key);
+            }
+        }
+    }
+
+    /// Take back the most recent answer: roll the stats back and ask that question again
+    fn undo_last_answer(&mut self) {
+        let Some(round) = self.answer_history.pop() else {
+            return;
+        };
+        self.run = round.run;
+        self.answers.pop();
+        self.stats = round.stats;
+        let _ = self.stats.save();
+        if let Some(key) = round.mastered {
+            self.mastery.forget(&key, round.question.pattern as usize);
+            let _ = self.mastery.save();
+        }
+        self.puzzle = self.next_puzzle(self.run.streak);
+        self.puzzle.set_question(round.question);
+        self.puzzle.undo_notice = true;
+        self.puzzle_resolved = false;
+        self.game_state = GameState::Active;
+        self.refresh_stats_snapshot();
+    }
+
+    /// Leaving would discard something: at least one round was played and the run isn't over
+    fn run_in_progress(&self) -> bool {
+        self.run.rounds > 0 && self.game_state != GameState::GameOver
+    }
+
+    /// Open a dialog, or close it with `None`, with the focus on its first button
+    fn open_overlay(&mut self, overlay: Option<Overlay>) {
+        self.overlay = overlay;
+        self.overlay_focus = DialogFocus::default();
+    }
+
+    fn handle_pause_input(&mut self, key: KeyEvent) {
+        match key {
+            x if keybinds::pressed(x, keybinds::PAUSE) => self.overlay = None,
+            x if keybinds::pressed(x, keybinds::RESTART) && self.restart == Restart::Allowed => {
+                self.reset_game_state();
+            },
+            x if keybinds::is_exit(x) => {
+                self.open_overlay(self.run_in_progress().then_some(Overlay::ConfirmQuit));
+                self.exit_intended = self.overlay.is_none();
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_confirm_quit_input(&mut self, key: KeyEvent) {
+        match key {
+            // A run with answers ends on its summary instead of going straight to the menu
+            x if keybinds::pressed(x, keybinds::CONFIRM) => {
+                if self.answers.is_empty() {
+                    self.exit_intended = true;
+                } else {
+                    self.show_summary(None);
+                }
+            },
+            x if keybinds::pressed(x, keybinds::CANCEL) || keybinds::is_exit(x) => {
+                self.overlay = None;
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_game_over_input(&mut self, key: KeyEvent) {
+        if let Some(banner) = &mut self.game_over_banner
+            && banner.summary.is_typing()
+        {
+            // Any key shows the rest of the summary
+            banner.summary.skip();
+            return;
+        }
+        if let Some(review) = &mut self.review {
+            review.handle_input(key);
+            if review.is_closed() {
+                self.review = None;
+            }
+            return;
+        }
+        match key {
+            x if keybinds::pressed(x, keybinds::REVIEW) => {
+                self.review = MistakeReview::new(&self.answers);
+            },
+            x if (keybinds::is_select(x) || keybinds::pressed(x, keybinds::RESTART))
+                && self.restart == Restart::Allowed =>
+            {
+                self.reset_game_state();
+            },
+            x if keybinds::is_exit(x) => {
+                self.exit_intended = true;
+            },
+            _ => {},
+        }
+    }
+
+    /// Start a fresh run in the same mode
+    fn reset_game_state(&mut self) {
+        self.overlay = None;
+        self.game_over_banner = None;
+        self.confetti.clear();
+        self.countdown = self.countdown_secs;
+        self.run = Run::new(self.run.max_lives);
+        self.game_state = GameState::Active;
+        let high_score_key = Self::compute_high_score_key(&self.bits, self.number_mode);
+        self.prev_high_score_for_display = self.high_scores.get(&high_score_key);
+        self.new_high_score_reached = false;
+        // Playlists and lists start over from their first question, which goes by the answers
+        self.answers.clear();
+        self.restart_questions();
+        self.puzzle_resolved = false;
+        self.answer_history.clear();
+        self.summary = None;
+        self.review = None;
+        self.refresh_stats_snapshot();
     }
 
     fn handle_no_result_yet(&mut self, input: KeyEvent) {
+        let choices = &self.puzzle.question.choices;
+        if let Some(&choice) = keybinds::choice_index(input).and_then(|index| choices.get(index)) {
+            self.puzzle.selected_suggestion = Some(choice);
+            self.submit_selected();
+            return;
+        }
         match input {
             x if keybinds::is_right(x) => {
                 // select the next suggestion
                 if let Some(selected) = self.puzzle.selected_suggestion {
-                    let current_index = self.puzzle.suggestions.iter().position(|&x| x == selected);
+                    let current_index =
+                        self.puzzle.question.choices.iter().position(|&x| x == selected);
                     if let Some(index) = current_index {
-                        let next_index = (index + 1) % self.puzzle.suggestions.len();
-                        self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[next_index]);
+                        let next_index = (index + 1) % self.puzzle.question.choices.len();
+                        self.puzzle.selected_suggestion =
+                            Some(self.puzzle.question.choices[next_index]);
                     }
                 } else {
                     // if no suggestion is selected, select the first one
-                    self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[0]);
+                    self.puzzle.selected_suggestion = Some(self.puzzle.question.choices[0]);
                 }
             },
             x if keybinds::is_left(x) => {
                 // select the previous suggestion
                 if let Some(selected) = self.puzzle.selected_suggestion {
-                    let current_index = self.puzzle.suggestions.iter().position(|&x| x == selected);
+                    let current_index =
+                        self.puzzle.question.choices.iter().position(|&x| x == selected);
                     if let Some(index) = current_index {
                         let prev_index = if index == 0 {
-                            self.puzzle.suggestions.len() - 1
+                            self.puzzle.question.choices.len() - 1
                         } else {
                             index - 1
                         };
-                        self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[prev_index]);
+                        self.puzzle.selected_suggestion =
+                            Some(self.puzzle.question.choices[prev_index]);
                     }
                 }
             },
-            x if keybinds::is_select(x) => {
-                if let Some(selected) = self.puzzle.selected_suggestion {
-                    if self.puzzle.is_correct_guess(selected) {
-                        self.puzzle.guess_result = Some(GuessResult::Correct);
-                    } else {
-                        self.puzzle.guess_result = Some(GuessResult::Incorrect);
-                    }
-                    self.finalize_round();
-                }
-            },
-            KeyEvent { code: KeyCode::Char('s' | 'S'), .. } => {
+            x if keybinds::is_select(x) => self.submit_selected(),
+            x if keybinds::pressed(x, keybinds::SKIP) => {
                 // Skip puzzle counts as timeout
                 self.puzzle.guess_result = Some(GuessResult::Timeout);
                 self.finalize_round();
             },
+            x if self.practice && keybinds::pressed(x, keybinds::UNDO) => {
+                self.undo_last_answer();
+            },
+            x if keybinds::pressed(x, keybinds::HINT) => {
+                self.puzzle.take_hint();
+                self.puzzle.hints_shown = HintsShown::Pinned;
+            },
+            x if settings::get().key_releases && keybinds::pressed(x, keybinds::PEEK) => {
+                self.puzzle.peek();
+            },
             _ => {},
         }
     }
 
+    fn submit_selected(&mut self) {
+        if let Some(selected) = self.puzzle.selected_suggestion {
+            if self.is_correct(selected) {
+                self.puzzle.guess_result = Some(GuessResult::Correct);
+            } else {
+                self.puzzle.guess_result = Some(GuessResult::Incorrect);
+            }
+            self.finalize_round();
+        }
+    }
+
+    /// A question is up and waiting for its answer
+    fn is_answering(&self) -> bool {
+        self.countdown <= 0.0
+            && self.overlay.is_none()
+            && self.game_state == GameState::Active
+            && self.puzzle.guess_result.is_none()
+    }
+
+    /// Clicking a suggestion selects it, clicking the selected one submits it
+    fn handle_game_mouse(&mut self, mouse: MouseEvent) {
+        if !self.is_answering() || mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        let Some(suggestion) = self.puzzle.suggestion_at(mouse.column, mouse.row) else {
+            return;
+        };
+        if self.puzzle.selected_suggestion == Some(suggestion) {
+            self.submit_selected();
+        } else {
+            self.puzzle.selected_suggestion = Some(suggestion);
+        }
+    }
+
+    /// Pasting one of the choices answers with it
+    fn handle_game_paste(&mut self, text: &str) {
+        if !self.is_answering() {
+            return;
+        }
+        let text = text.trim();
+        let Some(choice) = text.parse().ok().filter(|n| self.puzzle.question.choices.contains(n))
+        else {
+            toast::show(format!("{text} is not one of the choices"));
+            return;
+        };
+        self.puzzle.selected_suggestion = Some(choice);
+        self.submit_selected();
+    }
+
     fn handle_result_available(&mut self, key: KeyEvent) {
         match key {
             x if keybinds::is_select(x) => {
                 match self.game_state {
                     GameState::PendingGameOver => {
-                        // reveal summary
-                        self.game_state = GameState::GameOver;
+                        self.show_summary(Some(GameOverBanner::new()));
                     },
+                    GameState::Result if self.run_finished() => self.show_summary(None),
                     GameState::Result => {
                         // start next puzzle
-                        self.puzzle =
-                            Self::init_puzzle(self.bits.clone(), self.number_mode, self.streak);
+                        self.puzzle = self.next_puzzle(self.run.streak);
                         self.puzzle_resolved = false;
                         self.game_state = GameState::Active;
                     },
@@ -627,23 +1645,29 @@ impl BinaryNumbersGame {
                 }
             },
             x if keybinds::is_exit(x) => self.exit_intended = true,
+            x if self.practice && keybinds::pressed(x, keybinds::UNDO) => {
+                self.undo_last_answer();
+            },
             _ => {},
         }
     }
 
     fn refresh_stats_snapshot(&mut self) {
         self.puzzle.stats_snapshot = Some(StatsSnapshot {
-            score: self.score,
-            streak: self.streak,
-            max_streak: self.max_streak,
-            rounds: self.rounds,
-            lives: self.lives,
+            score: self.run.score,
+            streak: self.run.streak,
+            max_streak: self.run.max_streak,
+            rounds: self.run.rounds,
             bits: self.bits.clone(),
             number_mode: self.number_mode,
             hearts: self.lives_hearts(),
-            game_state: self.game_state,
             prev_high_score: self.prev_high_score_for_display,
             new_high_score: self.new_high_score_reached,
+            practice: self.practice,
+            can_undo: self.practice && !self.answer_history.is_empty(),
+            playlist: self
+                .run_length()
+                .map(|length| ((self.answers.len() + 1).min(length), length)),
         });
     }
 }
@@ -655,84 +1679,18 @@ enum GuessResult {
     Timeout,
 }
 
-#[derive(Clone)]
-pub enum Bits {
-    Four,
-    FourShift4,
-    FourShift8,
-    FourShift12,
-    Eight,
-    Twelve,
-    Sixteen,
-}
-
-impl Bits {
-    pub const fn to_int(&self) -> u32 {
-        match self {
-            Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 4,
-            Self::Eight => 8,
-            Self::Twelve => 12,
-            Self::Sixteen => 16,
-        }
-    }
-    pub const fn scale_factor(&self) -> u32 {
-        match self {
-            Self::Four => 1,
-            Self::FourShift4 => 16,
-            Self::FourShift8 => 256,
-            Self::FourShift12 => 4096,
-            Self::Eight => 1,
-            Self::Twelve => 1,
-            Self::Sixteen => 1,
-        }
-    }
-    pub const fn high_score_key(&self) -> u32 {
-        match self {
-            Self::Four => 4,
-            Self::FourShift4 => 44,
-            Self::FourShift8 => 48,
-            Self::FourShift12 => 412,
-            Self::Eight => 8,
-            Self::Twelve => 12,
-            Self::Sixteen => 16,
-        }
-    }
-    pub const fn upper_bound(&self) -> u32 {
-        (u32::pow(2, self.to_int()) - 1) * self.scale_factor()
-    }
-    pub const fn suggestion_count(&self) -> usize {
-        match self {
-            Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 3,
-            Self::Eight => 4,
-            Self::Twelve => 5,
-            Self::Sixteen => 6,
-        }
-    }
-    pub const fn label(&self) -> &'static str {
-        match self {
-            Self::Four => "4 bit",
-            Self::FourShift4 => "4 bit*16",
-            Self::FourShift8 => "4 bit*256",
-            Self::FourShift12 => "4 bit*4096",
-            Self::Eight => "8 bit",
-            Self::Twelve => "12 bit",
-            Self::Sixteen => "16 bit",
-        }
-    }
+/// Hints stay on screen once asked for with the hint key, peeked ones only while the
+/// peek key is held
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum HintsShown {
+    Hidden,
+    Peeking,
+    Pinned,
 }
 
 pub struct BinaryNumbersPuzzle {
     bits: Bits,
-    /// Raw bit pattern (unscaled) for display as binary string.
-    /// This is u32 (not i32) because it stores the BIT PATTERN, not the numeric value.
-    /// In signed mode, negative numbers use two's complement representation:
-    /// - For -1 in 4-bit: raw_current_number = 15 (0b1111), displayed as "1111"
-    /// - For -8 in 4-bit: raw_current_number = 8 (0b1000), displayed as "1000"
-    ///
-    /// The same bit pattern has different meanings in signed vs unsigned mode.
-    raw_current_number: u32,
-    suggestions: Vec<i32>,
-    correct_answer: i32,
+    question: Question,
     selected_suggestion: Option<i32>,
     time_total: f64,
     time_left: f64,
@@ -740,88 +1698,38 @@ pub struct BinaryNumbersPuzzle {
     last_points_awarded: u32,
     stats_snapshot: Option<StatsSnapshot>,
     skip_first_dt: bool, // Skip first dt to prevent timer jump when starting new puzzle
+    timed: bool,
+    undo_notice: bool, // Set when this question was restored by undoing an answer
+    /// Hints taken on this question, each one halves its points
+    hints_used: u32,
+    /// Whether the hints taken are on screen
+    hints_shown: HintsShown,
+    /// The cheat sheet was open at some point while this question was asked
+    cheat_sheet_seen: bool,
+    elapsed: f64, // Seconds spent on this question so far, also counted when untimed
+    /// Where each suggestion was last drawn, for mouse hit-testing
+    suggestion_areas: RefCell<Vec<Rect>>,
 }
 
 impl BinaryNumbersPuzzle {
-    pub fn new(bits: Bits, number_mode: NumberMode, streak: u32) -> Self {
-        let mut rng = rand::rng();
-
-        let mut suggestions = Vec::new();
-        let scale = bits.scale_factor();
-        let num_bits = bits.to_int();
-
-        match number_mode {
-            NumberMode::Unsigned => {
-                while suggestions.len() < bits.suggestion_count() {
-                    let raw = rng.random_range(0..u32::pow(2, num_bits));
-                    let num = (raw * scale) as i32;
-                    if !suggestions.contains(&num) {
-                        suggestions.push(num);
-                    }
-                }
-            },
-            NumberMode::Signed => {
-                // For signed mode, use two's complement representation
-                // Range is from -(2^(n-1)) to 2^(n-1)-1
-                while suggestions.len() < bits.suggestion_count() {
-                    let raw = rng.random_range(0..u32::pow(2, num_bits));
-                    // Convert raw bits to signed value using two's complement
-                    let signed_val = if raw >= (1 << (num_bits - 1)) {
-                        // Negative number: raw - 2^n
-                        (raw as i32) - (1 << num_bits)
-                    } else {
-                        // Positive number
-                        raw as i32
-                    };
-                    let num = signed_val * (scale as i32);
-                    if !suggestions.contains(&num) {
-                        suggestions.push(num);
-                    }
-                }
-            },
-        }
-
-        // Pick a random suggestion as the current number
-        let correct_index = rng.random_range(0..suggestions.len());
-        let current_number_signed = suggestions[correct_index];
-
-        // Shuffle suggestions so the correct answer is in a random position
-        suggestions.shuffle(&mut rng);
-
-        // Calculate raw_current_number based on mode
-        let raw_current_number = match number_mode {
-            NumberMode::Unsigned => {
-                let current_number = current_number_signed.unsigned_abs();
-                current_number / scale
-            },
-            NumberMode::Signed => {
-                // For signed mode, we need to preserve the two's complement representation
-                // Example: -1 in 4-bit two's complement is 0b1111
-                // We cast i32 to u32 (preserving bit pattern), then mask to n-bits
-                // Result: -1 becomes 15u32 (0b1111), which displays as "1111"
-                let unscaled_signed = current_number_signed / (scale as i32);
-
-                // Convert to unsigned bits using two's complement masking
-                // Casting i32 to u32 reinterprets the bits (not a numeric conversion)
-                // For n-bit number, mask is (2^n - 1)
-                let mask = (1u32 << num_bits) - 1;
-                (unscaled_signed as u32) & mask
-            },
-        };
+    #[cfg(test)]
+    pub fn new(bits: Bits, number_mode: NumberMode, streak: u32, rng: &mut impl Rng) -> Self {
+        let question = Question::generate(bits.clone(), number_mode, rng);
+        Self::from_question(bits, question, streak)
+    }
 
-        // Calculate time based on difficulty
-        let time_total = 10.0 - (streak.min(8) as f64 * 0.5);
+    /// A puzzle for a question that was already dealt; a longer streak gets less time
+    pub fn from_question(bits: Bits, question: Question, streak: u32) -> Self {
+        let time_total = scoring::time_limit(streak);
         let time_left = time_total;
 
-        let selected_suggestion = Some(suggestions[0]);
+        let selected_suggestion = question.choices.first().copied();
         let guess_result = None;
         let last_points_awarded = 0;
 
         Self {
             bits,
-            raw_current_number,
-            suggestions,
-            correct_answer: current_number_signed,
+            question,
             time_total,
             time_left,
             selected_suggestion,
@@ -829,26 +1737,57 @@ impl BinaryNumbersPuzzle {
             last_points_awarded,
             stats_snapshot: None,
             skip_first_dt: true,
+            timed: true,
+            undo_notice: false,
+            hints_used: 0,
+            hints_shown: HintsShown::Hidden,
+            cheat_sheet_seen: false,
+            elapsed: 0.0,
+            suggestion_areas: RefCell::new(Vec::new()),
         }
     }
 
+    /// Reveal the next hint for the question, if it has one left
+    fn take_hint(&mut self) {
+        if self.question.hint(self.hints_used).is_some() {
+            self.hints_used += 1;
+        }
+    }
+
+    /// Show the hints while the peek key is held, taking the first one if none was yet
+    fn peek(&mut self) {
+        if self.hints_used == 0 {
+            self.take_hint();
+        }
+        if self.hints_shown == HintsShown::Hidden {
+            self.hints_shown = HintsShown::Peeking;
+        }
+    }
+
+    /// Disable the countdown, for practice mode
+    pub fn untimed(mut self) -> Self {
+        self.timed = false;
+        self
+    }
+
+    fn set_question(&mut self, question: Question) {
+        self.selected_suggestion = question.choices.first().copied();
+        self.question = question;
+    }
+
     pub fn suggestions(&self) -> &[i32] {
-        &self.suggestions
+        &self.question.choices
     }
 
     pub fn is_correct_guess(&self, guess: i32) -> bool {
-        guess == self.correct_answer
+        self.question.is_correct(guess)
     }
 
-    pub fn current_to_binary_string(&self) -> String {
-        let width = self.bits.to_int() as usize;
-        let raw = format!("{:0width$b}", self.raw_current_number, width = width);
-        raw.chars()
-            .collect::<Vec<_>>()
-            .chunks(4)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .collect::<Vec<_>>()
-            .join(" ")
+    /// The suggestion drawn at a terminal position
+    fn suggestion_at(&self, column: u16, row: u16) -> Option<i32> {
+        let areas = self.suggestion_areas.borrow();
+        let index = areas.iter().position(|area| area.contains(Position::new(column, row)))?;
+        self.suggestions().get(index).copied()
     }
 
     pub fn run(&mut self, dt: f64) {
@@ -859,6 +1798,10 @@ impl BinaryNumbersPuzzle {
         if self.guess_result.is_some() {
             return;
         }
+        self.elapsed += dt;
+        if !self.timed {
+            return;
+        }
         self.time_left -= dt;
         if self.time_left <= 0.0 {
             self.guess_result = Some(GuessResult::Timeout);
@@ -866,40 +1809,8 @@ impl BinaryNumbersPuzzle {
     }
 }
 
-impl Widget for &mut BinaryNumbersGame {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        self.render_ref(area, buf);
-    }
-}
-
-// Simple ASCII gauge renderer to avoid variable glyph heights from Unicode block elements
-fn render_ascii_gauge(area: Rect, buf: &mut Buffer, ratio: f64, color: Color) {
-    #[allow(clippy::cast_sign_loss)]
-    #[allow(clippy::cast_possible_truncation)]
-    let fill_width =
-        (f64::from(area.width) * ratio.clamp(0.0, 1.0)).round().min(f64::from(area.width)) as u16;
-
-    if area.height == 0 {
-        return;
-    }
-
-    for x in 0..area.width {
-        let filled = x < fill_width;
-        let symbol = if filled { "=" } else { " " };
-        let style = if filled {
-            Style::default().fg(color)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        if let Some(cell) = buf.cell_mut((area.x + x, area.y)) {
-            cell.set_symbol(symbol);
-            cell.set_style(style);
-        }
-    }
-}
-
-struct HighScores {
+/// Best score per mode, keyed by `BinaryNumbersGame::compute_high_score_key`
+pub struct HighScores {
     scores: HashMap<String, u32>,
 }
 
@@ -910,17 +1821,14 @@ impl HighScores {
         Self { scores: HashMap::new() }
     }
 
-    fn load() -> Self {
+    pub fn load() -> Self {
         let mut hs = Self::empty();
-        if let Ok(mut file) = File::open(Self::FILE) {
-            let mut contents = String::new();
-            if file.read_to_string(&mut contents).is_ok() {
-                for line in contents.lines() {
-                    if let Some((k, v)) = line.split_once('=')
-                        && let Ok(score) = v.trim().parse::<u32>()
-                    {
-                        hs.scores.insert(k.trim().to_string(), score);
-                    }
+        if let Some(contents) = storage::active().read(Self::FILE) {
+            for line in contents.lines() {
+                if let Some((k, v)) = line.split_once('=')
+                    && let Ok(score) = v.trim().parse::<u32>()
+                {
+                    hs.scores.insert(k.trim().to_string(), score);
                 }
             }
         }
@@ -936,11 +1844,10 @@ impl HighScores {
             let val = self.get(key);
             let _ = writeln!(data, "{key}={val}");
         }
-        let mut file = File::create(Self::FILE)?;
-        file.write_all(data.as_bytes())
+        storage::active().write(Self::FILE, &data)
     }
 
-    fn get(&self, bits: &str) -> u32 {
+    pub fn get(&self, bits: &str) -> u32 {
         *self.scores.get(bits).unwrap_or(&0)
     }
 
@@ -952,133 +1859,72 @@ impl HighScores {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
+    use crate::message::Message;
+    use crate::screen::{Screen, Transition};
+    use crossterm::event::{KeyCode, KeyEventKind, KeyEventState, KeyModifiers};
     use std::fs;
     use std::sync::Mutex;
 
     static HS_LOCK: Mutex<()> = Mutex::new(());
 
+    /// Run a test that may write the high score, mastery or stats files, restoring them afterwards
     fn with_high_score_file<F: FnOnce()>(f: F) {
         #[allow(clippy::expect_used)]
         let _guard = HS_LOCK.lock().expect("Failed to lock high score mutex");
-        let original = fs::read_to_string(HighScores::FILE).ok();
+        let files = [HighScores::FILE, Mastery::FILE, Stats::FILE];
+        let originals = files.map(|file| fs::read_to_string(file).ok());
         f();
         // restore
-        match original {
-            Some(data) => {
-                let _ = fs::write(HighScores::FILE, data);
-            },
-            None => {
-                let _ = fs::remove_file(HighScores::FILE);
-            },
-        }
-    }
-
-    #[test]
-    fn bits_properties() {
-        assert_eq!(Bits::Four.to_int(), 4);
-        assert_eq!(Bits::Four.upper_bound(), 15);
-        assert_eq!(Bits::Four.suggestion_count(), 3);
-
-        assert_eq!(Bits::FourShift4.scale_factor(), 16);
-        assert_eq!(Bits::FourShift4.upper_bound(), 240);
-        assert_eq!(Bits::FourShift4.suggestion_count(), 3);
-
-        assert_eq!(Bits::FourShift8.scale_factor(), 256);
-        assert_eq!(Bits::FourShift12.high_score_key(), 412);
-        assert_eq!(Bits::Eight.upper_bound(), 255);
-
-        assert_eq!(Bits::Sixteen.suggestion_count(), 6);
-    }
-
-    #[test]
-    fn puzzle_generation_unique_and_scaled() {
-        let p = BinaryNumbersPuzzle::new(Bits::FourShift4.clone(), NumberMode::Unsigned, 0);
-        let scale = Bits::FourShift4.scale_factor();
-        assert_eq!(p.suggestions().len(), Bits::FourShift4.suggestion_count());
-        // uniqueness
-        let mut sorted = p.suggestions().to_vec();
-        sorted.sort_unstable();
-        for pair in sorted.windows(2) {
-            assert_ne!(pair[0], pair[1]);
-        }
-        // scaling property
-        for &s in p.suggestions() {
-            assert_eq!(s.unsigned_abs() % scale, 0);
+        for (file, original) in files.into_iter().zip(originals) {
+            match original {
+                Some(data) => {
+                    let _ = fs::write(file, data);
+                },
+                None => {
+                    let _ = fs::remove_file(file);
+                },
+            }
         }
-        // correct answer must be one of suggestions and raw_current_number * scale == correct_answer (unsigned)
-        assert!(p.suggestions().contains(&p.correct_answer));
-        assert_eq!(p.raw_current_number * scale, p.correct_answer.unsigned_abs());
     }
 
     #[test]
     fn binary_string_formatting_groups_every_four_bits() {
-        let mut p = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Unsigned, 0);
-        p.raw_current_number = 0xAB; // 171 = 10101011
-        assert_eq!(p.current_to_binary_string(), "1010 1011");
-        let mut p4 = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0);
-        p4.raw_current_number = 0b0101;
-        assert_eq!(p4.current_to_binary_string(), "0101");
+        let mut p =
+            BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Unsigned, 0, &mut rand::rng());
+        p.question.pattern = 0xAB; // 171 = 10101011
+        assert_eq!(p.question.binary_string(), "1010 1011");
+        let mut p4 =
+            BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0, &mut rand::rng());
+        p4.question.pattern = 0b0101;
+        assert_eq!(p4.question.binary_string(), "0101");
     }
 
     #[test]
     fn signed_mode_negative_numbers_show_sign_bit() {
         // Test 4-bit signed mode with a negative number
-        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Signed, 0);
+        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Signed, 0, &mut rand::rng());
         // In 4-bit two's complement, -8 is represented as 1000
-        p.raw_current_number = 0b1000; // -8 in 4-bit two's complement
-        assert_eq!(p.current_to_binary_string(), "1000", "4-bit: -8 should be 1000");
+        p.question.pattern = 0b1000; // -8 in 4-bit two's complement
+        assert_eq!(p.question.binary_string(), "1000", "4-bit: -8 should be 1000");
 
         // In 4-bit two's complement, -1 is represented as 1111
-        p.raw_current_number = 0b1111; // -1 in 4-bit two's complement
-        assert_eq!(p.current_to_binary_string(), "1111", "4-bit: -1 should be 1111");
+        p.question.pattern = 0b1111; // -1 in 4-bit two's complement
+        assert_eq!(p.question.binary_string(), "1111", "4-bit: -1 should be 1111");
 
         // Test 8-bit signed mode with a negative number
-        let mut p8 = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Signed, 0);
+        let mut p8 = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Signed, 0, &mut rand::rng());
         // In 8-bit two's complement, -128 is represented as 10000000
-        p8.raw_current_number = 0b10000000; // -128 in 8-bit two's complement
-        assert_eq!(p8.current_to_binary_string(), "1000 0000", "8-bit: -128 should be 1000 0000");
+        p8.question.pattern = 0b10000000; // -128 in 8-bit two's complement
+        assert_eq!(p8.question.binary_string(), "1000 0000", "8-bit: -128 should be 1000 0000");
 
         // In 8-bit two's complement, -1 is represented as 11111111
-        p8.raw_current_number = 0b11111111; // -1 in 8-bit two's complement
-        assert_eq!(p8.current_to_binary_string(), "1111 1111", "8-bit: -1 should be 1111 1111");
-    }
-
-    #[test]
-    fn signed_mode_puzzle_generates_correct_raw_bits_for_negative() {
-        // Generate many puzzles and check that when we have a negative number,
-        // the raw_current_number has the sign bit set correctly
-        for _ in 0..20 {
-            let p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Signed, 0);
-            let current_signed = p.correct_answer;
-
-            if current_signed < 0 {
-                // For negative numbers in 4-bit two's complement, the MSB (bit 3) should be 1
-                // which means raw_current_number should be >= 8 (0b1000)
-                assert!(
-                    p.raw_current_number >= 8,
-                    "Negative number {} should have raw bits >= 8 (sign bit set), but got {}. Binary: {}",
-                    current_signed,
-                    p.raw_current_number,
-                    p.current_to_binary_string()
-                );
-            } else {
-                // For positive numbers (including 0), MSB should be 0
-                // which means raw_current_number should be < 8
-                assert!(
-                    p.raw_current_number < 8,
-                    "Positive number {} should have raw bits < 8 (sign bit clear), but got {}. Binary: {}",
-                    current_signed,
-                    p.raw_current_number,
-                    p.current_to_binary_string()
-                );
-            }
-        }
+        p8.question.pattern = 0b11111111; // -1 in 8-bit two's complement
+        assert_eq!(p8.question.binary_string(), "1111 1111", "8-bit: -1 should be 1111 1111");
     }
 
     #[test]
     fn puzzle_timeout_sets_guess_result() {
-        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0);
+        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0, &mut rand::rng());
         p.time_left = 0.5;
         // First run() skips dt due to skip_first_dt flag
         // The reason for this is to prevent timer jump when starting a new puzzle
@@ -1089,54 +1935,16 @@ mod tests {
         assert_eq!(p.guess_result, Some(GuessResult::Timeout));
     }
 
-    #[test]
-    fn suggestions_are_randomized() {
-        // Verify that suggestions are properly randomized and the first suggestion
-        // is not always the correct answer
-        let mut first_is_correct_count = 0;
-        let trials = 100;
-
-        for _ in 0..trials {
-            let p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0);
-            // Check if the first suggestion happens to be the correct answer
-            if p.suggestions[0] == p.correct_answer {
-                first_is_correct_count += 1;
-            }
-        }
-
-        // With 3 suggestions for 4-bit mode, we expect roughly 33% to be correct by chance
-        // Allow a range of 20-50% (which is generous for 100 trials to account for randomness)
-        // The key point is that it's NOT 100% (which would indicate no randomization)
-        assert!(
-            first_is_correct_count >= 20 && first_is_correct_count <= 50,
-            "First suggestion was correct {} times out of {}, expected around 33% (20-50 range). \
-             If this is close to 100%, suggestions are not randomized!",
-            first_is_correct_count,
-            trials
-        );
-
-        // Also verify that the correct answer is actually one of the suggestions
-        for _ in 0..10 {
-            let p = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Signed, 0);
-            assert!(
-                p.suggestions.contains(&p.correct_answer),
-                "correct_answer {} must be in suggestions {:?}",
-                p.correct_answer,
-                p.suggestions
-            );
-        }
-    }
-
     #[test]
     fn finalize_round_correct_increments_score_streak_and_sets_result_state() {
         with_high_score_file(|| {
             let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
             // ensure deterministic: mark puzzle correct
-            let answer = g.puzzle.correct_answer;
+            let answer = g.puzzle.question.answer;
             g.puzzle.guess_result = Some(GuessResult::Correct);
             g.finalize_round();
-            assert_eq!(g.streak, 1);
-            assert_eq!(g.score, 10); // base points
+            assert_eq!(g.run.streak, 1);
+            assert_eq!(g.run.score, 10); // base points
             assert_eq!(g.puzzle.last_points_awarded, 10);
             assert_eq!(g.game_state, GameState::Result);
             assert!(g.puzzle_resolved);
@@ -1144,16 +1952,150 @@ mod tests {
         });
     }
 
+    #[test]
+    fn hints_run_out_and_halve_the_points() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+            for _ in 0..3 {
+                g.handle_game_input(KeyEvent::from(KeyCode::Char('?')));
+            }
+            assert_eq!(g.puzzle.hints_used, 2);
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!((g.run.score, g.puzzle.last_points_awarded), (2, 2));
+        });
+    }
+
+    #[test]
+    fn cheat_sheet_opens_only_in_allowed_modes() {
+        let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+        let c = KeyEvent::from(KeyCode::Char('c'));
+        g.handle_game_input(c);
+        assert!(g.cheat_sheet.is_some() && g.puzzle.cheat_sheet_seen);
+        g.handle_game_input(c);
+        assert!(g.cheat_sheet.is_none());
+
+        let settings = settings::get();
+        settings::set(settings::Settings {
+            cheat_sheet: settings::ModeSet::parse("4").unwrap_or_default(),
+            ..settings
+        });
+        g.handle_game_input(c);
+        settings::set(settings);
+        assert!(g.cheat_sheet.is_none());
+    }
+
+    #[test]
+    fn playlists_switch_modes_and_end_on_the_summary() {
+        with_high_score_file(|| {
+            let Ok(playlist) = Playlist::parse("1x 4, 2x 8 signed") else { panic!("valid") };
+            // Practice keeps the lives, so the playlist is what ends the run
+            let mut g = BinaryNumbersGame::new(Bits::Sixteen, NumberMode::Unsigned)
+                .with_practice(true)
+                .with_playlist(playlist);
+            let enter = KeyEvent::from(KeyCode::Enter);
+            let mut modes = Vec::new();
+            for _ in 0..3 {
+                modes.push((g.puzzle.question.bits.clone(), g.number_mode));
+                g.handle_game_input(KeyEvent::from(KeyCode::Char('s')));
+                g.handle_game_input(enter);
+            }
+            assert_eq!(
+                modes,
+                [
+                    (Bits::Four, NumberMode::Unsigned),
+                    (Bits::Eight, NumberMode::Signed),
+                    (Bits::Eight, NumberMode::Signed),
+                ]
+            );
+            assert!(g.summary.is_some() && g.game_over_banner.is_none());
+            assert_eq!(g.answers.len(), 3);
+        });
+    }
+
+    #[test]
+    fn holding_space_peeks_at_a_hint_where_releases_are_reported() {
+        with_high_score_file(|| {
+            let space = KeyEvent::from(KeyCode::Char(' '));
+            let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+            g.update(Message::Key(space));
+            assert_eq!(g.puzzle.hints_used, 0, "no releases, no peeking");
+
+            let before = settings::get();
+            settings::set(settings::Settings { key_releases: true, ..before });
+            g.update(Message::Key(space));
+            assert_eq!((g.puzzle.hints_used, g.puzzle.hints_shown), (1, HintsShown::Peeking));
+            g.update(Message::KeyRelease(space));
+            assert_eq!((g.puzzle.hints_used, g.puzzle.hints_shown), (1, HintsShown::Hidden));
+            g.update(Message::Key(KeyEvent::from(KeyCode::Char('?'))));
+            g.update(Message::KeyRelease(space));
+            assert_eq!((g.puzzle.hints_used, g.puzzle.hints_shown), (2, HintsShown::Pinned));
+            settings::set(before);
+        });
+    }
+
+    #[test]
+    fn number_keys_answer_with_the_choice_at_their_position() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+            g.handle_game_input(KeyEvent::from(KeyCode::Char('9')));
+            assert!(g.puzzle.guess_result.is_none(), "there are only four choices");
+            let second = g.puzzle.question.choices[1];
+            g.handle_game_input(KeyEvent::from(KeyCode::Char('2')));
+            assert_eq!(g.answers.last().and_then(|answer| answer.guess), Some(second));
+
+            let mut buf = Buffer::empty(Rect::new(0, 0, 65, 30));
+            g.puzzle.render_suggestions(Rect::new(0, 0, 40, 3), &mut buf);
+            assert_eq!(buf[(1, 0)].symbol(), "1");
+            assert_eq!(buf[(11, 0)].symbol(), "2");
+        });
+    }
+
+    #[test]
+    fn pasting_a_choice_answers_with_it() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+            let choices = g.puzzle.question.choices.clone();
+            let Some(other) = (0..).find(|n| !choices.contains(n)) else { panic!("a free value") };
+            g.update(Message::Paste(other.to_string()));
+            assert!(g.puzzle.guess_result.is_none());
+            g.update(Message::Paste(format!(" {}\n", choices[2])));
+            assert_eq!(g.answers.last().and_then(|answer| answer.guess), Some(choices[2]));
+        });
+    }
+
+    #[test]
+    fn question_lists_are_asked_in_order_then_end_the_run() {
+        with_high_score_file(|| {
+            let Ok(list) = QuestionList::read("1011 0100,180 148,180\n1111,,-1") else {
+                panic!("valid list");
+            };
+            let mut g = BinaryNumbersGame::new(Bits::Sixteen, NumberMode::Unsigned)
+                .with_practice(true)
+                .with_questions(list);
+            assert_eq!(g.puzzle.question.choices, [180, 148]);
+            assert_eq!(g.puzzle.stats_snapshot.as_ref().and_then(|s| s.playlist), Some((1, 2)));
+            let enter = KeyEvent::from(KeyCode::Enter);
+            g.handle_game_input(KeyEvent::from(KeyCode::Char('s')));
+            g.handle_game_input(enter);
+            assert_eq!((g.puzzle.question.pattern, g.number_mode), (0b1111, NumberMode::Signed));
+            g.handle_game_input(KeyEvent::from(KeyCode::Char('s')));
+            g.handle_game_input(enter);
+            assert!(g.summary.is_some());
+        });
+    }
+
     #[test]
     fn life_awarded_every_five_streak() {
         with_high_score_file(|| {
             let mut g = BinaryNumbersGame::new_with_max_lives(Bits::Four, NumberMode::Unsigned, 3);
-            g.lives = 2; // below max
-            g.streak = 4; // about to become 5
+            g.run.lives = 2; // below max
+            g.run.streak = 4; // about to become 5
             g.puzzle.guess_result = Some(GuessResult::Correct);
             g.finalize_round();
-            assert_eq!(g.streak, 5);
-            assert_eq!(g.lives, 3); // gained life
+            assert_eq!(g.run.streak, 5);
+            assert!(g.confetti.is_active(), "a milestone is celebrated");
+            assert_eq!(g.run.lives, 3); // gained life
         });
     }
 
@@ -1161,12 +2103,13 @@ mod tests {
     fn incorrect_guess_resets_streak_and_loses_life() {
         with_high_score_file(|| {
             let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
-            g.streak = 3;
-            let lives_before = g.lives;
+            g.run.streak = 3;
+            let lives_before = g.run.lives;
             g.puzzle.guess_result = Some(GuessResult::Incorrect);
             g.finalize_round();
-            assert_eq!(g.streak, 0);
-            assert_eq!(g.lives, lives_before - 1);
+            assert_eq!(g.run.streak, 0);
+            assert_eq!(g.run.lives, lives_before - 1);
+            assert!(g.shake.is_active());
         });
     }
 
@@ -1174,14 +2117,39 @@ mod tests {
     fn pending_game_over_when_life_reaches_zero() {
         with_high_score_file(|| {
             let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
-            g.lives = 1;
+            g.run.lives = 1;
             g.puzzle.guess_result = Some(GuessResult::Incorrect);
             g.finalize_round();
-            assert_eq!(g.lives, 0);
+            assert_eq!(g.run.lives, 0);
             assert_eq!(g.game_state, GameState::PendingGameOver);
         });
     }
 
+    #[test]
+    fn summary_follows_the_game_over_reveal() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            g.run.lives = 1;
+            g.puzzle.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            g.update(Message::Key(KeyEvent::from(KeyCode::Enter)));
+            let shown =
+                |g: &BinaryNumbersGame| g.game_over_banner.as_ref().map(|b| b.summary_shown);
+            assert_eq!(shown(&g), Some(false));
+            assert!(MainScreenWidget::is_animating(&g));
+
+            // Pausing the animations stops the reveal without ending it
+            g.update(Message::AnimationFinished);
+            assert_eq!(shown(&g), Some(false));
+
+            crate::animation::advance(Duration::from_secs(2));
+            assert!(!MainScreenWidget::is_animating(&g));
+            g.update(Message::AnimationFinished);
+            assert_eq!(shown(&g), Some(true));
+            assert!(MainScreenWidget::is_animating(&g), "the banner loops under the summary");
+        });
+    }
+
     #[test]
     fn high_score_updates_and_flag_set() {
         with_high_score_file(|| {
@@ -1198,10 +2166,320 @@ mod tests {
         });
     }
 
+    #[test]
+    fn practice_undo_restores_question_and_stats() {
+        with_high_score_file(|| {
+            let mut g =
+                BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned).with_practice(true);
+            let answer = g.puzzle.question.answer;
+            let suggestions = g.puzzle.question.choices.clone();
+            let stats = g.stats.clone();
+            let key = BinaryNumbersGame::compute_high_score_key(&Bits::Four, NumberMode::Unsigned);
+            let pattern = g.puzzle.question.pattern as usize;
+            let mastered = g.mastery.coverage(&key, 16).contains(pattern);
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!((g.run.score, g.run.streak, g.run.rounds), (10, 1, 1));
+            assert!(!g.new_high_score_reached, "practice runs don't set high scores");
+
+            let undo = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::empty());
+            g.handle_game_input(undo);
+            assert_eq!((g.run.score, g.run.streak, g.run.rounds), (0, 0, 0));
+            assert_eq!(g.game_state, GameState::Active);
+            assert_eq!(g.puzzle.guess_result, None);
+            assert_eq!(g.puzzle.question.answer, answer);
+            assert_eq!(g.puzzle.question.choices, suggestions);
+            assert!(g.puzzle.undo_notice);
+            assert_eq!(g.stats, stats, "the answer is taken out of the stats");
+            assert_eq!(Stats::load(), stats, "and out of the saved ones");
+            assert_eq!(g.mastery.coverage(&key, 16).contains(pattern), mastered);
+
+            // nothing left to undo
+            g.handle_game_input(undo);
+            assert_eq!(g.run.rounds, 0);
+        });
+    }
+
+    #[test]
+    fn undoing_across_a_playlist_section_forgets_the_mastery_of_the_answered_mode() {
+        with_high_score_file(|| {
+            let Ok(playlist) = Playlist::parse("1x 4, 1x 8 signed") else { panic!("valid") };
+            let mut g = BinaryNumbersGame::new(Bits::Sixteen, NumberMode::Unsigned)
+                .with_practice(true)
+                .with_playlist(playlist);
+            let key = BinaryNumbersGame::compute_high_score_key(&Bits::Four, NumberMode::Unsigned);
+            let pattern = g.puzzle.question.pattern as usize;
+            g.mastery.forget(&key, pattern);
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert!(g.mastery.coverage(&key, 16).contains(pattern));
+
+            g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+            assert_eq!((g.bits.clone(), g.number_mode), (Bits::Eight, NumberMode::Signed));
+            g.handle_game_input(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::empty()));
+            assert!(!g.mastery.coverage(&key, 16).contains(pattern));
+            assert_eq!((g.bits.clone(), g.number_mode), (Bits::Four, NumberMode::Unsigned));
+        });
+    }
+
+    #[test]
+    fn practice_mode_is_untimed_and_keeps_lives() {
+        with_high_score_file(|| {
+            let mut g =
+                BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned).with_practice(true);
+            g.run(0.0);
+            g.run(100.0);
+            assert_eq!(g.puzzle.guess_result, None);
+            let lives_before = g.run.lives;
+            g.puzzle.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(g.run.lives, lives_before);
+        });
+    }
+
+    #[test]
+    fn fast_correct_answers_count_towards_mastery() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            g.mastery = Mastery::empty();
+            let key = BinaryNumbersGame::compute_high_score_key(&g.bits, g.number_mode);
+
+            // too slow: not recorded
+            g.puzzle.elapsed = MASTERY_SPEED_SECS + 1.0;
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.mastery.coverage(&key, 16).count(), 0);
+
+            g.puzzle = g.next_puzzle(0);
+            let value = g.puzzle.question.pattern as usize;
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert!(g.mastery.coverage(&key, 16).contains(value));
+        });
+    }
+
+    #[test]
+    fn guest_mode_leaves_profile_files_untouched() {
+        with_high_score_file(|| {
+            let _ = fs::write(HighScores::FILE, "4u=7\n");
+            let _ = fs::remove_file(Mastery::FILE);
+            settings::set(settings::Settings { guest: true, ..settings::get() });
+
+            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            assert_eq!(g.high_scores.get("4u"), 0, "guests start without saved scores");
+            g.run.score = 100;
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            settings::set(settings::Settings { guest: false, ..settings::get() });
+
+            assert_eq!(fs::read_to_string(HighScores::FILE).ok().as_deref(), Some("4u=7\n"));
+            assert!(fs::metadata(Mastery::FILE).is_err());
+        });
+    }
+
+    #[test]
+    fn quitting_a_run_in_progress_asks_for_confirmation() {
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+        let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+        g.handle_game_input(esc);
+        assert!(g.is_exit_intended(), "nothing to lose before the first round");
+
+        let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+        g.run.rounds = 1;
+        g.handle_game_input(esc);
+        assert!(g.overlay == Some(Overlay::ConfirmQuit) && !g.is_exit_intended());
+        let time_left = g.puzzle.time_left;
+        g.run(1.0);
+        assert!((g.puzzle.time_left - time_left).abs() < f64::EPSILON, "timer pauses");
+        g.handle_game_input(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::empty()));
+        assert!(g.overlay.is_none() && !g.is_exit_intended());
+
+        g.handle_game_input(esc);
+        g.handle_game_input(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::empty()));
+        assert!(g.is_exit_intended());
+    }
+
+    #[test]
+    fn pause_menu_restarts_the_same_mode() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty());
+        let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Signed);
+        g.run.score = 30;
+        g.run.rounds = 4;
+        g.handle_game_input(key('p'));
+        assert_eq!(g.overlay, Some(Overlay::Paused));
+        g.handle_game_input(key('r'));
+        assert_eq!(g.overlay, None);
+        assert_eq!((g.run.score, g.run.rounds), (0, 0));
+        assert!(matches!(g.bits, Bits::Eight) && g.number_mode == NumberMode::Signed);
+
+        // Enter presses the focused button, which starts on the first one
+        g.run.rounds = 4;
+        g.handle_game_input(key('p'));
+        g.handle_game_input(KeyEvent::from(KeyCode::Right));
+        g.handle_game_input(KeyEvent::from(KeyCode::Right));
+        g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(g.overlay, Some(Overlay::ConfirmQuit));
+        g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+        assert!(g.is_exit_intended());
+    }
+
+    #[test]
+    fn runs_without_restart_keep_their_answers() {
+        with_high_score_file(|| {
+            let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty());
+            let mut g =
+                BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned).with_restart(false);
+            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+
+            g.handle_game_input(key('p'));
+            let hints = MainScreenWidget::key_hints(&g);
+            assert!(hints.iter().all(|hint| hint.action != "restart"), "{hints:?}");
+            g.handle_game_input(key('r'));
+            assert_eq!(g.overlay, Some(Overlay::Paused));
+            // The button after "resume" is "quit" now
+            g.handle_game_input(KeyEvent::from(KeyCode::Right));
+            g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+            assert_eq!(g.overlay, Some(Overlay::ConfirmQuit));
+            assert_eq!(g.answered(), 1);
+
+            g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+            assert!(g.is_over());
+            g.handle_game_input(key('r'));
+            g.handle_game_input(KeyEvent::from(KeyCode::Enter));
+            assert!(g.is_over() && g.answered() == 1, "the summary stays");
+        });
+    }
+
+    #[test]
+    fn instructions_name_the_keys_of_the_layout() {
+        let g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned);
+        let drawn = || {
+            let area = Rect::new(0, 0, 100, 40);
+            let mut buf = Buffer::empty(area);
+            g.render_ref(area, &mut buf);
+            buf.content.iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+        assert!(drawn().contains("<←→> select  <Enter> confirm  <s> skip  <Esc> exit"));
+
+        let before = settings::get();
+        settings::set(settings::Settings { key_layout: keybinds::KeyLayout::Dvorak, ..before });
+        let dvorak = drawn();
+        settings::set(before);
+        assert!(dvorak.contains("<o> skip"), "where QWERTY has s");
+    }
+
+    #[test]
+    fn clicking_a_suggestion_selects_then_submits_it() {
+        with_high_score_file(|| {
+            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let area = Rect::new(0, 0, 80, 30);
+            g.render_ref(area, &mut Buffer::empty(area));
+            let target = g.puzzle.suggestion_areas.borrow()[1];
+            let click = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: target.x + 1,
+                row: target.y + 1,
+                modifiers: KeyModifiers::NONE,
+            };
+
+            g.handle_mouse(click);
+            assert_eq!(g.puzzle.selected_suggestion, Some(g.puzzle.suggestions()[1]));
+            assert!(g.puzzle.guess_result.is_none());
+            g.handle_mouse(click);
+            assert!(g.puzzle.guess_result.is_some());
+            assert_eq!(g.run.rounds, 1);
+        });
+    }
+
+    #[test]
+    fn countdown_holds_the_timer_until_go() {
+        let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned).with_countdown();
+        let time_left = g.puzzle.time_left;
+        assert_eq!(g.countdown_label(), Some("3"));
+        g.run(COUNTDOWN_STEP_SECS * 3.5);
+        assert_eq!(g.countdown_label(), Some("GO!"));
+        assert!((g.puzzle.time_left - time_left).abs() < f64::EPSILON);
+        g.run(COUNTDOWN_STEP_SECS);
+        assert_eq!(g.countdown_label(), None);
+        g.run(1.0); // the first frame after a new puzzle is skipped
+        g.run(1.0);
+        assert!(g.puzzle.time_left < time_left);
+
+        let practice = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned)
+            .with_practice(true)
+            .with_countdown();
+        assert_eq!(practice.countdown_label(), None, "practice is untimed");
+    }
+
+    #[test]
+    fn messages_play_a_round_and_close_the_game() {
+        with_high_score_file(|| {
+            let key = |code| Message::Key(KeyEvent::from(code));
+            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let time_left = g.puzzle.time_left;
+            g.update(Message::Tick(0.0)); // the first frame after a new puzzle is skipped
+            g.update(Message::Tick(time_left + 1.0));
+            assert_eq!(g.game_state, GameState::Result);
+            assert_eq!((g.run.rounds, g.run.lives), (1, 2));
+
+            assert!(matches!(g.update(key(KeyCode::Esc)), Transition::Stay));
+            assert!(matches!(g.update(key(KeyCode::Char('y'))), Transition::Stay));
+            assert_eq!(g.game_state, GameState::GameOver, "a quit run ends on its summary");
+            assert!(g.summary.is_some() && g.game_over_banner.is_none());
+            assert!(matches!(g.update(key(KeyCode::Esc)), Transition::Pop));
+        });
+    }
+
+    #[test]
+    fn same_seed_deals_the_same_questions() {
+        let questions = |g: &mut BinaryNumbersGame| -> Vec<(u32, Vec<i32>)> {
+            (0..5)
+                .map(|_| {
+                    let question = (g.puzzle.question.pattern, g.puzzle.question.choices.clone());
+                    g.puzzle = g.next_puzzle(0);
+                    question
+                })
+                .collect()
+        };
+        let mut a = BinaryNumbersGame::new(Bits::Sixteen, NumberMode::Signed).with_seed(42);
+        let mut b = BinaryNumbersGame::new(Bits::Sixteen, NumberMode::Signed).with_seed(42);
+        let first_run = questions(&mut a);
+        assert_eq!(first_run, questions(&mut b));
+
+        a.reset_game_state();
+        assert_eq!(questions(&mut a), first_run, "restarting replays the sequence");
+    }
+
+    #[test]
+    fn standard_questions_ignore_the_dealing_settings() {
+        let deal = || {
+            let mut g = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned)
+                .with_standard_questions()
+                .with_seed(7);
+            (0..5)
+                .map(|_| {
+                    let choices = g.puzzle.question.choices.clone();
+                    g.puzzle = g.next_puzzle(0);
+                    choices
+                })
+                .collect::<Vec<_>>()
+        };
+        let standard = deal();
+
+        let before = settings::get();
+        let Some(choices) = settings::ChoiceCounts::parse("2") else { panic!("valid") };
+        settings::set(settings::Settings { choices, no_repeat: 0, ..before });
+        let configured = deal();
+        settings::set(before);
+        assert_eq!(configured, standard);
+        assert!(standard.iter().all(|choices| choices.len() == Bits::Eight.suggestion_count()));
+    }
+
     #[test]
     fn hearts_representation_matches_lives() {
         let mut g = BinaryNumbersGame::new_with_max_lives(Bits::Four, NumberMode::Unsigned, 3);
-        g.lives = 2;
+        g.run.lives = 2;
         assert_eq!(g.lives_hearts(), "♥♥·");
     }
 
@@ -0,0 +1,110 @@
+//! The modes a question can be asked in.
+
+/// How a bit pattern is read: as a plain binary number or in two's complement
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NumberMode {
+    Unsigned,
+    Signed,
+}
+
+impl NumberMode {
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Unsigned => "UNSIGNED",
+            Self::Signed => "SIGNED",
+        }
+    }
+}
+
+/// Width of the questions of a mode; the 4 bit variants scale every value by a power of 16
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Bits {
+    Four,
+    FourShift4,
+    FourShift8,
+    FourShift12,
+    Eight,
+    Twelve,
+    Sixteen,
+}
+
+impl Bits {
+    pub const fn to_int(&self) -> u32 {
+        match self {
+            Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 4,
+            Self::Eight => 8,
+            Self::Twelve => 12,
+            Self::Sixteen => 16,
+        }
+    }
+    pub const fn scale_factor(&self) -> u32 {
+        match self {
+            Self::Four => 1,
+            Self::FourShift4 => 16,
+            Self::FourShift8 => 256,
+            Self::FourShift12 => 4096,
+            Self::Eight => 1,
+            Self::Twelve => 1,
+            Self::Sixteen => 1,
+        }
+    }
+    pub const fn high_score_key(&self) -> u32 {
+        match self {
+            Self::Four => 4,
+            Self::FourShift4 => 44,
+            Self::FourShift8 => 48,
+            Self::FourShift12 => 412,
+            Self::Eight => 8,
+            Self::Twelve => 12,
+            Self::Sixteen => 16,
+        }
+    }
+    /// Number of distinct bit patterns a question can show
+    pub const fn value_space(&self) -> usize {
+        1 << self.to_int()
+    }
+    pub const fn upper_bound(&self) -> u32 {
+        (u32::pow(2, self.to_int()) - 1) * self.scale_factor()
+    }
+    pub const fn suggestion_count(&self) -> usize {
+        match self {
+            Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 3,
+            Self::Eight => 4,
+            Self::Twelve => 5,
+            Self::Sixteen => 6,
+        }
+    }
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Four => "4 bit",
+            Self::FourShift4 => "4 bit*16",
+            Self::FourShift8 => "4 bit*256",
+            Self::FourShift12 => "4 bit*4096",
+            Self::Eight => "8 bit",
+            Self::Twelve => "12 bit",
+            Self::Sixteen => "16 bit",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_properties() {
+        assert_eq!(Bits::Four.to_int(), 4);
+        assert_eq!(Bits::Four.upper_bound(), 15);
+        assert_eq!(Bits::Four.suggestion_count(), 3);
+
+        assert_eq!(Bits::FourShift4.scale_factor(), 16);
+        assert_eq!(Bits::FourShift4.upper_bound(), 240);
+        assert_eq!(Bits::FourShift4.suggestion_count(), 3);
+
+        assert_eq!(Bits::FourShift8.scale_factor(), 256);
+        assert_eq!(Bits::FourShift12.high_score_key(), 412);
+        assert_eq!(Bits::Eight.upper_bound(), 255);
+
+        assert_eq!(Bits::Sixteen.suggestion_count(), 6);
+    }
+}
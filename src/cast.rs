@@ -0,0 +1,105 @@
+//! Recording a session as an asciinema cast, to publish clips of the game. Everything
+//! written to the terminal also goes to the cast, one output event per flushed frame, so
+//! `asciinema play` shows it with the same timing.
+
+use ratatui::layout::Size;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A writer that passes everything on to the terminal and keeps a copy in a cast
+pub struct Recorder<W: Write, C: Write = BufWriter<File>> {
+    terminal: W,
+    cast: C,
+    started: Instant,
+    /// Written since the last flush, the next event of the cast
+    frame: Vec<u8>,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Start a cast file at `path` for a terminal of `size`
+    pub fn create(terminal: W, path: &str, size: Size) -> io::Result<Self> {
+        let file = File::create(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{path}: {err}")))?;
+        Self::new(terminal, BufWriter::new(file), size)
+    }
+}
+
+impl<W: Write, C: Write> Recorder<W, C> {
+    fn new(terminal: W, mut cast: C, size: Size) -> io::Result<Self> {
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+        writeln!(
+            cast,
+            r#"{{"version": 2, "width": {}, "height": {}, "timestamp": {timestamp}, "env": {{"TERM": {}}}}}"#,
+            size.width,
+            size.height,
+            json_string(&term)
+        )?;
+        Ok(Self { terminal, cast, started: Instant::now(), frame: Vec::new() })
+    }
+}
+
+impl<W: Write, C: Write> Write for Recorder<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.terminal.write(buf)?;
+        self.frame.extend_from_slice(&buf[..written]);
+        Ok(written)
+    }
+
+    /// A frame is complete: show it, then add it to the cast
+    fn flush(&mut self) -> io::Result<()> {
+        self.terminal.flush()?;
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        let time = self.started.elapsed().as_secs_f64();
+        let data = String::from_utf8_lossy(&self.frame);
+        writeln!(self.cast, r#"[{time:.6}, "o", {}]"#, json_string(&data))?;
+        self.frame.clear();
+        self.cast.flush()
+    }
+}
+
+/// `text` as a JSON string, quoted and escaped
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_reach_the_terminal_and_the_cast_as_one_event_each() {
+        let Ok(mut recorder) = Recorder::new(Vec::new(), Vec::new(), Size::new(80, 24)) else {
+            panic!("writing to memory");
+        };
+        let _ = recorder.write_all(b"\x1b[1;1H");
+        let _ = recorder.write_all("\"0b1011\" ✓\n".as_bytes());
+        let _ = recorder.flush();
+        let _ = recorder.flush();
+
+        assert_eq!(recorder.terminal, "\x1b[1;1H\"0b1011\" ✓\n".as_bytes());
+        let cast = String::from_utf8_lossy(&recorder.cast);
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 2, "a header and one frame");
+        assert!(lines[0].starts_with(r#"{"version": 2, "width": 80, "height": 24, "#));
+        assert!(lines[1].ends_with(r#", "o", "\u001b[1;1H\"0b1011\" ✓\n"]"#), "{}", lines[1]);
+    }
+}
@@ -0,0 +1,132 @@
+//! A reference table of what each bit of a mode is worth, shown next to the question
+//! for players still learning the powers of two.
+
+use crate::theme;
+use binbreak::{Bits, NumberMode};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+/// Bit positions and their values, highest first, in columns of up to eight rows
+pub struct CheatSheet {
+    /// Position of each bit as the LEDs label it, and its value
+    rows: Vec<(u32, i64)>,
+}
+
+impl CheatSheet {
+    const ROWS_PER_COLUMN: usize = 8;
+    const COLUMN_GAP: u16 = 2;
+
+    /// The bits of `bits`, shifted modes starting above bit 0. In signed mode the highest
+    /// bit counts negative.
+    pub fn new(bits: &Bits, number_mode: NumberMode) -> Self {
+        let width = bits.to_int();
+        let shift = bits.scale_factor().trailing_zeros();
+        let rows = (0..width)
+            .rev()
+            .map(|index| {
+                let value = 1i64 << (index + shift);
+                let sign_bit = index == width - 1 && number_mode == NumberMode::Signed;
+                (index + shift, if sign_bit { -value } else { value })
+            })
+            .collect();
+        Self { rows }
+    }
+
+    fn columns(&self) -> Vec<&[(u32, i64)]> {
+        self.rows.chunks(Self::ROWS_PER_COLUMN).collect()
+    }
+
+    /// Widest position and widest value, for aligning the rows
+    fn cell_widths(&self) -> (usize, usize) {
+        let widest = |text: fn(&(u32, i64)) -> String| {
+            self.rows.iter().map(|row| text(row).len()).max().unwrap_or(0)
+        };
+        (widest(|(bit, _)| bit.to_string()), widest(|(_, value)| value.to_string()))
+    }
+
+    fn column_width(&self) -> u16 {
+        let (bit, value) = self.cell_widths();
+        #[allow(clippy::cast_possible_truncation)]
+        let width = (bit + 1 + value) as u16;
+        width
+    }
+
+    /// The columns in a border with a blank column of padding on each side
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn size(&self) -> Size {
+        let columns = self.columns();
+        let count = columns.len() as u16;
+        let rows = columns.first().map_or(0, |column| column.len()) as u16;
+        Size::new(count * self.column_width() + (count - 1) * Self::COLUMN_GAP + 4, rows + 2)
+    }
+}
+
+impl Widget for &CheatSheet {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        Clear.render(area, buf);
+        let block = Block::bordered()
+            .title(" Bits ")
+            .title_alignment(Center)
+            .border_style(Style::default().fg(theme.muted));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let (bit_width, value_width) = self.cell_widths();
+        let columns = self.columns();
+        let mut constraints = Vec::new();
+        for index in 0..columns.len() {
+            if index > 0 {
+                constraints.push(Constraint::Length(CheatSheet::COLUMN_GAP));
+            }
+            constraints.push(Constraint::Length(self.column_width()));
+        }
+        let areas = Layout::horizontal(constraints).horizontal_margin(1).split(inner);
+        for (column, &column_area) in columns.iter().zip(areas.iter().step_by(2)) {
+            let lines: Vec<Line> = column
+                .iter()
+                .map(|(bit, value)| {
+                    let color = if *value < 0 { theme.incorrect } else { theme.text };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{bit:>bit_width$} "),
+                            Style::default().fg(theme.muted),
+                        ),
+                        Span::styled(
+                            format!("{value:>value_width$}"),
+                            Style::default().fg(color).bold(),
+                        ),
+                    ])
+                })
+                .collect();
+            Paragraph::new(lines).render(column_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cheat_sheets_list_the_value_of_every_bit_highest_first() {
+        let sheet = CheatSheet::new(&Bits::Sixteen, NumberMode::Signed);
+        assert_eq!(sheet.rows.first(), Some(&(15, -32768)));
+        assert_eq!(sheet.rows.last(), Some(&(0, 1)));
+        // Two columns of "15 -32768"
+        assert_eq!(sheet.size(), Size::new(2 * 9 + 2 + 4, 10));
+
+        let sheet = CheatSheet::new(&Bits::FourShift4, NumberMode::Unsigned);
+        assert_eq!(sheet.rows, [(7, 128), (6, 64), (5, 32), (4, 16)]);
+        assert_eq!(sheet.size(), Size::new(5 + 4, 6));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 9, 6));
+        (&sheet).render(buf.area, &mut buf);
+        let row: String = (0..9).map(|x| buf[(x, 1)].symbol()).collect();
+        assert_eq!(row, "│ 7 128 │");
+    }
+}
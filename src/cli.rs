@@ -0,0 +1,247 @@
+//! Command line options. Everything here can also be chosen in the app;
+//! the flags exist to skip the menu and to script launches.
+
+use crate::config::Config;
+use crate::playlist::Playlist;
+use crate::theme::ThemePreset;
+use crate::timing::FrameRate;
+use crate::worksheet::{self, Format, QuestionList};
+use binbreak::{Bits, NumberMode, Question};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::io::Write;
+
+#[derive(Parser)]
+#[command(version, about)]
+#[allow(clippy::struct_excessive_bools)] // one per command line flag
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Skip the menu and start this mode right away: 4, 4x16, 4x256, 4x4096, 8, 12 or 16
+    #[arg(long, value_parser = parse_bits)]
+    pub bits: Option<Bits>,
+
+    /// How to play the mode started with --bits
+    #[arg(long, value_enum, default_value_t = PlayMode::Classic)]
+    pub mode: PlayMode,
+
+    /// Number mode to start with: unsigned or signed
+    #[arg(long, value_parser = parse_number_mode, default_value = "unsigned")]
+    pub numbers: NumberMode,
+
+    /// Play the modes listed in a playlist file back to back as one run, e.g. a file with
+    /// `10x 8 bit, 5x 4x16 signed`
+    #[arg(long, value_parser = Playlist::load)]
+    pub playlist: Option<Playlist>,
+
+    /// Play the questions of a file in order, one per row like `binbreak gen --format csv`
+    /// writes them: `1011 0100,180 148 52,180`
+    #[arg(long, value_parser = QuestionList::load, conflicts_with = "playlist")]
+    pub questions: Option<QuestionList>,
+
+    /// Host a race on this port for another player to join over the network; the race
+    /// is the mode of --bits (8 bit by default) and --numbers
+    #[arg(long, value_name = "PORT", conflicts_with_all = ["playlist", "questions", "join"])]
+    pub host: Option<u16>,
+
+    /// Join the race hosted at this address, e.g. 192.168.1.20:4321
+    #[arg(long, value_name = "ADDRESS", conflicts_with_all = ["playlist", "questions"])]
+    pub join: Option<String>,
+
+    /// Deal the same questions for the same seed, to race a friend or reproduce a bug
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Start with the title animation paused
+    #[arg(long)]
+    pub no_animation: bool,
+
+    /// Color theme: default, gruvbox, dracula, solarized, light or plain
+    #[arg(long, value_parser = parse_theme)]
+    pub theme: Option<ThemePreset>,
+
+    /// Play without colors (also enabled by the NO_COLOR environment variable)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Play as a guest: high scores and mastery are neither read nor written
+    #[arg(long)]
+    pub guest: bool,
+
+    /// Show still frames instead of animations
+    #[arg(long)]
+    pub reduce_motion: bool,
+
+    /// Reload the art whenever a file in assets/art or the art config directory changes
+    #[arg(long)]
+    pub dev: bool,
+
+    /// Record the session to this file as an asciinema cast, e.g. to publish a clip with
+    /// `asciinema upload`
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<String>,
+
+    /// Frames per second while something moves, 1 to 240 (default 30)
+    #[arg(long, value_parser = parse_fps)]
+    pub fps: Option<FrameRate>,
+}
+
+/// Things to do instead of starting the game
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print a shell completion script
+    ///
+    /// e.g. `binbreak completions fish > ~/.config/fish/completions/binbreak.fish`
+    Completions {
+        /// bash, zsh, fish, elvish or powershell
+        shell: Shell,
+    },
+    /// Print questions and their answers instead of playing, e.g. for worksheets
+    Gen {
+        /// Mode to draw questions from: 4, 4x16, 4x256, 4x4096, 8, 12 or 16
+        #[arg(long, value_parser = parse_bits)]
+        bits: Bits,
+
+        /// How many questions to print
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+
+        /// unsigned or signed
+        #[arg(long, value_parser = parse_number_mode, default_value = "unsigned")]
+        numbers: NumberMode,
+
+        /// csv for spreadsheets, text for a printable sheet
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+
+        /// Print the same questions for the same seed
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+impl Command {
+    pub fn run(&self, out: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Self::Completions { shell } => {
+                clap_complete::generate(*shell, &mut Cli::command(), env!("CARGO_PKG_NAME"), out);
+                Ok(())
+            },
+            Self::Gen { bits, count, numbers, format, seed } => {
+                let mut rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(*seed),
+                    None => StdRng::from_os_rng(),
+                };
+                let questions: Vec<_> = (0..*count)
+                    .map(|_| Question::generate(bits.clone(), *numbers, &mut rng))
+                    .collect();
+                worksheet::write(&questions, *format, out)
+            },
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum PlayMode {
+    /// Timed rounds with lives and high scores
+    Classic,
+    /// Untimed, no lives lost and no high scores
+    Practice,
+}
+
+impl Cli {
+    /// The config file with the command line choices layered on top
+    pub fn apply_to(&self, config: Config) -> Config {
+        match self.theme {
+            Some(preset) => config.with_override("theme", preset.label()),
+            None => config,
+        }
+    }
+}
+
+/// A mode by its `--bits` name, also used to name modes in the config file
+pub fn parse_bits(value: &str) -> Result<Bits, String> {
+    match value.trim().to_lowercase().replace('*', "x").as_str() {
+        "4" => Ok(Bits::Four),
+        "4x16" => Ok(Bits::FourShift4),
+        "4x256" => Ok(Bits::FourShift8),
+        "4x4096" => Ok(Bits::FourShift12),
+        "8" => Ok(Bits::Eight),
+        "12" => Ok(Bits::Twelve),
+        "16" => Ok(Bits::Sixteen),
+        _ => Err("expected 4, 4x16, 4x256, 4x4096, 8, 12 or 16".to_string()),
+    }
+}
+
+fn parse_number_mode(value: &str) -> Result<NumberMode, String> {
+    match value.trim().to_lowercase().as_str() {
+        "unsigned" => Ok(NumberMode::Unsigned),
+        "signed" => Ok(NumberMode::Signed),
+        _ => Err("expected unsigned or signed".to_string()),
+    }
+}
+
+fn parse_fps(value: &str) -> Result<FrameRate, String> {
+    FrameRate::parse(value).ok_or_else(|| format!("expected 1 to {}", FrameRate::MAX))
+}
+
+fn parse_theme(value: &str) -> Result<ThemePreset, String> {
+    ThemePreset::from_name(value)
+        .ok_or_else(|| "expected default, gruvbox, dracula, solarized, light or plain".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_launch_options() {
+        let cli = Cli::try_parse_from([
+            "binbreak",
+            "--bits",
+            "4x256",
+            "--mode",
+            "practice",
+            "--numbers",
+            "signed",
+            "--theme",
+            "Dracula",
+            "--no-animation",
+            "--seed",
+            "42",
+            "--fps",
+            "60",
+        ])
+        .unwrap_or_else(|e| panic!("{e}"));
+        assert!(matches!(cli.bits, Some(Bits::FourShift8)));
+        assert_eq!(cli.mode, PlayMode::Practice);
+        assert_eq!(cli.numbers, NumberMode::Signed);
+        assert_eq!(cli.theme, Some(ThemePreset::Dracula));
+        assert!(cli.no_animation && !cli.guest);
+        assert_eq!(cli.seed, Some(42));
+        assert_eq!(cli.fps, FrameRate::parse("60"));
+        assert_eq!(cli.apply_to(Config::default()).get("theme"), Some("DRACULA"));
+
+        assert!(Cli::try_parse_from(["binbreak", "--bits", "7"]).is_err());
+        assert!(
+            Cli::try_parse_from(["binbreak", "--host", "4321", "--join", "host:4321"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["binbreak", "--fps", "0"]).is_err());
+    }
+
+    #[test]
+    fn prints_completions_for_the_flags() {
+        let cli = Cli::try_parse_from(["binbreak", "completions", "bash"])
+            .unwrap_or_else(|e| panic!("{e}"));
+        let mut script = Vec::new();
+        let Some(command) = cli.command else {
+            panic!("expected the completions subcommand");
+        };
+        command.run(&mut script).unwrap_or_else(|e| panic!("{e}"));
+        let script = String::from_utf8_lossy(&script);
+        assert!(script.contains("--bits") && script.contains("--seed"));
+    }
+}
@@ -0,0 +1,190 @@
+//! Terminal color capability detection and downgrading of theme colors
+//! to the nearest color the terminal can actually show.
+
+use crate::config::Config;
+use ratatui::style::Color;
+
+/// How many colors the terminal can display
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// The 16 standard ANSI colors
+    Ansi16,
+    /// The xterm 256 color palette
+    Ansi256,
+    /// 24-bit RGB
+    #[default]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// `colors = 16 | 256 | truecolor` in the config wins, otherwise guess from
+    /// `COLORTERM` and `TERM` like most terminal apps do
+    pub fn detect(config: &Config) -> Self {
+        if let Some(depth) = config.get("colors").and_then(Self::from_name) {
+            return depth;
+        }
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        Self::from_env(&colorterm, &term)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "16" | "ansi" => Some(Self::Ansi16),
+            "256" => Some(Self::Ansi256),
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            _ => None,
+        }
+    }
+
+    fn from_env(colorterm: &str, term: &str) -> Self {
+        if matches!(colorterm, "truecolor" | "24bit") {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            // Windows Terminal and modern conhost don't set TERM but handle RGB fine
+            Self::TrueColor
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+/// The standard ANSI colors with their usual xterm RGB values
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the 6x6x6 color cube in the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The closest color the given depth can show; named colors are always kept
+pub fn resolve(color: Color, depth: ColorDepth) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) if depth < ColorDepth::Ansi256 => indexed_rgb(index),
+        _ => return color,
+    };
+    match depth {
+        ColorDepth::TrueColor => color,
+        ColorDepth::Ansi256 => Color::Indexed(nearest_indexed(rgb)),
+        ColorDepth::Ansi16 => nearest_ansi16(rgb),
+    }
+}
+
+/// The color `t` of the way from `from` to `to` (0 to 1), as close as `depth` can show.
+/// The terminal's default colors have no RGB value to mix, so they switch halfway.
+pub fn blend(from: Color, to: Color, t: f32, depth: ColorDepth) -> Color {
+    let (Some(a), Some(b)) = (rgb(from), rgb(to)) else {
+        return if t < 0.5 { from } else { to };
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let mix = |a: u8, b: u8| {
+        (f32::from(a) + (f32::from(b) - f32::from(a)) * t.clamp(0.0, 1.0)).round() as u8
+    };
+    resolve(Color::Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2)), depth)
+}
+
+/// RGB value of a color, None for the terminal's default colors
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        named => ANSI16.iter().find(|(c, _)| *c == named).map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Perceptual "redmean" distance; plain RGB distance maps saturated colors to gray
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    let red_mean = (u32::from(r1) + u32::from(r2)) / 2;
+    (512 + red_mean) * d(r1, r2) + 1024 * d(g1, g2) + (767 - red_mean) * d(b1, b2)
+}
+
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16.iter().min_by_key(|(_, ansi)| distance(rgb, *ansi)).map_or(Color::Reset, |(c, _)| *c)
+}
+
+/// Best match among the color cube (16..=231) and the grayscale ramp (232..=255)
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let level =
+        |v: u8| (0..6_u8).min_by_key(|&i| CUBE_LEVELS[i as usize].abs_diff(v)).unwrap_or_default();
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube_index = 16 + 36 * r + 6 * g + b;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let average = ((u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3) as u8;
+    let gray_step = (average.saturating_sub(3) / 10).min(23);
+    let gray_index = 232 + gray_step;
+
+    if distance(rgb, indexed_rgb(gray_index)) < distance(rgb, indexed_rgb(cube_index)) {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+/// RGB value of a 256 color palette entry
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            let level = |n: u8| CUBE_LEVELS[n as usize];
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        },
+        _ => {
+            let v = 8 + 10 * (index - 232);
+            (v, v, v)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_depth_from_environment() {
+        assert_eq!(ColorDepth::from_env("truecolor", "xterm"), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::from_env("", "xterm-256color"), ColorDepth::Ansi256);
+        assert_eq!(ColorDepth::from_env("", "linux"), ColorDepth::Ansi16);
+        assert_eq!(ColorDepth::from_name("256"), Some(ColorDepth::Ansi256));
+    }
+
+    #[test]
+    fn downgrades_to_nearest_color() {
+        let orange = Color::Rgb(254, 128, 25);
+        assert_eq!(resolve(orange, ColorDepth::TrueColor), orange);
+        assert_eq!(resolve(orange, ColorDepth::Ansi256), Color::Indexed(208));
+        assert_eq!(resolve(Color::Rgb(40, 40, 40), ColorDepth::Ansi256), Color::Indexed(235));
+        assert_eq!(resolve(Color::Rgb(100, 255, 100), ColorDepth::Ansi16), Color::LightGreen);
+        assert_eq!(resolve(Color::Indexed(196), ColorDepth::Ansi16), Color::LightRed);
+        assert_eq!(resolve(Color::Cyan, ColorDepth::Ansi16), Color::Cyan);
+    }
+
+    #[test]
+    fn blends_through_rgb() {
+        let (black, white) = (Color::Black, Color::Rgb(255, 255, 255));
+        assert_eq!(blend(black, white, 0.5, ColorDepth::TrueColor), Color::Rgb(128, 128, 128));
+        assert_eq!(blend(black, white, 0.5, ColorDepth::Ansi16), Color::DarkGray);
+        assert_eq!(blend(black, white, 2.0, ColorDepth::TrueColor), white);
+        assert_eq!(blend(Color::Reset, white, 0.4, ColorDepth::TrueColor), Color::Reset);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// User configuration read from `config.txt` in the binbreak config directory.
+///
+/// The format is one `key = value` pair per line; blank lines and lines
+/// starting with `#` are ignored. Later keys override earlier ones.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Config {
+    entries: Vec<(String, String)>,
+}
+
+impl Config {
+    const FILE: &'static str = "config.txt";
+
+    /// Directory holding the config file and other user assets
+    pub fn dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir).join("binbreak"));
+        }
+        if let Some(dir) = std::env::var_os("APPDATA").filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir).join("binbreak"));
+        }
+        std::env::var_os("HOME")
+            .filter(|d| !d.is_empty())
+            .map(|home| PathBuf::from(home).join(".config").join("binbreak"))
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        Self::dir().map(|dir| dir.join(Self::FILE))
+    }
+
+    /// Load the config file, treating a missing or unreadable file as empty
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map_or_else(Self::default, |s| Self::parse(&s))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+            .collect();
+        Self { entries }
+    }
+
+    /// The same config with one more entry, which wins over the file's
+    pub fn with_override(mut self, key: &str, value: &str) -> Self {
+        self.entries.push((key.to_lowercase(), value.to_string()));
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// All entries below a dotted prefix, with the prefix stripped
+    pub fn section<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.entries.iter().filter_map(move |(k, v)| {
+            k.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .map(|rest| (rest, v.as_str()))
+        })
+    }
+}
+
+/// Detects edits to the config file by polling its modification time,
+/// so changes can be applied while the app is running.
+pub struct ConfigWatcher {
+    path: Option<PathBuf>,
+    last_modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+    pub fn new() -> Self {
+        let path = Config::path();
+        let last_modified = path.as_deref().and_then(modified_time);
+        Self { path, last_modified, last_check: Instant::now() }
+    }
+
+    /// Returns true once per change to the file (including creation and deletion)
+    pub fn poll_changed(&mut self) -> bool {
+        if self.last_check.elapsed() < Self::CHECK_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+        let modified = self.path.as_deref().and_then(modified_time);
+        if modified == self.last_modified {
+            return false;
+        }
+        self.last_modified = modified;
+        true
+    }
+}
+
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
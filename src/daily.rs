@@ -0,0 +1,205 @@
+//! The daily challenge: the same 20 questions for everyone on a day, dealt from the day's
+//! number. The first run of a day counts and goes to the leaderboard, if one is set up;
+//! later runs are practice. A run counts from its first answer, so leaving it doesn't get
+//! another go, and it can't be restarted.
+
+use crate::binary_numbers::BinaryNumbersGame;
+use crate::keybinds::KeyHint;
+use crate::leaderboard;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::playlist::Playlist;
+use crate::settings;
+use crate::storage;
+use crate::toast;
+use binbreak::{Bits, NumberMode};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Rect, Size};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::mpsc::Receiver;
+
+/// Questions of a daily challenge
+const LENGTH: usize = 20;
+
+/// Scores of the first run of each day played
+#[derive(Default, Debug, PartialEq, Eq)]
+struct DailyRecord {
+    /// Keyed by days since the Unix epoch, like the stats
+    scores: BTreeMap<u64, u32>,
+}
+
+impl DailyRecord {
+    const FILE: &'static str = "binbreak_daily.txt";
+
+    /// Lines are `day.<day>=<score>`
+    fn load() -> Self {
+        let contents = storage::active().read(Self::FILE).unwrap_or_default();
+        let scores = contents
+            .lines()
+            .filter_map(|line| {
+                let (name, score) = line.split_once('=')?;
+                let day = name.trim().strip_prefix("day.")?.parse().ok()?;
+                Some((day, score.trim().parse().ok()?))
+            })
+            .collect();
+        Self { scores }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let mut data = String::new();
+        for (day, score) in &self.scores {
+            let _ = writeln!(data, "day.{day}={score}");
+        }
+        storage::active().write(Self::FILE, &data)
+    }
+}
+
+/// How the run shown counts
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Run {
+    /// Nothing answered yet
+    Unanswered,
+    /// The day's first run, kept with a score of 0 until it is over
+    Counted,
+    /// The day was played before
+    Practice,
+    /// Over, and kept if it counted
+    Recorded,
+}
+
+/// A run of the day's questions
+pub struct DailyChallenge {
+    day: u64,
+    game: BinaryNumbersGame,
+    run: Run,
+    /// The score on its way to the leaderboard
+    submission: Option<Receiver<Result<(), String>>>,
+}
+
+impl DailyChallenge {
+    pub fn new(day: u64) -> Self {
+        let game = BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned)
+            .with_playlist(Playlist::repeat(LENGTH, Bits::Eight, NumberMode::Unsigned))
+            .with_standard_questions()
+            .with_seed(day)
+            .with_countdown()
+            .with_restart(false);
+        Self { day, game, run: Run::Unanswered, submission: None }
+    }
+
+    fn after_game_update(&mut self) {
+        if self.run == Run::Unanswered && self.game.answered() > 0 {
+            self.start_run();
+        }
+        if self.game.is_over() && matches!(self.run, Run::Counted | Run::Practice) {
+            self.record_run();
+        }
+    }
+
+    /// Mark the day as played once the first answer is in, unless it was played before
+    fn start_run(&mut self) {
+        let mut record = DailyRecord::load();
+        if record.scores.contains_key(&self.day) {
+            self.run = Run::Practice;
+            return;
+        }
+        record.scores.insert(self.day, 0);
+        let _ = record.save();
+        self.run = Run::Counted;
+    }
+
+    /// Keep the score of the day's first run and send it on
+    fn record_run(&mut self) {
+        let counted = self.run == Run::Counted;
+        self.run = Run::Recorded;
+        let mut record = DailyRecord::load();
+        if !counted {
+            let first = record.scores.get(&self.day).copied().unwrap_or_default();
+            toast::show(format!("Only the first run of the day counts: {first}"));
+            return;
+        }
+        let score = self.game.score();
+        record.scores.insert(self.day, score);
+        let _ = record.save();
+        if let Some(endpoint) = settings::leaderboard()
+            && !settings::get().guest
+        {
+            self.submission = Some(leaderboard::submit(endpoint, self.day, score));
+        }
+    }
+
+    fn poll_submission(&mut self) {
+        let Some(sent) = self.submission.as_ref().and_then(|sent| sent.try_recv().ok()) else {
+            return;
+        };
+        self.submission = None;
+        match sent {
+            Ok(()) => toast::show("Score sent to the leaderboard"),
+            Err(err) => toast::show(format!("Couldn't send the score: {err}")),
+        }
+    }
+}
+
+impl WidgetRef for DailyChallenge {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        self.game.render_ref(area, buf);
+    }
+}
+
+/// Everything goes to the run, which is watched for its first answer and its end
+impl MainScreenWidget for DailyChallenge {
+    fn run(&mut self, dt: f64) {
+        self.game.run(dt);
+        self.after_game_update();
+        self.poll_submission();
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        self.game.handle_input(input);
+        self.after_game_update();
+    }
+
+    fn handle_key_release(&mut self, input: KeyEvent) {
+        self.game.handle_key_release(input);
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        self.game.handle_mouse(mouse);
+        self.after_game_update();
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        self.game.handle_paste(text);
+        self.after_game_update();
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.game.is_exit_intended()
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        MainScreenWidget::key_hints(&self.game)
+    }
+
+    fn min_size(&self) -> Size {
+        MainScreenWidget::min_size(&self.game)
+    }
+
+    /// Polls for the leaderboard's answer while a score is sent
+    fn is_animating(&self) -> bool {
+        MainScreenWidget::is_animating(&self.game) || self.submission.is_some()
+    }
+
+    fn animation_finished(&mut self) {
+        self.game.animation_finished();
+    }
+
+    fn config_changed(&mut self) {
+        MainScreenWidget::config_changed(&mut self.game);
+    }
+
+    fn save(&mut self) {
+        MainScreenWidget::save(&mut self.game);
+    }
+}
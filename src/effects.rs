@@ -0,0 +1,615 @@
+//! Short-lived decoration drawn over a screen: confetti and the like. Effects run on
+//! the animation clock and stay out of the way of players who asked for less motion.
+
+use crate::animation;
+use crate::color;
+use crate::settings;
+use crate::terminal_bg::Background;
+use crate::theme;
+use crate::utils::ProceduralAnimationWidget;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Color;
+use ratatui::widgets::Widget;
+use std::time::Duration;
+
+/// The terminal's own background, roughly: effects fade towards it
+fn background_color() -> Color {
+    match settings::get().background {
+        Background::Dark => Color::Black,
+        Background::Light => Color::White,
+    }
+}
+
+/// Cheap, well mixed hash of two numbers, for effects that look random but are the
+/// same in every frame
+fn noise(a: u32, b: u32) -> u32 {
+    let mut h = a.wrapping_mul(0x9E37_79B9) ^ b.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297A_2D39);
+    h ^ (h >> 15)
+}
+
+/// What moves behind the start menu, set with `menu_background` in the config
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum MenuBackground {
+    #[default]
+    None,
+    /// Falling columns of binary digits, see [`MatrixRainWidget`]
+    Rain,
+    /// Flowing bands of color, see [`PlasmaWidget`]
+    Plasma,
+}
+
+impl MenuBackground {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" | "off" => Some(Self::None),
+            "rain" | "matrix" => Some(Self::Rain),
+            "plasma" => Some(Self::Plasma),
+            _ => None,
+        }
+    }
+
+    /// Something is drawn and it moves
+    pub fn is_animating(self) -> bool {
+        self != Self::None && !settings::get().reduce_motion && !animation::is_paused()
+    }
+}
+
+/// Columns of 0s and 1s falling at their own speed, each pulling a fading trail.
+/// Only some columns rain, so text on top stays easy to read.
+pub struct MatrixRainWidget {
+    head: Color,
+    trail: Color,
+}
+
+impl MatrixRainWidget {
+    /// One in this many columns rains
+    const SPARSENESS: u32 = 3;
+    /// Cells behind the head before the trail has faded out
+    const TRAIL: u16 = 8;
+    /// Times a second the digits in a trail change
+    const FLICKER: f32 = 6.0;
+
+    pub const fn new(head: Color, trail: Color) -> Self {
+        Self { head, trail }
+    }
+}
+
+/// Nothing is drawn with reduced motion
+impl Widget for MatrixRainWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if settings::get().reduce_motion {
+            return;
+        }
+        let t = animation::now().as_secs_f32();
+        let depth = settings::get().color_depth;
+        let background = background_color();
+        let cycle = f32::from(area.height + Self::TRAIL);
+        for column in 0..area.width {
+            let seed = noise(u32::from(column), 0);
+            if !seed.is_multiple_of(Self::SPARSENESS) {
+                continue;
+            }
+            // 4 to 12 rows a second, starting anywhere in the cycle
+            let speed = 4.0 + (seed >> 8 & 0xFF) as f32 / 32.0;
+            let head = (t * speed + (seed >> 16) as f32).rem_euclid(cycle) as u16;
+            let flicker = (t * Self::FLICKER) as u32;
+            for behind in 0..Self::TRAIL {
+                let Some(row) = head.checked_sub(behind).filter(|row| *row < area.height) else {
+                    continue;
+                };
+                let bit = noise(u32::from(column) << 16 | u32::from(row), flicker) & 1;
+                let color = if behind == 0 {
+                    self.head
+                } else {
+                    let faded = f32::from(behind) / f32::from(Self::TRAIL);
+                    color::blend(self.trail, background, faded, depth)
+                };
+                if let Some(cell) = buf.cell_mut(Position::new(area.x + column, area.y + row)) {
+                    cell.set_char(if bit == 1 { '1' } else { '0' }).set_fg(color);
+                }
+            }
+        }
+    }
+}
+
+/// Bands of color flowing across an area, drawn with shade characters. A procedural
+/// animation over a block of shades, so it pauses and loops like the title does.
+pub struct PlasmaWidget {
+    animation: ProceduralAnimationWidget,
+    width: u16,
+    height: u16,
+}
+
+impl PlasmaWidget {
+    /// Shades from the faintest to the strongest part of the field
+    const SHADES: [char; 4] = ['·', '░', '▒', '▓'];
+    /// How far the strongest part goes from `dim` to the highlight color, low enough
+    /// that text on top stays easy to read
+    const INTENSITY: f32 = 0.6;
+    /// One loop of the field takes 200 frames of 50 ms
+    const NUM_FRAMES: usize = 200;
+
+    /// A field of `width` by `height` cells, fading from `dim` to `bright`
+    pub fn new(width: u16, height: u16, dim: Color, bright: Color) -> Self {
+        let row = Self::SHADES[0].to_string().repeat(usize::from(width));
+        let art = (0..height).map(|_| format!("{row}\n")).collect();
+        let depth = settings::get().color_depth;
+        let color_fn = move |x, y, progress, _cycle, highlight| {
+            color::blend(dim, highlight, plasma(x, y, progress) * Self::INTENSITY, depth)
+        };
+        let char_fn = |x, y, progress, _cycle, _ch| {
+            let level = (plasma(x, y, progress) * Self::SHADES.len() as f32) as usize;
+            Self::SHADES[level.min(Self::SHADES.len() - 1)]
+        };
+        let mut animation = ProceduralAnimationWidget::new(
+            art,
+            Self::NUM_FRAMES,
+            Duration::from_millis(50),
+            color_fn,
+        )
+        .with_char_fn(char_fn);
+        animation.set_highlight_color(bright);
+        Self { animation, width, height }
+    }
+
+    /// The area the field was made for
+    pub const fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+}
+
+/// Strength of the plasma field at a cell, 0 to 1. Every wave turns a whole number of
+/// times per loop, so the end of a loop flows into the start of the next.
+fn plasma(x: usize, y: usize, progress: f32) -> f32 {
+    let (x, y) = (x as f32, y as f32 * 2.0);
+    let t = progress * std::f32::consts::TAU;
+    let waves = (x * 0.12 + t).sin()
+        + (y * 0.15 - t).sin()
+        + ((x + y) * 0.08 + 2.0 * t).sin()
+        + ((x * x + y * y).sqrt() * 0.1 - t).sin();
+    (waves / 4.0 + 1.0) / 2.0
+}
+
+/// Nothing is drawn with reduced motion
+impl Widget for &PlasmaWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !settings::get().reduce_motion {
+            self.animation.render_to_buffer(area, buf);
+        }
+    }
+}
+
+/// How an effect fades: its strength from 1 at the start to 0 at the end
+#[derive(Copy, Clone, Debug)]
+pub enum Easing {
+    Linear,
+    /// Fades quickly at first, then lingers
+    EaseOut,
+}
+
+impl Easing {
+    /// Strength `progress` of the way through (0 to 1)
+    fn strength(self, progress: f32) -> f32 {
+        let left = 1.0 - progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => left,
+            Self::EaseOut => left * left,
+        }
+    }
+}
+
+/// A bit of confetti. Its path follows from where and when it was thrown, so
+/// nothing has to be stepped each tick.
+struct Particle {
+    /// Cells from the center of the area it is drawn in
+    origin: (f32, f32),
+    /// Cells per second
+    velocity: (f32, f32),
+    /// Animation time it was thrown
+    born: Duration,
+    lifetime: Duration,
+    ch: char,
+    color: Color,
+}
+
+impl Particle {
+    /// Where the particle is at animation time `now`, None before or after its lifetime
+    fn position_at(&self, now: Duration, gravity: f32) -> Option<(f32, f32)> {
+        let age = now.checked_sub(self.born).filter(|age| *age < self.lifetime)?;
+        let t = age.as_secs_f32();
+        Some((
+            self.origin.0 + self.velocity.0 * t,
+            self.origin.1 + self.velocity.1 * t + 0.5 * gravity * t * t,
+        ))
+    }
+}
+
+/// Particles thrown out in bursts that fall and fade out after a while
+pub struct ParticleField {
+    particles: Vec<Particle>,
+    /// Downward acceleration in cells per second squared
+    gravity: f32,
+    chars: &'static [char],
+}
+
+impl ParticleField {
+    /// Cells are about twice as high as wide, so particles move half as fast up and down
+    const ASPECT: f32 = 0.5;
+
+    pub fn confetti() -> Self {
+        const CHARS: &[char] = &['*', '+', '•', '·', '▪', '◆', '~'];
+        Self { particles: Vec::new(), gravity: 30.0, chars: CHARS }
+    }
+
+    /// Throw `count` particles up and out from `x`, `y` cells off the center of the area
+    /// the field is drawn in, in the given colors
+    pub fn burst(&mut self, x: f32, y: f32, count: usize, colors: &[Color], rng: &mut impl Rng) {
+        if settings::get().reduce_motion {
+            return;
+        }
+        let now = animation::now();
+        self.particles.retain(|particle| particle.position_at(now, self.gravity).is_some());
+        for _ in 0..count {
+            self.particles.push(Particle {
+                origin: (x, y),
+                velocity: (
+                    rng.random_range(-25.0..25.0),
+                    rng.random_range(-36.0..-12.0) * Self::ASPECT,
+                ),
+                born: now,
+                lifetime: Duration::from_millis(rng.random_range(800..1600)),
+                ch: self.chars.choose(rng).copied().unwrap_or('*'),
+                color: colors.choose(rng).copied().unwrap_or(Color::Reset),
+            });
+        }
+    }
+
+    /// Some particle is still flying
+    pub fn is_active(&self) -> bool {
+        let now = animation::now();
+        !animation::is_paused()
+            && self
+                .particles
+                .iter()
+                .any(|particle| particle.position_at(now, self.gravity).is_some())
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+}
+
+/// Particles only take the cell's character and color, the background shows through
+impl Widget for &ParticleField {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let now = animation::now();
+        let center = (
+            f32::from(area.x) + f32::from(area.width) / 2.0,
+            f32::from(area.y) + f32::from(area.height) / 2.0,
+        );
+        for particle in &self.particles {
+            let Some((x, y)) = particle.position_at(now, self.gravity) else {
+                continue;
+            };
+            let (x, y) = ((center.0 + x).floor(), (center.1 + y).floor());
+            if x < 0.0 || y < 0.0 {
+                continue;
+            }
+            let position = Position::new(x as u16, y as u16);
+            if area.contains(position)
+                && let Some(cell) = buf.cell_mut(position)
+            {
+                cell.set_char(particle.ch).set_fg(particle.color);
+            }
+        }
+    }
+}
+
+/// Jolts a screen sideways for a moment, e.g. after a wrong answer
+#[derive(Default)]
+pub struct Shake {
+    /// Animation time the shake started
+    started: Option<Duration>,
+}
+
+impl Shake {
+    const DURATION: Duration = Duration::from_millis(150);
+    /// Cells the first jolt moves, later ones move less
+    const AMPLITUDE: f32 = 2.0;
+    /// Time between changes of direction
+    const SWING: Duration = Duration::from_millis(25);
+
+    pub fn start(&mut self) {
+        if !settings::get().reduce_motion {
+            self.started = Some(animation::now());
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !animation::is_paused() && self.elapsed().is_some()
+    }
+
+    /// Time since the start, None when not shaking
+    fn elapsed(&self) -> Option<Duration> {
+        let elapsed = animation::now().checked_sub(self.started?)?;
+        (elapsed < Self::DURATION).then_some(elapsed)
+    }
+
+    /// Cells to move right (or left, when negative) right now
+    fn offset(&self) -> i32 {
+        let Some(elapsed) = self.elapsed() else {
+            return 0;
+        };
+        let progress = elapsed.as_secs_f32() / Self::DURATION.as_secs_f32();
+        let amplitude = Self::AMPLITUDE * Easing::Linear.strength(progress);
+        let swing = elapsed.as_millis() / Self::SWING.as_millis();
+        let direction = if swing % 2 == 0 { 1.0 } else { -1.0 };
+        (amplitude * direction).round() as i32
+    }
+
+    /// `rect` moved by the current offset, kept inside `bounds`
+    pub fn apply(&self, rect: Rect, bounds: Rect) -> Rect {
+        let max_x = bounds.right().saturating_sub(rect.width).max(bounds.x);
+        let x = (i32::from(rect.x) + self.offset()).clamp(i32::from(bounds.x), i32::from(max_x));
+        Rect { x: x as u16, ..rect }
+    }
+}
+
+/// Tints an area's background for a moment, e.g. green or red after an answer
+pub struct Flash {
+    duration: Duration,
+    easing: Easing,
+    /// Animation time the flash started and its color
+    started: Option<(Duration, Color)>,
+}
+
+impl Flash {
+    /// How far the background goes towards the flash color at its brightest, so the
+    /// text on it stays readable
+    const PEAK: f32 = 0.35;
+
+    pub const fn new(duration: Duration, easing: Easing) -> Self {
+        Self { duration, easing, started: None }
+    }
+
+    pub fn start(&mut self, color: Color) {
+        if !settings::get().reduce_motion {
+            self.started = Some((animation::now(), color));
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !animation::is_paused() && self.strength().is_some()
+    }
+
+    /// How strong the flash is right now and its color, None when not flashing
+    fn strength(&self) -> Option<(f32, Color)> {
+        let (started, color) = self.started?;
+        let elapsed = animation::now().checked_sub(started)?;
+        (elapsed < self.duration).then(|| {
+            let progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            (self.easing.strength(progress), color)
+        })
+    }
+}
+
+/// Cells on the terminal's own background are tinted as if it were black or white
+impl Widget for &Flash {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some((strength, color)) = self.strength() else {
+            return;
+        };
+        if theme::current().monochrome {
+            return;
+        }
+        let depth = settings::get().color_depth;
+        let default_bg = background_color();
+        for position in area.positions() {
+            if let Some(cell) = buf.cell_mut(position) {
+                let bg = if cell.bg == Color::Reset { default_bg } else { cell.bg };
+                cell.set_bg(color::blend(bg, color, strength * Flash::PEAK, depth));
+            }
+        }
+    }
+}
+
+/// Fades from one screen to the next: the last frame of the old one dims to the
+/// background, then the new one brightens from it
+pub struct Fade {
+    outgoing: Buffer,
+    /// Animation time the fade started
+    started: Duration,
+}
+
+impl Fade {
+    const DURATION: Duration = Duration::from_millis(300);
+
+    /// None with reduced motion, screens are switched at once then
+    pub fn new(outgoing: Buffer) -> Option<Self> {
+        (!settings::get().reduce_motion).then(|| Self { outgoing, started: animation::now() })
+    }
+
+    /// How far the fade is, None once it is over
+    fn progress(&self) -> Option<f32> {
+        let elapsed = animation::now().checked_sub(self.started)?;
+        (elapsed < Self::DURATION).then(|| elapsed.as_secs_f32() / Self::DURATION.as_secs_f32())
+    }
+
+    pub fn is_active(&self) -> bool {
+        !animation::is_paused() && self.progress().is_some()
+    }
+}
+
+/// Drawn over the new screen, which was drawn into `buf` already
+impl Widget for &Fade {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(progress) = self.progress() else {
+            return;
+        };
+        let theme = theme::current();
+        let depth = settings::get().color_depth;
+        let background = background_color();
+        let old = progress < 0.5;
+        let dim = if old { progress * 2.0 } else { (1.0 - progress) * 2.0 };
+        for position in area.positions() {
+            if old
+                && let Some(cell) = self.outgoing.cell(position).cloned()
+                && let Some(target) = buf.cell_mut(position)
+            {
+                *target = cell;
+            }
+            if theme.monochrome {
+                continue;
+            }
+            if let Some(cell) = buf.cell_mut(position) {
+                let fg = if cell.fg == Color::Reset { theme.text } else { cell.fg };
+                let bg = if cell.bg == Color::Reset { background } else { cell.bg };
+                cell.set_fg(color::blend(fg, background, dim, depth));
+                if cell.bg != Color::Reset {
+                    cell.set_bg(color::blend(bg, background, dim, depth));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn confetti_flies_up_falls_and_is_gone_after_its_lifetime() {
+        let mut field = ParticleField::confetti();
+        field.burst(0.0, 0.0, 20, &[Color::Red], &mut StdRng::seed_from_u64(7));
+        assert!(field.is_active());
+
+        let area = Rect::new(0, 0, 60, 40);
+        let mut buf = Buffer::empty(area);
+        animation::advance(Duration::from_millis(100));
+        (&field).render(area, &mut buf);
+        let drawn: Vec<_> = buf.content.iter().filter(|cell| cell.symbol() != " ").collect();
+        assert!(!drawn.is_empty() && drawn.iter().all(|cell| cell.fg == Color::Red));
+        let above = |buf: &Buffer| buf.content[..60 * 20].iter().any(|cell| cell.symbol() != " ");
+        assert!(above(&buf), "thrown upwards first");
+
+        animation::advance(Duration::from_millis(1500));
+        assert!(!field.is_active());
+        let mut buf = Buffer::empty(area);
+        (&field).render(area, &mut buf);
+        assert!(!above(&buf));
+    }
+
+    #[test]
+    fn shake_swings_less_and_less_and_stays_in_bounds() {
+        let mut shake = Shake::default();
+        let (rect, bounds) = (Rect::new(5, 2, 10, 4), Rect::new(0, 0, 16, 8));
+        assert_eq!(shake.apply(rect, bounds), rect);
+
+        shake.start();
+        let mut offsets = Vec::new();
+        while shake.is_active() {
+            offsets.push(shake.offset());
+            animation::advance(Duration::from_millis(25));
+        }
+        assert_eq!(offsets, [2, -2, 1, -1, 1, 0]);
+        assert_eq!(shake.offset(), 0);
+
+        shake.start();
+        assert_eq!(shake.apply(rect, bounds).x, 6, "clamped to the right edge");
+    }
+
+    #[test]
+    fn fade_shows_the_old_screen_then_the_new_one() {
+        settings::set(settings::Settings {
+            color_depth: color::ColorDepth::TrueColor,
+            background: Background::Dark,
+            ..settings::get()
+        });
+        let area = Rect::new(0, 0, 1, 1);
+        let mut old = Buffer::empty(area);
+        old.content[0].set_char('o').set_fg(Color::Rgb(200, 200, 200));
+        let Some(fade) = Fade::new(old) else { panic!("motion is on") };
+        let frame = |fade: &Fade| {
+            let mut buf = Buffer::empty(area);
+            buf.content[0].set_char('n').set_fg(Color::Rgb(200, 200, 200));
+            fade.render(area, &mut buf);
+            (buf.content[0].symbol().to_string(), buf.content[0].fg)
+        };
+        assert_eq!(frame(&fade), ("o".to_string(), Color::Rgb(200, 200, 200)));
+        animation::advance(Duration::from_millis(75));
+        assert_eq!(frame(&fade), ("o".to_string(), Color::Rgb(100, 100, 100)));
+        animation::advance(Duration::from_millis(150));
+        assert_eq!(frame(&fade), ("n".to_string(), Color::Rgb(100, 100, 100)));
+        animation::advance(Duration::from_millis(75));
+        assert!(!fade.is_active());
+        assert_eq!(frame(&fade), ("n".to_string(), Color::Rgb(200, 200, 200)));
+    }
+
+    #[test]
+    fn rain_falls_in_some_columns() {
+        let area = Rect::new(0, 0, 30, 10);
+        let rained = |buf: &Buffer| -> Vec<u16> {
+            (0..area.width)
+                .filter(|&x| (0..area.height).any(|y| buf[(x, y)].symbol() != " "))
+                .collect()
+        };
+        let mut before = Buffer::empty(area);
+        MatrixRainWidget::new(Color::Green, Color::Green).render(area, &mut before);
+        animation::advance(Duration::from_millis(500));
+        let mut after = Buffer::empty(area);
+        MatrixRainWidget::new(Color::Green, Color::Green).render(area, &mut after);
+
+        let columns = rained(&before);
+        assert!(columns.len() < 20, "only some columns rain: {columns:?}");
+        assert!(columns.iter().all(|&x| noise(u32::from(x), 0).is_multiple_of(3)));
+        assert!(rained(&after).iter().all(|x| columns.contains(x)));
+        assert_ne!(before, after, "it moves");
+    }
+
+    #[test]
+    fn plasma_loops_through_every_shade() {
+        let field = PlasmaWidget::new(40, 12, Color::Black, Color::Blue);
+        assert_eq!(field.size(), (40, 12));
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        (&field).render(area, &mut buf);
+        for shade in PlasmaWidget::SHADES {
+            assert!(buf.content.iter().any(|cell| cell.symbol() == shade.to_string()), "{shade}");
+        }
+        for (x, y) in [(0, 0), (17, 5), (39, 11)] {
+            assert!((plasma(x, y, 0.0) - plasma(x, y, 1.0)).abs() < 1e-4, "seamless loop");
+        }
+    }
+
+    #[test]
+    fn flash_tints_the_background_less_and_less() {
+        settings::set(settings::Settings {
+            color_depth: color::ColorDepth::TrueColor,
+            background: Background::Dark,
+            ..settings::get()
+        });
+        let mut flash = Flash::new(Duration::from_millis(200), Easing::Linear);
+        flash.start(Color::Rgb(0, 200, 0));
+        let area = Rect::new(0, 0, 2, 1);
+        let tint = |flash: &Flash| {
+            let mut buf = Buffer::empty(area);
+            flash.render(area, &mut buf);
+            buf.content[1].bg
+        };
+        assert_eq!(tint(&flash), Color::Rgb(0, 70, 0));
+        animation::advance(Duration::from_millis(100));
+        assert_eq!(tint(&flash), Color::Rgb(0, 35, 0));
+        animation::advance(Duration::from_millis(100));
+        assert!(!flash.is_active());
+        assert_eq!(tint(&flash), Color::Reset);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::message::Message;
+use crossterm::event;
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Terminal input, read on a thread of its own so the app never blocks on it, and a
+/// tick whenever the tick rate passes without one.
+pub struct Events {
+    /// Input with the moment it was read
+    receiver: Receiver<(Instant, io::Result<Message>)>,
+    last_tick: Instant,
+    /// How long the last input waited before the app took it
+    latency: Duration,
+}
+
+impl Events {
+    /// Start reading terminal input and listening for termination signals in the background
+    pub fn start() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        forward_signals(sender.clone());
+        thread::spawn(move || {
+            loop {
+                let msg = event::read().map(Message::from_event);
+                let failed = msg.is_err();
+                // The app is gone, or the terminal can't be read anymore
+                if sender.send((Instant::now(), msg)).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self::new(receiver)
+    }
+
+    fn new(receiver: Receiver<(Instant, io::Result<Message>)>) -> Self {
+        Self { receiver, last_tick: Instant::now(), latency: Duration::ZERO }
+    }
+
+    /// Wait for the next input, or a tick once `tick_rate` passed since the last one.
+    /// A tick that is due goes first, so a flood of mouse moves can't hold up time.
+    pub fn next(&mut self, tick_rate: Duration) -> io::Result<Message> {
+        let Some(timeout) = (self.last_tick + tick_rate).checked_duration_since(Instant::now())
+        else {
+            return Ok(self.tick());
+        };
+        match self.receiver.recv_timeout(timeout) {
+            Ok((read_at, msg)) => {
+                self.latency = read_at.elapsed();
+                msg
+            },
+            Err(RecvTimeoutError::Timeout) => Ok(self.tick()),
+            Err(RecvTimeoutError::Disconnected) => Err(io::ErrorKind::BrokenPipe.into()),
+        }
+    }
+
+    /// Input that arrived in the meantime, without waiting for more
+    pub fn pending(&mut self) -> impl Iterator<Item = io::Result<Message>> + '_ {
+        let latency = &mut self.latency;
+        self.receiver.try_iter().map(move |(read_at, msg)| {
+            *latency = read_at.elapsed();
+            msg
+        })
+    }
+
+    pub const fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    fn tick(&mut self) -> Message {
+        let now = Instant::now();
+        let dt = now - self.last_tick;
+        self.last_tick = now;
+        Message::Tick(dt.as_secs_f64())
+    }
+}
+
+/// SIGTERM, SIGHUP (the terminal was closed) and an external SIGINT end the app like
+/// quitting does, so progress is saved and the terminal is restored
+#[cfg(unix)]
+fn forward_signals(sender: Sender<(Instant, io::Result<Message>)>) {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+    use signal_hook::iterator::Signals;
+
+    let mut signals = match Signals::new([SIGTERM, SIGHUP, SIGINT]) {
+        Ok(signals) => signals,
+        Err(err) => {
+            tracing::warn!("can't listen for termination signals: {err}");
+            return;
+        },
+    };
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            tracing::info!(signal, "termination signal received");
+            if sender.send((Instant::now(), Ok(Message::Terminate))).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Windows closes console apps without a signal the app could handle
+#[cfg(not(unix))]
+const fn forward_signals(_sender: Sender<(Instant, io::Result<Message>)>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Size;
+
+    #[test]
+    fn input_arrives_in_order_and_ticks_fill_the_gaps() {
+        let (sender, receiver) = mpsc::channel();
+        let mut events = Events::new(receiver);
+        let resize = Message::Resize(Size::new(80, 24));
+        assert!(sender.send((Instant::now(), Ok(resize.clone()))).is_ok());
+        assert!(sender.send((Instant::now(), Ok(Message::AnimationFinished))).is_ok());
+
+        let hour = Duration::from_secs(3600);
+        assert_eq!(events.next(hour).ok(), Some(resize));
+        assert_eq!(
+            events.pending().filter_map(Result::ok).collect::<Vec<_>>(),
+            [Message::AnimationFinished]
+        );
+        assert!(matches!(events.next(Duration::from_millis(1)), Ok(Message::Tick(dt)) if dt > 0.0));
+
+        drop(sender);
+        assert!(events.next(hour).is_err(), "input closed");
+    }
+}
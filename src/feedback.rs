@@ -0,0 +1,145 @@
+//! What the game signals besides drawing it: sound effects, and for players without
+//! sound the terminal bell or a flash of the whole screen. Screens `give` a cue; the app
+//! collects bells and flashes into its `Alerts`, like toasts.
+
+use crate::animation;
+use crate::audio;
+use crate::settings;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Widget;
+use std::cell::Cell;
+use std::time::Duration;
+
+/// Something happened that the player should notice
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Cue {
+    Correct,
+    Wrong,
+    /// The time for a question ran out, or it was skipped
+    Timeout,
+    /// A step of the countdown before a run, or one of the last seconds of a question
+    Tick,
+    /// A new personal best
+    Record,
+}
+
+impl Cue {
+    /// Cues the bell is for, the ones that cost a life
+    const fn alerts(self) -> bool {
+        matches!(self, Self::Wrong | Self::Timeout)
+    }
+}
+
+/// How wrong answers and timeouts are signalled without sound, from the `bell` key of the
+/// config: `ring` the terminal bell, `flash` the screen, or `both`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Bell {
+    #[default]
+    Off,
+    Ring,
+    Flash,
+    Both,
+}
+
+impl Bell {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "off" | "false" | "no" => Some(Self::Off),
+            "ring" | "on" | "true" | "yes" => Some(Self::Ring),
+            "flash" => Some(Self::Flash),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    const fn rings(self) -> bool {
+        matches!(self, Self::Ring | Self::Both)
+    }
+
+    const fn flashes(self) -> bool {
+        matches!(self, Self::Flash | Self::Both)
+    }
+}
+
+thread_local! {
+    /// A cue the bell is for was given since the app last collected alerts
+    static PENDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Signal `cue` in every way the settings ask for
+pub fn give(cue: Cue) {
+    audio::play(cue);
+    if cue.alerts() && settings::get().bell != Bell::Off {
+        PENDING.set(true);
+    }
+}
+
+/// The flash on screen, if one is
+#[derive(Default)]
+pub struct Alerts {
+    /// Animation time the flash started at
+    flash_started: Option<Duration>,
+}
+
+impl Alerts {
+    /// Time the screen stays flashed
+    const FLASH: Duration = Duration::from_millis(150);
+
+    /// Take the alert given since the last call and end a flash whose time is up. True if
+    /// the terminal bell should ring.
+    pub fn update(&mut self) -> bool {
+        let now = animation::now();
+        self.flash_started =
+            self.flash_started.filter(|&started| now.saturating_sub(started) < Self::FLASH);
+        if !PENDING.take() {
+            return false;
+        }
+        let bell = settings::get().bell;
+        if bell.flashes() {
+            self.flash_started = Some(now);
+        }
+        bell.rings()
+    }
+
+    pub const fn is_active(&self) -> bool {
+        self.flash_started.is_some()
+    }
+}
+
+/// Everything drawn so far in reverse video
+impl Widget for &Alerts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.is_active() {
+            buf.set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lost_lives_ring_or_flash_as_the_bell_setting_says() {
+        let before = settings::get();
+        let mut alerts = Alerts::default();
+        settings::set(settings::Settings { bell: Bell::Ring, ..before });
+        give(Cue::Correct);
+        assert!(!alerts.update());
+        give(Cue::Timeout);
+        assert!(alerts.update());
+        assert!(!alerts.is_active());
+
+        settings::set(settings::Settings { bell: Bell::Flash, ..before });
+        give(Cue::Wrong);
+        assert!(!alerts.update());
+        assert!(alerts.is_active());
+        animation::advance(Alerts::FLASH);
+        alerts.update();
+        assert!(!alerts.is_active());
+        assert_eq!(Bell::parse(" Both"), Some(Bell::Both));
+        settings::set(before);
+    }
+}
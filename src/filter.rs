@@ -0,0 +1,112 @@
+//! Narrowing down the numbers a run asks about, e.g. to drill only multiples of 8.
+
+use crate::bits::{Bits, NumberMode};
+use crate::question::value_of;
+
+/// Numbers matched by one rule of a filter, written `12`, `16..=31` or `*8`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ValueRule {
+    Value(i32),
+    /// Both ends included; `16..32` is read as `16..=31`
+    Range(i32, i32),
+    MultipleOf(i32),
+}
+
+impl ValueRule {
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        if let Some(factor) = rule.strip_prefix('*') {
+            return factor.trim().parse().ok().filter(|&factor| factor > 0).map(Self::MultipleOf);
+        }
+        if let Some((start, end)) = rule.split_once("..") {
+            let start: i32 = start.trim().parse().ok()?;
+            let end = match end.strip_prefix('=') {
+                Some(end) => end.trim().parse().ok()?,
+                None => end.trim().parse::<i32>().ok()?.checked_sub(1)?,
+            };
+            return (start <= end).then_some(Self::Range(start, end));
+        }
+        rule.parse().ok().map(Self::Value)
+    }
+
+    pub const fn matches(self, value: i32) -> bool {
+        match self {
+            Self::Value(number) => value == number,
+            Self::Range(start, end) => start <= value && value <= end,
+            Self::MultipleOf(factor) => value % factor == 0,
+        }
+    }
+}
+
+/// The answers a run may ask: those matching any `only` rule, if there are any, and
+/// no `except` rule. Values are the answers as shown, scaled and signed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValueFilter {
+    only: Vec<ValueRule>,
+    except: Vec<ValueRule>,
+}
+
+impl ValueFilter {
+    /// Rules separated by commas, e.g. `*8, 100..=200`; None if any can't be read
+    pub fn parse_rules(rules: &str) -> Option<Vec<ValueRule>> {
+        rules.split(',').filter(|rule| !rule.trim().is_empty()).map(ValueRule::parse).collect()
+    }
+
+    /// Ask only numbers matching one of `rules`
+    pub fn only(mut self, rules: Vec<ValueRule>) -> Self {
+        self.only = rules;
+        self
+    }
+
+    /// Never ask numbers matching one of `rules`
+    pub fn except(mut self, rules: Vec<ValueRule>) -> Self {
+        self.except = rules;
+        self
+    }
+
+    /// The filter has rules at all
+    pub fn is_active(&self) -> bool {
+        !self.only.is_empty() || !self.except.is_empty()
+    }
+
+    pub fn allows(&self, value: i32) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|rule| rule.matches(value)))
+            && !self.except.iter().any(|rule| rule.matches(value))
+    }
+
+    /// The patterns of a mode whose values pass, lowest first. None without rules, and
+    /// when they leave nothing of the mode to ask, so the mode can still be played.
+    pub fn patterns(&self, bits: &Bits, number_mode: NumberMode) -> Option<Vec<u32>> {
+        if !self.is_active() {
+            return None;
+        }
+        let space = bits.value_space() as u32;
+        let patterns: Vec<u32> =
+            (0..space).filter(|&p| self.allows(value_of(bits, number_mode, p))).collect();
+        (!patterns.is_empty()).then_some(patterns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_keep_the_only_values_minus_the_excepted_ones() {
+        assert_eq!(ValueRule::parse(" *8"), Some(ValueRule::MultipleOf(8)));
+        assert_eq!(ValueRule::parse("-8..0"), Some(ValueRule::Range(-8, -1)));
+        assert_eq!(ValueRule::parse("3..=3"), Some(ValueRule::Range(3, 3)));
+        assert_eq!(ValueRule::parse("*0"), None);
+        assert_eq!(ValueFilter::parse_rules("1, x"), None);
+
+        let Some(only) = ValueFilter::parse_rules("*4, 13") else { panic!("valid rules") };
+        let filter = ValueFilter::default().only(only).except(vec![ValueRule::Value(0)]);
+        assert_eq!(filter.patterns(&Bits::Four, NumberMode::Unsigned), Some(vec![4, 8, 12, 13]));
+        // -8 and -4 in two's complement
+        assert_eq!(filter.patterns(&Bits::Four, NumberMode::Signed), Some(vec![4, 8, 12]));
+
+        let filter = ValueFilter::default().only(vec![ValueRule::Range(1000, 2000)]);
+        assert_eq!(filter.patterns(&Bits::Four, NumberMode::Unsigned), None);
+        assert_eq!(ValueFilter::default().patterns(&Bits::Four, NumberMode::Unsigned), None);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::main_screen_widget::MainScreenWidget;
+use binbreak::Question;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+/// A playable mode. The app drives every mode through this trait, so a new kind of
+/// question (hex, bitwise operations, ...) is a new implementation rather than
+/// another special case in the app.
+pub trait GameMode: MainScreenWidget {
+    /// Deal the next question of this mode
+    fn next_question(&mut self) -> Question;
+    /// Whether `guess` answers the current question
+    fn is_correct(&self, guess: i32) -> bool;
+    /// Draw the current question, the part of the screen that differs between modes
+    fn render_question(&self, area: Rect, buf: &mut Buffer);
+    /// A question clock is running, so the app keeps redrawing at full rate
+    fn is_active(&self) -> bool;
+}
@@ -0,0 +1,376 @@
+//! Two players on one keyboard, taking turns at the same questions: each plays a run of
+//! the mode, then a scoreboard puts their runs side by side.
+
+use crate::binary_numbers::BinaryNumbersGame;
+use crate::keybinds::{self, KeyHint};
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::summary::RunSummary;
+use crate::theme;
+use binbreak::{Bits, NumberMode};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+/// Players taking turns
+const PLAYERS: usize = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Stage {
+    Playing,
+    /// Between turns, until the next player is ready
+    Switching,
+    Scoreboard,
+}
+
+/// A match of turns at the same questions
+pub struct Hotseat {
+    bits: Bits,
+    number_mode: NumberMode,
+    practice: bool,
+    coverage: bool,
+    /// Seed from the command line, kept for every match; other matches draw their own
+    fixed_seed: Option<u64>,
+    /// Deals the same questions to every turn of this match
+    seed: u64,
+    /// The run of the player whose turn it is, or the last one played between turns
+    game: BinaryNumbersGame,
+    stage: Stage,
+    /// Summaries of the turns played, in turn order
+    results: Vec<RunSummary>,
+    exit_intended: bool,
+}
+
+impl Hotseat {
+    /// Width of the labels of the scoreboard rows
+    const LABEL_WIDTH: usize = 14;
+    /// Width of a player's column on the scoreboard
+    const COLUMN_WIDTH: usize = 10;
+
+    pub fn new(bits: Bits, number_mode: NumberMode, seed: Option<u64>) -> Self {
+        let match_seed = seed.unwrap_or_else(rand::random);
+        let game = BinaryNumbersGame::new(bits.clone(), number_mode);
+        let mut hotseat = Self {
+            bits,
+            number_mode,
+            practice: false,
+            coverage: false,
+            fixed_seed: seed,
+            seed: match_seed,
+            game,
+            stage: Stage::Playing,
+            results: Vec::new(),
+            exit_intended: false,
+        };
+        hotseat.start_turn();
+        hotseat
+    }
+
+    /// Every turn is untimed and loses no lives
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self.start_turn();
+        self
+    }
+
+    /// Every turn deals from the whole value space evenly
+    pub fn with_coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self.start_turn();
+        self
+    }
+
+    /// A fresh run from the first question of the match for the next player
+    fn start_turn(&mut self) {
+        self.game = BinaryNumbersGame::new(self.bits.clone(), self.number_mode)
+            .with_practice(self.practice)
+            .with_coverage(self.coverage)
+            .with_seed(self.seed)
+            .with_countdown();
+        self.stage = Stage::Playing;
+    }
+
+    /// Number of the player whose turn it is, or is next, from 1
+    fn player(&self) -> usize {
+        self.results.len() + usize::from(self.stage == Stage::Playing)
+    }
+
+    /// After the game handled something: a run that is over ends the turn, leaving the
+    /// game before any answer leaves the match
+    fn after_game_update(&mut self) {
+        if self.game.is_exit_intended() {
+            self.exit_intended = true;
+        } else if let Some(summary) = self.game.take_summary() {
+            self.results.push(summary);
+            self.stage = if self.results.len() < PLAYERS {
+                Stage::Switching
+            } else {
+                Stage::Scoreboard
+            };
+        }
+    }
+
+    fn handle_between_turns(&mut self, key: KeyEvent) {
+        match key {
+            x if keybinds::is_select(x) => {
+                if self.stage == Stage::Scoreboard {
+                    // A rematch at new questions, unless they are fixed
+                    self.seed = self.fixed_seed.unwrap_or_else(rand::random);
+                    self.results.clear();
+                }
+                self.start_turn();
+            },
+            x if keybinds::is_exit(x) => self.exit_intended = true,
+            _ => {},
+        }
+    }
+
+    /// The players with the highest score, from 1
+    fn leaders(&self) -> Vec<usize> {
+        let best = self.results.iter().map(RunSummary::score).max().unwrap_or(0);
+        (1..=self.results.len())
+            .filter(|&player| self.results[player - 1].score() == best)
+            .collect()
+    }
+
+    fn switching_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let done = self.results.len();
+        let score = self.results.last().map_or(0, RunSummary::score);
+        vec![
+            Line::styled(format!("Player {done} scored {score}"), Style::default().fg(theme.score)),
+            Line::from(""),
+            Line::styled(
+                format!("Player {}, your turn", done + 1),
+                Style::default().fg(theme.accent).bold(),
+            ),
+            Line::styled(
+                "Same questions, hand over the keyboard",
+                Style::default().fg(theme.muted),
+            ),
+            Line::from(""),
+            Line::styled(
+                format!(
+                    "{} to start, {} to leave the match",
+                    keybinds::key_label(keybinds::SELECT),
+                    keybinds::key_label(keybinds::EXIT)
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ]
+    }
+
+    fn scoreboard_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let leaders = self.leaders();
+        let headline = match leaders.as_slice() {
+            [winner] => format!("Player {winner} wins!"),
+            _ => "It's a draw!".to_string(),
+        };
+        let mut lines = vec![
+            Line::styled(headline, Style::default().fg(theme.accent).bold()).centered(),
+            Line::from(""),
+        ];
+        let mut header = format!("{:width$}", "", width = Self::LABEL_WIDTH);
+        for player in 1..=self.results.len() {
+            header +=
+                &format!("{:>width$}", format!("Player {player}"), width = Self::COLUMN_WIDTH);
+        }
+        lines.push(Line::styled(header, Style::default().fg(theme.muted)));
+        let figures: Vec<_> = self.results.iter().map(RunSummary::figures).collect();
+        let labels = figures.first().map(|first| first.each_ref().map(|(label, _)| *label));
+        for (row, label) in labels.into_iter().flatten().enumerate() {
+            let mut spans = vec![Span::styled(
+                format!("{label:<width$}", width = Self::LABEL_WIDTH),
+                Style::default().fg(theme.muted),
+            )];
+            for (index, player_figures) in figures.iter().enumerate() {
+                // The winning score stands out
+                let style = if row == 0 && leaders == [index + 1] {
+                    Style::default().fg(theme.correct).bold()
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let value = &player_figures[row].1;
+                spans.push(Span::styled(
+                    format!("{value:>width$}", width = Self::COLUMN_WIDTH),
+                    style,
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+        lines.push(
+            Line::styled(
+                format!(
+                    "{} for a rematch, {} to change mode",
+                    keybinds::key_label(keybinds::SELECT),
+                    keybinds::key_label(keybinds::EXIT)
+                ),
+                Style::default().fg(theme.muted),
+            )
+            .centered(),
+        );
+        lines
+    }
+}
+
+impl WidgetRef for Hotseat {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let lines = match self.stage {
+            Stage::Playing => {
+                self.game.render_ref(area, buf);
+                // Whose turn it is, over the top edge of the game
+                let label = format!(" Player {} ", self.player());
+                let style = Style::default().fg(theme::current().accent).bold();
+                Line::styled(label, style).centered().render(Rect { height: 1, ..area }, buf);
+                return;
+            },
+            Stage::Switching => self.switching_lines(),
+            Stage::Scoreboard => self.scoreboard_lines(),
+        };
+        let theme = theme::current();
+        let block = Block::bordered()
+            .title(" Hotseat ")
+            .title_alignment(Center)
+            .border_style(Style::default().fg(theme.muted));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        #[allow(clippy::cast_possible_truncation)]
+        let (width, height) =
+            (lines.iter().map(Line::width).max().unwrap_or(0) as u16, lines.len() as u16);
+        let [column] =
+            Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(inner);
+        let [text_area] =
+            Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(column);
+        Paragraph::new(lines).render(text_area, buf);
+    }
+}
+
+/// Until the turns are over, everything goes to the game of the player at the keyboard
+impl MainScreenWidget for Hotseat {
+    fn run(&mut self, dt: f64) {
+        if self.stage == Stage::Playing {
+            self.game.run(dt);
+            self.after_game_update();
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if self.stage == Stage::Playing {
+            self.game.handle_input(input);
+            self.after_game_update();
+        } else {
+            self.handle_between_turns(input);
+        }
+    }
+
+    fn handle_key_release(&mut self, input: KeyEvent) {
+        if self.stage == Stage::Playing {
+            self.game.handle_key_release(input);
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.stage == Stage::Playing {
+            self.game.handle_mouse(mouse);
+            self.after_game_update();
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.stage == Stage::Playing {
+            self.game.handle_paste(text);
+            self.after_game_update();
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{EXIT, SELECT};
+        match self.stage {
+            Stage::Playing => MainScreenWidget::key_hints(&self.game),
+            Stage::Switching => {
+                vec![KeyHint::new(&[SELECT], "start"), KeyHint::new(&[EXIT], "leave")]
+            },
+            Stage::Scoreboard => {
+                vec![KeyHint::new(&[SELECT], "rematch"), KeyHint::new(&[EXIT], "menu")]
+            },
+        }
+    }
+
+    fn min_size(&self) -> Size {
+        MainScreenWidget::min_size(&self.game)
+    }
+
+    fn is_animating(&self) -> bool {
+        self.stage == Stage::Playing && MainScreenWidget::is_animating(&self.game)
+    }
+
+    fn animation_finished(&mut self) {
+        if self.stage == Stage::Playing {
+            self.game.animation_finished();
+            self.after_game_update();
+        }
+    }
+
+    fn config_changed(&mut self) {
+        MainScreenWidget::config_changed(&mut self.game);
+    }
+
+    fn save(&mut self) {
+        if self.stage == Stage::Playing {
+            MainScreenWidget::save(&mut self.game);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings;
+    use crossterm::event::KeyCode;
+
+    /// Answer the first question with its first choice, then quit onto the summary.
+    /// Returns the question as it was drawn.
+    fn play_one_question(hotseat: &mut Hotseat) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 30));
+        hotseat.game.render_ref(buf.area, &mut buf);
+        for code in [KeyCode::Char('1'), KeyCode::Esc, KeyCode::Char('y')] {
+            hotseat.handle_input(KeyEvent::from(code));
+        }
+        buf
+    }
+
+    #[test]
+    fn players_take_turns_at_the_same_questions_then_compare_runs() {
+        let before = settings::get();
+        settings::set(settings::Settings { guest: true, ..before });
+        let mut hotseat = Hotseat::new(Bits::Eight, NumberMode::Unsigned, None).with_practice(true);
+        assert_eq!(hotseat.player(), 1);
+        let first_turn = play_one_question(&mut hotseat);
+        assert_eq!(hotseat.stage, Stage::Switching);
+        assert_eq!(hotseat.switching_lines()[2].to_string(), "Player 2, your turn");
+
+        hotseat.handle_input(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(hotseat.player(), 2);
+        assert_eq!(play_one_question(&mut hotseat), first_turn, "the same questions");
+        assert_eq!(hotseat.stage, Stage::Scoreboard);
+        let lines: Vec<String> =
+            hotseat.scoreboard_lines().iter().map(ToString::to_string).collect();
+        assert_eq!(lines[2], format!("{:14}{:>10}{:>10}", "", "Player 1", "Player 2"));
+        assert!(lines[3].starts_with("Score"));
+
+        hotseat.handle_input(KeyEvent::from(KeyCode::Enter));
+        assert_eq!((hotseat.stage, hotseat.player()), (Stage::Playing, 1), "a rematch");
+        hotseat.handle_input(KeyEvent::from(KeyCode::Esc));
+        assert!(hotseat.is_exit_intended(), "leaving before an answer leaves the match");
+        settings::set(before);
+    }
+}
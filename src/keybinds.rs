@@ -1,25 +1,375 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::settings;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::cell::Cell;
 
-pub const fn is_up(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Up | KeyCode::Char('k'))
+/// Keys bound to one action; the first key is the one shown in hints
+pub type Binding = &'static [KeyCode];
+
+pub const UP: Binding = &[KeyCode::Up, KeyCode::Char('k')];
+pub const DOWN: Binding = &[KeyCode::Down, KeyCode::Char('j')];
+pub const LEFT: Binding = &[KeyCode::Left, KeyCode::Char('h')];
+pub const RIGHT: Binding = &[KeyCode::Right, KeyCode::Char('l')];
+pub const SELECT: Binding = &[KeyCode::Enter];
+pub const EXIT: Binding = &[KeyCode::Esc, KeyCode::Char('q')];
+pub const FIRST: Binding = &[KeyCode::Home];
+pub const LAST: Binding = &[KeyCode::End];
+
+/// Frame timings for diagnosing redraw and CPU use, on every screen
+pub const DEBUG: Binding = &[KeyCode::F(12)];
+/// Recent log lines, on every screen
+pub const LOG: Binding = &[KeyCode::F(11)];
+
+// Dialogs
+pub const CONFIRM: Binding = &[KeyCode::Char('y')];
+pub const CANCEL: Binding = &[KeyCode::Char('n')];
+
+// Game
+pub const SKIP: Binding = &[KeyCode::Char('s')];
+pub const UNDO: Binding = &[KeyCode::Char('u')];
+pub const PAUSE: Binding = &[KeyCode::Char('p')];
+pub const RESTART: Binding = &[KeyCode::Char('r')];
+pub const REVIEW: Binding = &[KeyCode::Char('v')];
+/// Not `h`, which moves left like in vim
+pub const HINT: Binding = &[KeyCode::Char('?')];
+pub const CHEAT_SHEET: Binding = &[KeyCode::Char('c')];
+/// Held down, where the terminal reports key releases
+pub const PEEK: Binding = &[KeyCode::Char(' ')];
+
+// Bit toggles
+pub const FLIP: Binding = &[KeyCode::Char(' ')];
+
+// Start menu
+pub const ANIMATION: Binding = &[KeyCode::Char('a')];
+pub const THEME: Binding = &[KeyCode::Char('t')];
+pub const PRACTICE: Binding = &[KeyCode::Char('p')];
+pub const GUEST: Binding = &[KeyCode::Char('g')];
+pub const MASTERY: Binding = &[KeyCode::Char('m')];
+pub const TUTORIAL: Binding = &[KeyCode::Char('i')];
+pub const FAVORITE: Binding = &[KeyCode::Char('f')];
+pub const SANDBOX: Binding = &[KeyCode::Char('b')];
+pub const STATS: Binding = &[KeyCode::Char('s')];
+pub const COVERAGE: Binding = &[KeyCode::Char('c')];
+/// Two players, as in versus
+pub const HOTSEAT: Binding = &[KeyCode::Char('v')];
+pub const KEY_PROFILE: Binding = &[KeyCode::Char('e')];
+pub const DAILY: Binding = &[KeyCode::Char('d')];
+pub const LEADERBOARD: Binding = &[KeyCode::Char('o')];
+
+/// The characters of a QWERTY keyboard, row by row
+const QWERTY: &str = "qwertyuiop[]asdfghjkl;'zxcvbnm,./1234567890-=";
+
+/// The keyboard layout typed on. Bindings name keys by where they sit on a QWERTY
+/// keyboard, so `hjkl` moves from the same keys on every layout, and hints show the
+/// letters printed on them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum KeyLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Azerty,
+    /// QWERTY, but only the arrow keys move
+    Arrows,
 }
 
-pub const fn is_down(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Down | KeyCode::Char('j'))
+impl KeyLayout {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "qwerty" => Some(Self::Qwerty),
+            "dvorak" => Some(Self::Dvorak),
+            "colemak" => Some(Self::Colemak),
+            "azerty" => Some(Self::Azerty),
+            "arrows" => Some(Self::Arrows),
+            _ => None,
+        }
+    }
+
+    /// The characters of the layout in the places of `QWERTY`'s
+    const fn keys(self) -> &'static str {
+        match self {
+            Self::Qwerty | Self::Arrows => QWERTY,
+            Self::Dvorak => "',.pyfgcrl/=aoeuidhtns-;qjkxbmwvz1234567890[]",
+            Self::Colemak => "qwfpgjluy;[]arstdhneio'zxcvbkm,./1234567890-=",
+            // The digits are shifted, unshifted the number row types these
+            Self::Azerty => "azertyuiop^$qsdfghjklmùwxcvbn,;:!&é\"'(-è_çà)=",
+        }
+    }
+
+    /// The QWERTY key in the place of `typed`
+    fn position(self, typed: char) -> char {
+        let index = self.keys().chars().position(|key| key == typed);
+        index.and_then(|index| QWERTY.chars().nth(index)).unwrap_or(typed)
+    }
+
+    /// The key of the layout in the place of the QWERTY key `key`
+    fn printed(self, key: char) -> char {
+        let index = QWERTY.chars().position(|qwerty| qwerty == key);
+        index.and_then(|index| self.keys().chars().nth(index)).unwrap_or(key)
+    }
 }
 
-pub const fn is_left(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Left | KeyCode::Char('h'))
+/// Keys in the style of an editor, on top of the arrow keys and `hjkl`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum KeyProfile {
+    #[default]
+    Standard,
+    /// `gg` and `G` jump to the first and last entry, `ZZ` quits
+    Vim,
+    /// `C-n`, `C-p`, `C-f` and `C-b` move, `C-a` and `C-e` jump, `C-g` cancels
+    Emacs,
 }
 
-pub const fn is_right(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Right | KeyCode::Char('l'))
+impl KeyProfile {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "standard" | "default" => Some(Self::Standard),
+            "vim" => Some(Self::Vim),
+            "emacs" => Some(Self::Emacs),
+            _ => None,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Vim => "vim",
+            Self::Emacs => "emacs",
+        }
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Standard => Self::Vim,
+            Self::Vim => Self::Emacs,
+            Self::Emacs => Self::Standard,
+        }
+    }
 }
 
-pub const fn is_select(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Enter)
+/// Seconds the first key of a vim sequence waits for the second before it goes through
+/// on its own, like vim's `timeoutlen`
+const SEQUENCE_TIMEOUT_SECS: f64 = 0.5;
+
+thread_local! {
+    /// First key of a vim sequence like `gg`, waiting for the second, and for how long
+    static PENDING: Cell<Option<(KeyEvent, f64)>> = const { Cell::new(None) };
 }
 
-pub const fn is_exit(key: KeyEvent) -> bool {
-    matches!(key.code, KeyCode::Esc | KeyCode::Char('q' | 'Q'))
+/// The keys a key press stands for in the key profile, as the screens know them. Each
+/// press goes through here once; a vim sequence holds its first key back until the
+/// second shows whether they belong together, or until `time_out` lets it go alone.
+pub fn translate(key: KeyEvent) -> Vec<KeyEvent> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match settings::get().key_profile {
+        KeyProfile::Standard => vec![key],
+        KeyProfile::Emacs => {
+            let code = match key.code {
+                KeyCode::Char('n') if ctrl => KeyCode::Down,
+                KeyCode::Char('p') if ctrl => KeyCode::Up,
+                KeyCode::Char('f') if ctrl => KeyCode::Right,
+                KeyCode::Char('b') if ctrl => KeyCode::Left,
+                KeyCode::Char('a') if ctrl => KeyCode::Home,
+                KeyCode::Char('e') if ctrl => KeyCode::End,
+                KeyCode::Char('g') if ctrl => KeyCode::Esc,
+                _ => return vec![key],
+            };
+            vec![KeyEvent::from(code)]
+        },
+        KeyProfile::Vim => {
+            let mut keys = Vec::new();
+            if let Some((first, _)) = PENDING.take() {
+                match (first.code, key.code) {
+                    (KeyCode::Char('g'), KeyCode::Char('g')) => return vec![KeyCode::Home.into()],
+                    (KeyCode::Char('Z'), KeyCode::Char('Z')) => return vec![KeyCode::Esc.into()],
+                    _ => keys.push(first),
+                }
+            }
+            match key.code {
+                KeyCode::Char('g' | 'Z') if !ctrl => PENDING.set(Some((key, 0.0))),
+                KeyCode::Char('G') => keys.push(KeyCode::End.into()),
+                _ => keys.push(key),
+            }
+            keys
+        },
+    }
+}
+
+/// A key held back by `translate` is waiting for the next one
+pub fn is_pending() -> bool {
+    PENDING.get().is_some()
+}
+
+/// Let `dt` seconds pass for a held back key; once no second key came in time, it is
+/// returned to go through on its own, e.g. a lone `g` for guest mode
+pub fn time_out(dt: f64) -> Option<KeyEvent> {
+    let (key, waited) = PENDING.take()?;
+    let waited = waited + dt;
+    if waited < SEQUENCE_TIMEOUT_SECS {
+        PENDING.set(Some((key, waited)));
+        return None;
+    }
+    Some(key)
+}
+
+/// Whether the key triggers the binding. Letters match regardless of case, and by their
+/// place on the keyboard.
+pub fn pressed(key: KeyEvent, binding: Binding) -> bool {
+    let layout = settings::get().key_layout;
+    let code = match key.code {
+        KeyCode::Char(c) => KeyCode::Char(layout.position(c.to_ascii_lowercase())),
+        code => code,
+    };
+    binding.contains(&code)
+}
+
+/// Like `pressed`, with the letters left out on the arrow keys layout
+fn moved(key: KeyEvent, binding: Binding) -> bool {
+    let arrows_only = settings::get().key_layout == KeyLayout::Arrows;
+    !(arrows_only && matches!(key.code, KeyCode::Char(_))) && pressed(key, binding)
+}
+
+pub fn is_up(key: KeyEvent) -> bool {
+    moved(key, UP)
+}
+
+pub fn is_down(key: KeyEvent) -> bool {
+    moved(key, DOWN)
+}
+
+pub fn is_left(key: KeyEvent) -> bool {
+    moved(key, LEFT)
+}
+
+pub fn is_right(key: KeyEvent) -> bool {
+    moved(key, RIGHT)
+}
+
+pub fn is_select(key: KeyEvent) -> bool {
+    pressed(key, SELECT)
+}
+
+pub fn is_exit(key: KeyEvent) -> bool {
+    pressed(key, EXIT)
+}
+
+/// The position of the answer a number key picks, 0 for `1`. Letters would clash with
+/// the game's other keys.
+pub fn choice_index(key: KeyEvent) -> Option<usize> {
+    let KeyCode::Char(typed) = key.code else { return None };
+    match settings::get().key_layout.position(typed) {
+        digit @ '1'..='9' => Some(digit as usize - '1' as usize),
+        _ => None,
+    }
+}
+
+/// Short label for the primary key of a binding, e.g. `↑` or `Enter`
+pub fn key_label(binding: Binding) -> String {
+    match binding.first() {
+        Some(KeyCode::Up) => "↑".to_string(),
+        Some(KeyCode::Down) => "↓".to_string(),
+        Some(KeyCode::Left) => "←".to_string(),
+        Some(KeyCode::Right) => "→".to_string(),
+        Some(KeyCode::Char(' ')) => "Space".to_string(),
+        Some(KeyCode::Char(c)) => settings::get().key_layout.printed(*c).to_string(),
+        Some(code) => code.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Text naming keys like `[p]`, with the letters of the keyboard layout in the brackets
+pub fn relabel(text: &str) -> String {
+    let layout = settings::get().key_layout;
+    let mut chars: Vec<char> = text.chars().collect();
+    for index in 1..chars.len().saturating_sub(1) {
+        if chars[index - 1] == '[' && chars[index + 1] == ']' {
+            chars[index] = layout.printed(chars[index]);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// One entry of the key hint footer: the keys of one or more bindings and what they do
+#[derive(Copy, Clone, Debug)]
+pub struct KeyHint {
+    pub bindings: &'static [Binding],
+    pub action: &'static str,
+}
+
+impl KeyHint {
+    pub const fn new(bindings: &'static [Binding], action: &'static str) -> Self {
+        Self { bindings, action }
+    }
+
+    /// The primary keys of all bindings, e.g. `↑↓`
+    pub fn keys(&self) -> String {
+        self.bindings.iter().map(|binding| key_label(binding)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn letters_match_case_insensitively_and_label_primary_key() {
+        let shift_q = KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+        assert!(is_exit(shift_q));
+        assert!(!is_select(shift_q));
+        assert_eq!(KeyHint::new(&[UP, DOWN], "select").keys(), "↑↓");
+        assert_eq!(key_label(SELECT), "Enter");
+        assert_eq!(key_label(EXIT), "Esc");
+        assert_eq!(choice_index(KeyEvent::from(KeyCode::Char('3'))), Some(2));
+        assert_eq!(choice_index(KeyEvent::from(KeyCode::Char('0'))), None);
+    }
+
+    #[test]
+    fn layouts_match_keys_by_their_place_on_the_keyboard() {
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+        let before = settings::get();
+        settings::set(settings::Settings { key_layout: KeyLayout::Dvorak, ..before });
+        // Where QWERTY has h and p
+        assert!(is_left(key('d')) && !is_left(key('h')));
+        assert!(pressed(key('L'), PAUSE));
+        assert_eq!(key_label(PAUSE), "l");
+        assert_eq!(relabel("[p] practice, [s] stats"), "[l] practice, [o] stats");
+
+        settings::set(settings::Settings { key_layout: KeyLayout::Azerty, ..before });
+        assert!(is_exit(key('a')));
+        assert_eq!(choice_index(key('é')), Some(1));
+        assert_eq!(choice_index(key('2')), Some(1));
+
+        settings::set(settings::Settings { key_layout: KeyLayout::Arrows, ..before });
+        assert!(!is_up(key('k')) && is_up(KeyEvent::from(KeyCode::Up)));
+        assert!(pressed(key('p'), PAUSE));
+        settings::set(before);
+    }
+
+    #[test]
+    fn profiles_turn_editor_keys_into_the_keys_screens_know() {
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+        let codes = |keys: Vec<KeyEvent>| keys.into_iter().map(|k| k.code).collect::<Vec<_>>();
+        let before = settings::get();
+        settings::set(settings::Settings { key_profile: KeyProfile::Vim, ..before });
+        assert_eq!(codes(translate(key('g'))), []);
+        assert_eq!(codes(translate(key('g'))), [KeyCode::Home]);
+        assert_eq!(codes(translate(key('G'))), [KeyCode::End]);
+        assert_eq!(codes(translate(key('Z'))), []);
+        assert_eq!(codes(translate(key('Z'))), [KeyCode::Esc]);
+        // A held back key that starts no sequence comes through before the next one
+        translate(key('g'));
+        assert_eq!(codes(translate(key('j'))), [KeyCode::Char('g'), KeyCode::Char('j')]);
+        // And on its own once the second is too late
+        translate(key('g'));
+        assert_eq!(time_out(0.1), None);
+        assert_eq!(time_out(0.5).map(|k| k.code), Some(KeyCode::Char('g')));
+        assert!(!is_pending());
+
+        settings::set(settings::Settings { key_profile: KeyProfile::Emacs, ..before });
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert_eq!(codes(translate(ctrl('n'))), [KeyCode::Down]);
+        assert_eq!(codes(translate(ctrl('g'))), [KeyCode::Esc]);
+        assert_eq!(codes(translate(key('g'))), [KeyCode::Char('g')]);
+        settings::set(before);
+    }
 }
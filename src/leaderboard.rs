@@ -0,0 +1,340 @@
+//! The daily challenge leaderboard: first runs of the day sent to a web server named in the
+//! config, and the day's top list fetched back. Nothing is sent unless `leaderboard.url`
+//! is set.
+//!
+//! The server takes `POST <url>/<day>` with a form of `name` and `score`, and answers
+//! `GET <url>/<day>` with a line `<score> <name>` per run, best first. The list fetched
+//! last is kept, so the screen still shows it offline.
+
+use crate::config::Config;
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::settings;
+use crate::storage;
+use crate::theme;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::fmt::Write as _;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// Runs shown, and kept of a fetched list
+const TOP: usize = 10;
+/// Longest name sent, longer ones are cut
+const NAME_LENGTH: usize = 16;
+
+/// Where scores go and the name they are sent under, from the `leaderboard.url` and
+/// `leaderboard.name` keys of the config
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+    url: String,
+    name: String,
+}
+
+impl Endpoint {
+    /// None unless a url is set; the name defaults to the login name
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let url = config.get("leaderboard.url")?.trim().trim_end_matches('/').to_string();
+        let name = config
+            .get("leaderboard.name")
+            .map(str::to_string)
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "anonymous".to_string());
+        let name: String = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        let name = name.chars().take(NAME_LENGTH).collect();
+        (!url.is_empty()).then_some(Self { url, name })
+    }
+
+    fn day_url(&self, day: u64) -> String {
+        format!("{}/{day}", self.url)
+    }
+}
+
+/// One run on the list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub score: u32,
+    pub name: String,
+}
+
+/// The top runs of a day
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Board {
+    pub day: u64,
+    pub entries: Vec<Entry>,
+}
+
+impl Board {
+    const FILE: &'static str = "binbreak_leaderboard.txt";
+
+    /// Lines `<score> <name>`, best first as the server sent them; others are skipped
+    fn parse_entries(contents: &str) -> Vec<Entry> {
+        contents
+            .lines()
+            .filter_map(|line| {
+                let (score, name) = line.trim().split_once(' ')?;
+                Some(Entry { score: score.parse().ok()?, name: name.trim().to_string() })
+            })
+            .take(TOP)
+            .collect()
+    }
+
+    /// The list fetched last; `day=<day>` and then the runs
+    pub fn load_cached() -> Option<Self> {
+        let contents = storage::active().read(Self::FILE)?;
+        let (first, entries) = contents.split_once('\n').unwrap_or((&contents, ""));
+        let day = first.trim().strip_prefix("day=")?.parse().ok()?;
+        Some(Self { day, entries: Self::parse_entries(entries) })
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let mut data = format!("day={}\n", self.day);
+        for entry in &self.entries {
+            let _ = writeln!(data, "{} {}", entry.score, entry.name);
+        }
+        storage::active().write(Self::FILE, &data)
+    }
+}
+
+fn agent() -> ureq::Agent {
+    let config =
+        ureq::Agent::config_builder().timeout_global(Some(Duration::from_secs(10))).build();
+    ureq::Agent::new_with_config(config)
+}
+
+/// Send the score of the day's first run in the background; the receiver gets an error
+/// message if that failed
+pub fn submit(endpoint: Endpoint, day: u64, score: u32) -> Receiver<Result<(), String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let score = score.to_string();
+        let form = [("name", endpoint.name.as_str()), ("score", score.as_str())];
+        let sent = agent().post(&endpoint.day_url(day)).send_form(form);
+        let sent = sent.map(drop).map_err(|err| err.to_string());
+        tracing::info!(day, ?sent, "leaderboard: submitted");
+        let _ = sender.send(sent);
+    });
+    receiver
+}
+
+/// Fetch the top list of `day` in the background
+pub fn fetch(endpoint: Endpoint, day: u64) -> Receiver<Result<Board, String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let fetched = agent()
+            .get(&endpoint.day_url(day))
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map(|contents| Board { day, entries: Board::parse_entries(&contents) })
+            .map_err(|err| err.to_string());
+        if let Err(err) = &fetched {
+            tracing::warn!(day, "leaderboard: fetching failed: {err}");
+        }
+        let _ = sender.send(fetched);
+    });
+    receiver
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Status {
+    /// No `leaderboard.url` in the config
+    NotSetUp,
+    Fetching,
+    Fetched,
+    /// Fetching failed, the cached list is shown
+    Offline,
+}
+
+/// Today's top list of the daily challenge
+pub struct LeaderboardScreen {
+    endpoint: Option<Endpoint>,
+    today: u64,
+    /// Fetched, or cached until then
+    board: Option<Board>,
+    status: Status,
+    fetching: Option<Receiver<Result<Board, String>>>,
+    exit_intended: bool,
+}
+
+impl LeaderboardScreen {
+    const WIDTH: u16 = 40;
+
+    pub fn new(today: u64) -> Self {
+        let mut screen = Self {
+            endpoint: settings::leaderboard(),
+            today,
+            board: Board::load_cached(),
+            status: Status::NotSetUp,
+            fetching: None,
+            exit_intended: false,
+        };
+        screen.refresh();
+        screen
+    }
+
+    fn refresh(&mut self) {
+        if let Some(endpoint) = &self.endpoint
+            && self.fetching.is_none()
+        {
+            self.fetching = Some(fetch(endpoint.clone(), self.today));
+            self.status = Status::Fetching;
+        }
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let theme = theme::current();
+        let text = match &self.status {
+            Status::NotSetUp => "Set leaderboard.url in the config to compare".to_string(),
+            Status::Fetching => "Fetching...".to_string(),
+            Status::Fetched => "Up to date".to_string(),
+            Status::Offline => match &self.board {
+                Some(board) if board.day == self.today => "Offline, shown as last fetched".into(),
+                Some(board) => {
+                    format!("Offline, from {} days ago", self.today.saturating_sub(board.day))
+                },
+                None => "Offline, nothing fetched yet".to_string(),
+            },
+        };
+        Line::styled(text, Style::default().fg(theme.muted))
+    }
+
+    fn entry_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let Some(board) = self.board.as_ref().filter(|board| !board.entries.is_empty()) else {
+            return vec![Line::styled("no runs yet", Style::default().fg(theme.muted))];
+        };
+        let own_name = self.endpoint.as_ref().map(|endpoint| endpoint.name.as_str());
+        board
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let color = if Some(entry.name.as_str()) == own_name {
+                    theme.accent
+                } else {
+                    theme.text
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>2}. ", index + 1), Style::default().fg(theme.muted)),
+                    Span::styled(
+                        format!("{:<width$}", entry.name, width = NAME_LENGTH),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(format!(" {:>8}", entry.score), Style::default().fg(theme.score)),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl WidgetRef for LeaderboardScreen {
+    #[allow(clippy::cast_possible_truncation)]
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let mut lines = vec![self.status_line(), Line::from("")];
+        lines.extend(self.entry_lines());
+        let full = Size::new(Self::WIDTH, lines.len() as u16 + 2);
+        let size = Size::new(full.width.min(area.width), full.height.min(area.height));
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        let block =
+            Block::bordered().title(" Daily leaderboard ").title_alignment(Center).fg(theme.muted);
+        let inner = block.inner(frame);
+        block.render(frame, buf);
+        Paragraph::new(lines).render(inner.inner(Margin::new(1, 0)), buf);
+    }
+}
+
+impl MainScreenWidget for LeaderboardScreen {
+    fn run(&mut self, _dt: f64) {
+        let Some(fetched) = self.fetching.as_ref().and_then(|fetching| fetching.try_recv().ok())
+        else {
+            return;
+        };
+        self.fetching = None;
+        match fetched {
+            Ok(board) => {
+                let _ = board.save();
+                self.board = Some(board);
+                self.status = Status::Fetched;
+            },
+            Err(_) => self.status = Status::Offline,
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if keybinds::is_exit(input) || keybinds::is_select(input) {
+            self.exit_intended = true;
+        } else if keybinds::pressed(input, keybinds::RESTART) {
+            self.refresh();
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    /// The status line and a few runs
+    fn min_size(&self) -> Size {
+        Size::new(Self::WIDTH, 8)
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        let mut hints = vec![KeyHint::new(&[keybinds::EXIT], "back")];
+        if self.endpoint.is_some() {
+            hints.push(KeyHint::new(&[keybinds::RESTART], "refresh"));
+        }
+        hints
+    }
+
+    /// Polls for the list while it is fetched
+    fn is_animating(&self) -> bool {
+        self.fetching.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn fetches_the_top_list_of_a_day() {
+        let Ok(listener) = TcpListener::bind("127.0.0.1:0") else { panic!("loopback") };
+        let Ok(address) = listener.local_addr() else { panic!("bound") };
+        let server = thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else { return String::new() };
+            let mut request_line = String::new();
+            let _ = BufReader::new(&stream).read_line(&mut request_line);
+            let body = "3400 ada\nnot a run\n2100 grace hopper\n";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            request_line
+        });
+        let config = Config::parse(&format!("leaderboard.url = http://{address}/daily/\n"));
+        let Some(endpoint) = Endpoint::from_config(&config) else { panic!("a url is set") };
+        let Ok(fetched) = fetch(endpoint, 20_000).recv() else { panic!("an answer") };
+
+        let entries = vec![
+            Entry { score: 3400, name: "ada".to_string() },
+            Entry { score: 2100, name: "grace hopper".to_string() },
+        ];
+        assert_eq!(fetched, Ok(Board { day: 20_000, entries }));
+        assert!(server.join().unwrap_or_default().starts_with("GET /daily/20000 "));
+        assert_eq!(Endpoint::from_config(&Config::default()), None);
+    }
+}
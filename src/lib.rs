@@ -0,0 +1,27 @@
+//! The rules of binbreak without a terminal: dealing questions and scoring runs.
+//! The `binbreak` binary draws these with ratatui; other frontends (a web page,
+//! a chat bot) can use them the same way.
+//!
+//! ```
+//! use binbreak::{Bits, NumberMode, Question, Run};
+//!
+//! let mut rng = rand::rng();
+//! let mut run = Run::new(3);
+//! let question = Question::generate(Bits::Eight, NumberMode::Unsigned, &mut rng);
+//! if question.is_correct(question.choices[0]) {
+//!     run.correct();
+//! } else {
+//!     run.incorrect(true);
+//! }
+//! assert_eq!(run.rounds, 1);
+//! ```
+
+pub mod bits;
+pub mod filter;
+pub mod question;
+pub mod scoring;
+
+pub use bits::{Bits, NumberMode};
+pub use filter::ValueFilter;
+pub use question::{Dealer, Distractors, Question};
+pub use scoring::Run;
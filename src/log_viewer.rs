@@ -0,0 +1,58 @@
+use crate::keybinds;
+use crate::logging;
+use crate::theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+
+/// Lines moved by Page Up and Page Down
+const PAGE: usize = 10;
+
+/// The latest log lines in a panel over the lower half of the screen
+#[derive(Default)]
+pub struct LogViewer {
+    /// Lines scrolled back from the newest one
+    offset: usize,
+}
+
+impl LogViewer {
+    /// Scroll through the lines; returns false when the viewer is closed
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let oldest = logging::recent().len().saturating_sub(1);
+        match key.code {
+            KeyCode::PageUp => self.offset = (self.offset + PAGE).min(oldest),
+            KeyCode::PageDown => self.offset = self.offset.saturating_sub(PAGE),
+            KeyCode::Home => self.offset = oldest,
+            KeyCode::End => self.offset = 0,
+            _ if keybinds::is_up(key) => self.offset = (self.offset + 1).min(oldest),
+            _ if keybinds::is_down(key) => self.offset = self.offset.saturating_sub(1),
+            _ if keybinds::is_exit(key) || keybinds::pressed(key, keybinds::LOG) => return false,
+            _ => {},
+        }
+        true
+    }
+}
+
+impl Widget for &LogViewer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let [_, panel] = Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        let block = Block::bordered()
+            .title(" log ")
+            .title_bottom(" ↑↓ scroll  Esc close ")
+            .border_style(Style::default().fg(theme.accent));
+        let visible = usize::from(block.inner(panel).height);
+        let lines = logging::recent();
+        let end = lines.len().saturating_sub(self.offset);
+        let lines: Vec<Line> = lines[end.saturating_sub(visible)..end]
+            .iter()
+            .map(|line| Line::styled(line.as_str(), Style::default().fg(theme.text)))
+            .collect();
+
+        Clear.render(panel, buf);
+        Paragraph::new(lines).block(block).render(panel, buf);
+    }
+}
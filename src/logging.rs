@@ -0,0 +1,94 @@
+use crate::config::Config;
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use tracing::level_filters::LevelFilter;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+/// Lines kept in memory for the log viewer
+const RECENT_LINES: usize = 500;
+/// Daily log files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Log files go to `logs` in the config directory
+pub fn dir() -> Option<PathBuf> {
+    Config::dir().map(|dir| dir.join("logs"))
+}
+
+/// Log to a file that rotates daily and keep the latest lines for the log viewer.
+/// `BINBREAK_LOG` sets the level: error, warn, info (the default), debug or trace.
+pub fn init() {
+    let level = std::env::var("BINBREAK_LOG")
+        .ok()
+        .and_then(|level| level.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::INFO);
+    // Without a writable log directory the viewer still shows what happened
+    let file = dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("binbreak")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .ok()
+    });
+    let _ = tracing_subscriber::registry()
+        .with(level)
+        .with(file.map(|file| fmt::layer().with_writer(file).with_ansi(false)))
+        .with(
+            fmt::layer()
+                .with_writer(|| RecentLines)
+                .with_ansi(false)
+                .without_time()
+                .with_target(false),
+        )
+        .try_init();
+}
+
+/// The latest log lines, oldest first
+pub fn recent() -> Vec<String> {
+    RECENT.lock().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+}
+
+/// Appends formatted log lines to the in-memory tail
+struct RecentLines;
+
+impl io::Write for RecentLines {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut recent = RECENT.lock().unwrap_or_else(PoisonError::into_inner);
+        for line in String::from_utf8_lossy(buf).lines().filter(|line| !line.trim().is_empty()) {
+            if recent.len() == RECENT_LINES {
+                recent.pop_front();
+            }
+            recent.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn keeps_only_the_latest_lines() {
+        for i in 0..RECENT_LINES + 2 {
+            // The formatter hands over each event in one write
+            let _ = RecentLines.write_all(format!(" INFO answer {i}\n\n").as_bytes());
+        }
+        let recent = recent();
+        assert_eq!(recent.len(), RECENT_LINES);
+        assert_eq!(recent[0], " INFO answer 2");
+        assert_eq!(recent.last().map(String::as_str), Some(" INFO answer 501"));
+    }
+}
@@ -1,13 +1,121 @@
+mod about;
+mod anim_dsl;
+mod animation;
 mod app;
+mod art;
+mod assets;
+mod audio;
 mod binary_numbers;
+mod cast;
+mod cheat_sheet;
+mod cli;
+mod color;
+mod config;
+mod daily;
+mod effects;
+mod events;
+mod feedback;
+mod game_mode;
+mod hotseat;
 mod keybinds;
+mod leaderboard;
+mod log_viewer;
+mod logging;
 mod main_screen_widget;
+mod mastery;
+mod menu_history;
+mod message;
+mod playlist;
+mod responsive;
+mod review;
+mod run_history;
+mod sandbox;
+mod screen;
+mod settings;
+mod stats;
+mod storage;
+mod summary;
+mod terminal_bg;
+mod theme;
+mod timing;
+mod toast;
+mod tutorial;
 mod utils;
+mod versus;
+mod worksheet;
+
+use clap::Parser;
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
+};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Size;
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let cli = cli::Cli::parse();
+    if let Some(command) = &cli.command {
+        command.run(&mut std::io::stdout())?;
+        return Ok(());
+    }
+    logging::init();
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting");
+    let background = terminal_bg::detect().unwrap_or_default();
+    let config = cli.apply_to(config::Config::load());
+    settings::set(settings::Settings { background, ..settings::Settings::resolve(&cli, &config) });
+    settings::set_value_filter(settings::resolve_value_filter(&config));
+    settings::set_leaderboard(leaderboard::Endpoint::from_config(&config));
+    // Opened before the terminal is taken over, so a bad path is reported plainly
+    let recorder = cli
+        .record
+        .as_deref()
+        .map(|path| {
+            let (width, height) = crossterm::terminal::size()?;
+            cast::Recorder::create(std::io::stdout(), path, Size::new(width, height))
+        })
+        .transpose()?;
     let mut terminal = ratatui::init();
-    let result = app::run_app(&mut terminal);
+    // Mouse support is a nicety, the game is fully playable without it. Focus reports
+    // let animations rest while the terminal is in the background, and a pasted answer
+    // arrives as one event instead of a key press per character.
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        EnableMouseCapture,
+        EnableFocusChange,
+        EnableBracketedPaste
+    );
+    // Terminals with the kitty keyboard protocol report key releases, so keys can be held
+    let key_releases = crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if key_releases {
+        let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+            | KeyboardEnhancementFlags::REPORT_EVENT_TYPES;
+        let _ = crossterm::execute!(std::io::stdout(), PushKeyboardEnhancementFlags(flags));
+        settings::set(settings::Settings { key_releases, ..settings::get() });
+    }
+    tracing::info!(key_releases, "keyboard");
+    // Read only from here on, the keyboard query above answers on the same input
+    let mut events = events::Events::start();
+    let result = match recorder {
+        Some(recorder) => Terminal::new(CrosstermBackend::new(recorder))
+            .map_err(Into::into)
+            .and_then(|mut recorded| app::run_app(&mut recorded, &mut events, &cli)),
+        None => app::run_app(&mut terminal, &mut events, &cli),
+    };
+    if key_releases {
+        let _ = crossterm::execute!(std::io::stdout(), PopKeyboardEnhancementFlags);
+    }
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        DisableFocusChange
+    );
     ratatui::restore();
+    if let Err(err) = &result {
+        tracing::error!("{err}");
+    }
     result
 }
@@ -1,6 +1,7 @@
-use crossterm::event::KeyEvent;
+use crate::keybinds::KeyHint;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
+use ratatui::layout::{Rect, Size};
 
 pub trait WidgetRef {
     fn render_ref(&self, area: Rect, buf: &mut Buffer);
@@ -9,5 +10,25 @@ pub trait WidgetRef {
 pub trait MainScreenWidget: WidgetRef {
     fn run(&mut self, dt: f64) -> ();
     fn handle_input(&mut self, input: KeyEvent) -> ();
+    /// A key was let go, on terminals that report it
+    fn handle_key_release(&mut self, _input: KeyEvent) {}
+    /// Mouse support is optional, screens ignore the mouse unless they opt in
+    fn handle_mouse(&mut self, _mouse: MouseEvent) {}
+    /// Text was pasted, screens without a use for it ignore it
+    fn handle_paste(&mut self, _text: &str) {}
     fn is_exit_intended(&self) -> bool;
+    /// Actions available right now, shown in the footer
+    fn key_hints(&self) -> Vec<KeyHint>;
+    /// Smallest area the screen can be drawn in without clipping
+    fn min_size(&self) -> Size;
+    /// Something moves, so the app keeps redrawing instead of waiting for input
+    fn is_animating(&self) -> bool {
+        false
+    }
+    /// Something stopped moving, e.g. an animation that plays once came to its end
+    fn animation_finished(&mut self) {}
+    /// The config file or the art changed: rebuild what was made from it
+    fn config_changed(&mut self) {}
+    /// The app is about to close: keep what would be lost otherwise
+    fn save(&mut self) {}
 }
@@ -0,0 +1,326 @@
+use crate::binary_numbers::BinaryNumbersGame;
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::responsive::LayoutMode;
+use crate::storage;
+use crate::theme;
+use crate::utils::{progress_ring, shade_glyph};
+use binbreak::{Bits, NumberMode};
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Correct answers at least this fast count towards mastering a value
+pub const MASTERY_SPEED_SECS: f64 = 5.0;
+
+/// Per-mode record of which values have been answered correctly at speed.
+/// Modes are keyed like high scores (e.g. `8u`), values by their raw bit pattern.
+pub struct Mastery {
+    coverage: HashMap<String, Coverage>,
+}
+
+/// A bitset over the value space of one mode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coverage {
+    words: Vec<u64>,
+    size: usize,
+}
+
+impl Coverage {
+    pub fn new(size: usize) -> Self {
+        Self { words: vec![0; size.div_ceil(64)], size }
+    }
+
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.size && self.words[value / 64] & (1 << (value % 64)) != 0
+    }
+
+    fn insert(&mut self, value: usize) -> bool {
+        if value >= self.size || self.contains(value) {
+            return false;
+        }
+        self.words[value / 64] |= 1 << (value % 64);
+        true
+    }
+
+    fn remove(&mut self, value: usize) {
+        if value < self.size {
+            self.words[value / 64] &= !(1 << (value % 64));
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Fraction of the value space covered, from 0.0 to 1.0
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> f64 {
+        if self.size == 0 {
+            0.0
+        } else {
+            self.count() as f64 / self.size as f64
+        }
+    }
+
+    /// Fraction covered within `values`, used to aggregate large value spaces
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio_in(&self, values: std::ops::Range<usize>) -> f64 {
+        let total = values.len();
+        let covered = values.filter(|&v| self.contains(v)).count();
+        if total == 0 { 0.0 } else { covered as f64 / total as f64 }
+    }
+
+    fn to_hex(&self) -> String {
+        self.words.iter().fold(String::new(), |mut out, word| {
+            let _ = write!(out, "{word:016x}");
+            out
+        })
+    }
+
+    fn from_hex(hex: &str, size: usize) -> Option<Self> {
+        let mut coverage = Self::new(size);
+        if hex.len() != coverage.words.len() * 16 {
+            return None;
+        }
+        for (word, chunk) in coverage.words.iter_mut().zip(hex.as_bytes().chunks(16)) {
+            *word = u64::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        }
+        Some(coverage)
+    }
+}
+
+impl Mastery {
+    pub const FILE: &'static str = "binbreak_mastery.txt";
+
+    pub fn empty() -> Self {
+        Self { coverage: HashMap::new() }
+    }
+
+    /// Load saved coverage; each line is `<mode key>=<value space size>:<hex bitset>`
+    pub fn load() -> Self {
+        let mut mastery = Self::empty();
+        if let Some(contents) = storage::active().read(Self::FILE) {
+            for line in contents.lines() {
+                if let Some((key, rest)) = line.split_once('=')
+                    && let Some((size, hex)) = rest.split_once(':')
+                    && let Ok(size) = size.trim().parse::<usize>()
+                    && let Some(coverage) = Coverage::from_hex(hex.trim(), size)
+                {
+                    mastery.coverage.insert(key.trim().to_string(), coverage);
+                }
+            }
+        }
+        mastery
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut keys: Vec<&String> = self.coverage.keys().collect();
+        keys.sort();
+        let mut data = String::new();
+        for key in keys {
+            let coverage = &self.coverage[key];
+            let _ = writeln!(data, "{key}={}:{}", coverage.size(), coverage.to_hex());
+        }
+        storage::active().write(Self::FILE, &data)
+    }
+
+    /// Coverage for a mode, empty if it was never played
+    pub fn coverage(&self, key: &str, size: usize) -> Coverage {
+        self.coverage
+            .get(key)
+            .filter(|c| c.size() == size)
+            .cloned()
+            .unwrap_or_else(|| Coverage::new(size))
+    }
+
+    /// Record a value as mastered, returns true if it wasn't before
+    pub fn record(&mut self, key: &str, size: usize, value: usize) -> bool {
+        let coverage = self.coverage.entry(key.to_string()).or_insert_with(|| Coverage::new(size));
+        if coverage.size() != size {
+            *coverage = Coverage::new(size);
+        }
+        coverage.insert(value)
+    }
+
+    /// Take back a value `record` added, e.g. for an undone answer
+    pub fn forget(&mut self, key: &str, value: usize) {
+        if let Some(coverage) = self.coverage.get_mut(key) {
+            coverage.remove(value);
+        }
+    }
+}
+
+/// Coverage map of a single mode: one cell per value, or per bucket of values
+/// for modes too large to show individually
+pub struct MasteryScreen {
+    bits: Bits,
+    number_mode: NumberMode,
+    coverage: Coverage,
+    exit_intended: bool,
+}
+
+impl MasteryScreen {
+    const COLUMNS: usize = 16;
+    const MAX_ROWS: usize = 16;
+    const WIDTH: u16 = 60;
+
+    pub fn new(bits: Bits, number_mode: NumberMode) -> Self {
+        let key = BinaryNumbersGame::compute_high_score_key(&bits, number_mode);
+        let coverage = Mastery::load().coverage(&key, bits.value_space());
+        Self { bits, number_mode, coverage, exit_intended: false }
+    }
+
+    /// Values represented by each grid cell
+    fn bucket_size(&self) -> usize {
+        (self.coverage.size() / (Self::COLUMNS * Self::MAX_ROWS)).max(1)
+    }
+
+    /// Summary, coverage grid and legend
+    fn lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let mode_color = theme.mode_color(&self.bits);
+        let bucket = self.bucket_size();
+        let cells = self.coverage.size() / bucket;
+        let rows = cells.div_ceil(Self::COLUMNS);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "{} {}  {} {:.0}% mastered",
+                    self.bits.label(),
+                    self.number_mode.label(),
+                    progress_ring(self.coverage.ratio()),
+                    self.coverage.ratio() * 100.0
+                ),
+                Style::default().fg(mode_color).bold(),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "{} of {} values answered correctly within {MASTERY_SPEED_SECS:.0}s",
+                    self.coverage.count(),
+                    self.coverage.size()
+                ),
+                Style::default().fg(theme.muted),
+            )),
+            Line::from(""),
+        ];
+
+        for row in 0..rows {
+            let first_value = row * Self::COLUMNS * bucket;
+            let mut spans =
+                vec![Span::styled(format!("{first_value:>5} "), Style::default().fg(theme.muted))];
+            for column in 0..Self::COLUMNS.min(cells - row * Self::COLUMNS) {
+                let start = first_value + column * bucket;
+                let ratio = self.coverage.ratio_in(start..start + bucket);
+                let color = if ratio > 0.0 { mode_color } else { theme.muted };
+                spans.push(Span::styled(format!(" {}", shade_glyph(ratio)), color));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        let legend = if bucket > 1 {
+            format!("each cell covers {bucket} values, shaded by coverage")
+        } else {
+            "one cell per value, read left to right".to_string()
+        };
+        lines.push(Line::from(Span::styled(legend, Style::default().fg(theme.muted))));
+        lines
+    }
+}
+
+impl WidgetRef for MasteryScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = self.lines();
+        #[allow(clippy::cast_possible_truncation)]
+        let full = Size::new(Self::WIDTH, lines.len() as u16 + 2);
+        let size = if LayoutMode::for_area(area, full).is_compact() {
+            lines.retain(|line| line.width() > 0);
+            self.min_size()
+        } else {
+            full
+        };
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        Paragraph::new(lines)
+            .alignment(Center)
+            .block(
+                Block::bordered()
+                    .title(" Mastery ")
+                    .title_alignment(Center)
+                    .fg(theme::current().muted),
+            )
+            .render(frame, buf);
+    }
+}
+
+impl MainScreenWidget for MasteryScreen {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if keybinds::is_exit(input) || keybinds::is_select(input) {
+            self.exit_intended = true;
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    /// Without the blank spacer lines, just wide enough for the text
+    #[allow(clippy::cast_possible_truncation)]
+    fn min_size(&self) -> Size {
+        let lines: Vec<Line> = self.lines().into_iter().filter(|line| line.width() > 0).collect();
+        let widest = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        Size::new(widest + 2, lines.len() as u16 + 2)
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(&[keybinds::EXIT], "back")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage_counts_and_round_trips_through_hex() {
+        let mut coverage = Coverage::new(256);
+        assert!(coverage.insert(0));
+        assert!(coverage.insert(255));
+        assert!(!coverage.insert(255));
+        assert!(!coverage.insert(256));
+        assert_eq!(coverage.count(), 2);
+        assert!((coverage.ratio() - 2.0 / 256.0).abs() < f64::EPSILON);
+        assert!((coverage.ratio_in(0..2) - 0.5).abs() < f64::EPSILON);
+
+        let hex = coverage.to_hex();
+        assert_eq!(Coverage::from_hex(&hex, 256), Some(coverage));
+        assert_eq!(Coverage::from_hex("ff", 256), None);
+    }
+
+    #[test]
+    fn record_resets_on_size_mismatch() {
+        let mut mastery = Mastery::empty();
+        assert!(mastery.record("4u", 16, 3));
+        assert_eq!(mastery.coverage("4u", 16).count(), 1);
+        assert_eq!(mastery.coverage("4u", 256).count(), 0);
+        assert!(mastery.record("4u", 256, 3));
+        assert_eq!(mastery.coverage("4u", 256).count(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::storage;
+use binbreak::Bits;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+/// How often each mode was played, the most recent one and the favorites,
+/// used to mark and order the start menu. Modes are keyed by `Bits::high_score_key`,
+/// so signed and unsigned count as the same mode.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct MenuHistory {
+    plays: HashMap<u32, u32>,
+    last_played: Option<u32>,
+    favorites: HashSet<u32>,
+}
+
+impl MenuHistory {
+    pub const FILE: &'static str = "binbreak_menu.txt";
+
+    /// Load saved history; lines are `plays.<mode key>=<count>`, `last=<mode key>`
+    /// and `favorite=<mode key>`
+    pub fn load() -> Self {
+        storage::active()
+            .read(Self::FILE)
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut history = Self::default();
+        for line in contents.lines() {
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<u32>() else {
+                continue;
+            };
+            match name.trim() {
+                "last" => history.last_played = Some(value),
+                "favorite" => {
+                    history.favorites.insert(value);
+                },
+                name => {
+                    if let Some(key) = name.strip_prefix("plays.").and_then(|k| k.parse().ok()) {
+                        history.plays.insert(key, value);
+                    }
+                },
+            }
+        }
+        history
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::active().write(Self::FILE, &self.serialize())
+    }
+
+    fn serialize(&self) -> String {
+        let mut data = String::new();
+        let mut plays: Vec<_> = self.plays.iter().collect();
+        plays.sort();
+        for (key, count) in plays {
+            let _ = writeln!(data, "plays.{key}={count}");
+        }
+        if let Some(key) = self.last_played {
+            let _ = writeln!(data, "last={key}");
+        }
+        let mut favorites: Vec<_> = self.favorites.iter().collect();
+        favorites.sort();
+        for key in favorites {
+            let _ = writeln!(data, "favorite={key}");
+        }
+        data
+    }
+
+    pub fn record_play(&mut self, bits: &Bits) {
+        let key = bits.high_score_key();
+        *self.plays.entry(key).or_default() += 1;
+        self.last_played = Some(key);
+    }
+
+    pub fn toggle_favorite(&mut self, bits: &Bits) {
+        let key = bits.high_score_key();
+        if !self.favorites.remove(&key) {
+            self.favorites.insert(key);
+        }
+    }
+
+    pub fn is_favorite(&self, bits: &Bits) -> bool {
+        self.favorites.contains(&bits.high_score_key())
+    }
+
+    pub fn is_last_played(&self, bits: &Bits) -> bool {
+        self.last_played == Some(bits.high_score_key())
+    }
+
+    /// Favorites first, the most played of them on top; everything else keeps its order
+    pub fn sort_key(&self, bits: &Bits) -> (bool, std::cmp::Reverse<u32>) {
+        let key = bits.high_score_key();
+        if self.favorites.contains(&key) {
+            (false, std::cmp::Reverse(self.plays.get(&key).copied().unwrap_or(0)))
+        } else {
+            (true, std::cmp::Reverse(0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_favorites_by_plays_and_round_trips() {
+        let mut history = MenuHistory::default();
+        history.record_play(&Bits::Eight);
+        history.record_play(&Bits::Twelve);
+        history.record_play(&Bits::Twelve);
+        history.toggle_favorite(&Bits::Eight);
+        history.toggle_favorite(&Bits::Twelve);
+        history.toggle_favorite(&Bits::Sixteen);
+        history.toggle_favorite(&Bits::Sixteen);
+
+        let mut modes = [Bits::Four, Bits::Eight, Bits::Twelve, Bits::Sixteen];
+        modes.sort_by_key(|bits| history.sort_key(bits));
+        let order: Vec<u32> = modes.iter().map(Bits::high_score_key).collect();
+        assert_eq!(order, [12, 8, 4, 16]);
+        assert!(history.is_last_played(&Bits::Twelve));
+
+        assert_eq!(MenuHistory::parse(&history.serialize()), history);
+    }
+}
@@ -0,0 +1,43 @@
+use crossterm::event::{Event, KeyEvent, KeyEventKind, MouseEvent};
+use ratatui::layout::Size;
+
+/// Everything that changes the state of a screen. Screens update from messages and
+/// draw in a separate view step, so a state change can be tested by sending the
+/// messages that cause it, without a terminal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// A key was pressed, or held down long enough to repeat
+    Key(KeyEvent),
+    /// A key was let go. Only terminals with the kitty keyboard protocol report these, see
+    /// `Settings::key_releases`.
+    KeyRelease(KeyEvent),
+    Mouse(MouseEvent),
+    /// Text pasted into the terminal, arriving at once rather than as key presses
+    Paste(String),
+    /// Time moved on by this many seconds
+    Tick(f64),
+    /// The area screens are drawn in changed size
+    Resize(Size),
+    /// The screen was animating and came to rest
+    AnimationFinished,
+    /// The terminal window gained (true) or lost focus
+    Focus(bool),
+    /// The app is asked to close, e.g. by SIGTERM or because its terminal was closed
+    Terminate,
+}
+
+impl Message {
+    /// The message for a terminal event. Repeats count as presses, like in terminals that
+    /// don't tell them apart, and a resize reports the whole terminal.
+    pub fn from_event(event: Event) -> Self {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Release => Self::KeyRelease(key),
+            Event::Key(key) => Self::Key(key),
+            Event::Mouse(mouse) => Self::Mouse(mouse),
+            Event::Paste(text) => Self::Paste(text),
+            Event::Resize(width, height) => Self::Resize(Size::new(width, height)),
+            Event::FocusGained => Self::Focus(true),
+            Event::FocusLost => Self::Focus(false),
+        }
+    }
+}
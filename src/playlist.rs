@@ -0,0 +1,116 @@
+//! Several modes played back to back as one session, e.g. ten 8 bit questions followed
+//! by five signed 4 bit ones.
+
+use crate::cli;
+use binbreak::{Bits, NumberMode};
+
+/// A number of questions in one mode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section {
+    pub count: usize,
+    pub bits: Bits,
+    pub number_mode: NumberMode,
+}
+
+/// The sections of a session in the order they are played
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Playlist {
+    sections: Vec<Section>,
+}
+
+impl Playlist {
+    /// Sections are separated by commas or new lines, each a count and a mode named like
+    /// `--bits` names it, optionally followed by `signed` or `unsigned`:
+    /// `10x 8 bit, 5x 4x16 signed`. Lines starting with `#` are comments.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut sections = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            for section in line.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                let section = Self::parse_section(section)
+                    .map_err(|err| format!("line {}: `{section}`: {err}", number + 1))?;
+                sections.push(section);
+            }
+        }
+        if sections.is_empty() {
+            return Err("the playlist has no sections".to_string());
+        }
+        Ok(Self { sections })
+    }
+
+    fn parse_section(section: &str) -> Result<Section, String> {
+        let digits = section.find(|c: char| !c.is_ascii_digit()).unwrap_or(section.len());
+        let count: usize = section[..digits]
+            .parse()
+            .ok()
+            .filter(|&count| count > 0)
+            .ok_or("expected a count of questions first, e.g. `10x 8`")?;
+        let mode = section[digits..].trim_start();
+        let mode = mode
+            .strip_prefix(['x', 'X', '×'])
+            .ok_or("expected an x after the count, e.g. `10x 8`")?
+            .trim()
+            .to_lowercase();
+        let (mode, number_mode) = match mode.rsplit_once(' ') {
+            Some((mode, "signed")) => (mode, NumberMode::Signed),
+            Some((mode, "unsigned")) => (mode, NumberMode::Unsigned),
+            _ => (mode.as_str(), NumberMode::Unsigned),
+        };
+        let name = mode.replace("-bit", "").replace("bit", "").replace(' ', "");
+        let bits = cli::parse_bits(&name)?;
+        Ok(Section { count, bits, number_mode })
+    }
+
+    /// `count` questions of one mode
+    pub fn repeat(count: usize, bits: Bits, number_mode: NumberMode) -> Self {
+        Self { sections: vec![Section { count, bits, number_mode }] }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+        Self::parse(&contents)
+    }
+
+    /// Questions in the whole session
+    pub fn total(&self) -> usize {
+        self.sections.iter().map(|section| section.count).sum()
+    }
+
+    /// The section the question at `index` of the session belongs to, None past its end
+    pub fn section_at(&self, index: usize) -> Option<&Section> {
+        let mut start = 0;
+        self.sections.iter().find(|section| {
+            start += section.count;
+            index < start
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playlists_parse_sections_and_find_the_one_of_each_question() {
+        let Ok(playlist) = Playlist::parse("# warm up\n2x 4 bit, 3× 8-bit signed\n1x4x16\n")
+        else {
+            panic!("valid playlist");
+        };
+        assert_eq!(playlist.total(), 6);
+        let section = |index| playlist.section_at(index).map(|s| (s.bits.clone(), s.number_mode));
+        assert_eq!(section(1), Some((Bits::Four, NumberMode::Unsigned)));
+        assert_eq!(section(2), Some((Bits::Eight, NumberMode::Signed)));
+        assert_eq!(section(5), Some((Bits::FourShift4, NumberMode::Unsigned)));
+        assert_eq!(section(6), None);
+
+        assert_eq!(
+            Playlist::parse("10x 8\n5x hex"),
+            Err("line 2: `5x hex`: expected 4, 4x16, 4x256, 4x4096, 8, 12 or 16".to_string())
+        );
+        assert!(Playlist::parse("8 bit").is_err());
+        assert!(Playlist::parse("# nothing").is_err());
+    }
+}
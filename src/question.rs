@@ -0,0 +1,710 @@
+//! Dealing questions: a bit pattern, the numbers to choose from and the right one.
+
+use crate::bits::{Bits, NumberMode};
+use crate::filter::ValueFilter;
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+use std::collections::VecDeque;
+
+/// One question, without any answer or timing state
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Question {
+    pub bits: Bits,
+    /// Raw bit pattern (unscaled) for display as binary string.
+    /// This is u32 (not i32) because it stores the BIT PATTERN, not the numeric value.
+    /// In signed mode, negative numbers use two's complement representation:
+    /// - For -1 in 4-bit: pattern = 15 (0b1111), displayed as "1111"
+    /// - For -8 in 4-bit: pattern = 8 (0b1000), displayed as "1000"
+    ///
+    /// The same bit pattern has different meanings in signed vs unsigned mode.
+    pub pattern: u32,
+    /// The numbers offered as answers, in the order they are shown
+    pub choices: Vec<i32>,
+    pub answer: i32,
+}
+
+impl Question {
+    pub fn generate(bits: Bits, number_mode: NumberMode, rng: &mut impl Rng) -> Self {
+        let count = bits.suggestion_count();
+        Self::generate_with_choices(bits, number_mode, count, rng)
+    }
+
+    /// A random question with `count` numbers to choose from instead of the mode's usual
+    /// number, at most every value of the mode
+    pub fn generate_with_choices(
+        bits: Bits,
+        number_mode: NumberMode,
+        count: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let count = count.clamp(1, bits.value_space());
+        let mut choices = Vec::new();
+        let scale = bits.scale_factor();
+        let num_bits = bits.to_int();
+
+        match number_mode {
+            NumberMode::Unsigned => {
+                while choices.len() < count {
+                    let raw = rng.random_range(0..u32::pow(2, num_bits));
+                    let num = (raw * scale) as i32;
+                    if !choices.contains(&num) {
+                        choices.push(num);
+                    }
+                }
+            },
+            NumberMode::Signed => {
+                // For signed mode, use two's complement representation
+                // Range is from -(2^(n-1)) to 2^(n-1)-1
+                while choices.len() < count {
+                    let raw = rng.random_range(0..u32::pow(2, num_bits));
+                    // Convert raw bits to signed value using two's complement
+                    let signed_val = if raw >= (1 << (num_bits - 1)) {
+                        // Negative number: raw - 2^n
+                        (raw as i32) - (1 << num_bits)
+                    } else {
+                        // Positive number
+                        raw as i32
+                    };
+                    let num = signed_val * (scale as i32);
+                    if !choices.contains(&num) {
+                        choices.push(num);
+                    }
+                }
+            },
+        }
+
+        // Pick a random choice as the answer
+        let answer_index = rng.random_range(0..choices.len());
+        let answer = choices[answer_index];
+
+        // Shuffle choices so the answer is in a random position
+        choices.shuffle(rng);
+
+        // Calculate the pattern based on mode
+        let pattern = match number_mode {
+            NumberMode::Unsigned => answer.unsigned_abs() / scale,
+            NumberMode::Signed => {
+                // For signed mode, we need to preserve the two's complement representation
+                // Example: -1 in 4-bit two's complement is 0b1111
+                // We cast i32 to u32 (preserving bit pattern), then mask to n-bits
+                // Result: -1 becomes 15u32 (0b1111), which displays as "1111"
+                let unscaled_signed = answer / (scale as i32);
+
+                // Convert to unsigned bits using two's complement masking
+                // Casting i32 to u32 reinterprets the bits (not a numeric conversion)
+                // For n-bit number, mask is (2^n - 1)
+                let mask = (1u32 << num_bits) - 1;
+                (unscaled_signed as u32) & mask
+            },
+        };
+
+        Self { bits, pattern, choices, answer }
+    }
+
+    /// A question about a given bit pattern, with random other numbers to choose from
+    pub fn with_pattern(
+        bits: Bits,
+        number_mode: NumberMode,
+        pattern: u32,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let count = bits.suggestion_count();
+        Self::with_pattern_and_choices(bits, number_mode, pattern, count, rng)
+    }
+
+    /// A question about a given bit pattern with `count` numbers to choose from, at most
+    /// every value of the mode
+    pub fn with_pattern_and_choices(
+        bits: Bits,
+        number_mode: NumberMode,
+        pattern: u32,
+        count: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let count = count.clamp(1, bits.value_space());
+        let answer = value_of(&bits, number_mode, pattern);
+        let mut choices = vec![answer];
+        while choices.len() < count {
+            let raw = rng.random_range(0..u32::pow(2, bits.to_int()));
+            let num = value_of(&bits, number_mode, raw);
+            if !choices.contains(&num) {
+                choices.push(num);
+            }
+        }
+        choices.shuffle(rng);
+        Self { bits, pattern, choices, answer }
+    }
+
+    pub fn is_correct(&self, guess: i32) -> bool {
+        guess == self.answer
+    }
+
+    /// The pattern as a sum of its powers of two, e.g. `0b1011_0100 = 128+32+16+4 = 180`.
+    /// A negative answer is in two's complement, so its highest bit counts negative.
+    pub fn breakdown(&self) -> String {
+        let terms: Vec<String> =
+            self.set_bits().into_iter().map(|(_, value)| value.to_string()).collect();
+        let binary = format!("0b{}", self.nibbles().join("_"));
+        match terms.len() {
+            0 | 1 => format!("{binary} = {}", self.answer),
+            _ => format!("{binary} = {} = {}", terms.join("+"), self.answer),
+        }
+    }
+
+    /// Index and value of each set bit of the pattern, highest first. The values add up
+    /// to the answer: they are scaled, and a negative answer's sign bit counts negative.
+    pub fn set_bits(&self) -> Vec<(u32, i64)> {
+        let width = self.bits.to_int();
+        let shift = self.bits.scale_factor().trailing_zeros();
+        (0..width)
+            .rev()
+            .filter(|index| self.pattern >> index & 1 == 1)
+            .map(|index| {
+                let weight = 1i64 << (index + shift);
+                let sign_bit = index == width - 1 && self.answer < 0;
+                (index, if sign_bit { -weight } else { weight })
+            })
+            .collect()
+    }
+
+    /// A nudge towards the answer, more telling with each `level`: first the value of the
+    /// highest set bit, then the range the upper half of the bits narrows the answer to.
+    /// None once there is nothing more to give away.
+    pub fn hint(&self, level: u32) -> Option<String> {
+        let set_bits = self.set_bits();
+        match level {
+            0 => Some(match set_bits.first() {
+                Some((_, value)) => format!("highest bit: {value}"),
+                None => "no bits set".to_string(),
+            }),
+            1 => {
+                let unknown = self.bits.to_int() / 2;
+                let known: i64 =
+                    set_bits.iter().filter(|&&(index, _)| index >= unknown).map(|(_, v)| v).sum();
+                let spread = ((1i64 << unknown) - 1) * i64::from(self.bits.scale_factor());
+                Some(format!("between {known} and {}", known + spread))
+            },
+            _ => None,
+        }
+    }
+
+    /// Patterns a slip away from this question's: one bit flipped, two neighboring groups
+    /// of four bits swapped, or one step off
+    pub fn near_misses(&self) -> Vec<u32> {
+        let width = self.bits.to_int();
+        let mask = (1u32 << width) - 1;
+        let mut misses: Vec<u32> = (0..width).map(|index| self.pattern ^ 1 << index).collect();
+        for shift in (4..width).step_by(4) {
+            let (low, high) = (self.pattern >> (shift - 4) & 0xF, self.pattern >> shift & 0xF);
+            let cleared = self.pattern & !(0xFF << (shift - 4));
+            misses.push(cleared | low << shift | high << (shift - 4));
+        }
+        misses.extend(self.pattern.checked_sub(1));
+        misses.push(self.pattern + 1);
+        let mut unique = Vec::new();
+        for miss in misses {
+            if miss != self.pattern && miss <= mask && !unique.contains(&miss) {
+                unique.push(miss);
+            }
+        }
+        unique
+    }
+
+    /// The bit pattern that reads as `value` in this question's mode, e.g. for a wrong
+    /// answer. Negative values are in two's complement.
+    pub fn pattern_of(&self, value: i32) -> u32 {
+        let mask = (1u32 << self.bits.to_int()) - 1;
+        ((value / self.bits.scale_factor() as i32) as u32) & mask
+    }
+
+    /// The pattern padded to the mode's width, in groups of four bits
+    pub fn binary_string(&self) -> String {
+        self.nibbles().join(" ")
+    }
+
+    /// The pattern padded to the mode's width as groups of four bits, highest first
+    pub fn nibbles(&self) -> Vec<String> {
+        self.nibbles_of(self.pattern)
+    }
+
+    /// Any pattern of this question's width as groups of four bits, highest first
+    pub fn nibbles_of(&self, pattern: u32) -> Vec<String> {
+        let width = self.bits.to_int() as usize;
+        let raw = format!("{:0width$b}", pattern, width = width);
+        raw.chars()
+            .collect::<Vec<_>>()
+            .chunks(4)
+            .map(|chunk| chunk.iter().collect::<String>())
+            .collect()
+    }
+}
+
+/// The number a pattern of the mode stands for: scaled, and in two's complement for signed
+pub fn value_of(bits: &Bits, number_mode: NumberMode, pattern: u32) -> i32 {
+    let width = bits.to_int();
+    let unscaled = match number_mode {
+        NumberMode::Signed if pattern >= 1 << (width - 1) => (pattern as i32) - (1 << width),
+        _ => pattern as i32,
+    };
+    unscaled * bits.scale_factor() as i32
+}
+
+/// How the numbers besides the answer are picked
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Distractors {
+    /// Anywhere in the mode's range
+    #[default]
+    Random,
+    /// Mistakes a player could make reading the bits, see `Question::near_misses`
+    NearMiss,
+}
+
+impl Distractors {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "random" => Some(Self::Random),
+            "near_miss" | "near" => Some(Self::NearMiss),
+            _ => None,
+        }
+    }
+}
+
+/// Deals the questions of a run one after another, never repeating a recent answer
+#[derive(Clone, Debug)]
+pub struct Dealer {
+    bits: Bits,
+    number_mode: NumberMode,
+    /// How many of the latest answers can't come up again, at least the last one
+    no_repeat: usize,
+    recent: VecDeque<i32>,
+    /// Deal from every sixteenth of the value space in turn instead of at random
+    coverage: bool,
+    /// Sixteenths of the value space not dealt from yet in this round of coverage
+    bands: Vec<u32>,
+    /// The answers that may be asked
+    filter: ValueFilter,
+    /// Numbers to choose from in each question
+    choices: usize,
+    distractors: Distractors,
+}
+
+impl Dealer {
+    /// Sixteen bands by the top four bits; the whole value space of a 4 bit mode
+    const BANDS: u32 = 16;
+
+    pub fn new(bits: Bits, number_mode: NumberMode) -> Self {
+        let choices = bits.suggestion_count();
+        Self {
+            bits,
+            number_mode,
+            no_repeat: 1,
+            recent: VecDeque::new(),
+            coverage: false,
+            bands: Vec::new(),
+            filter: ValueFilter::default(),
+            choices,
+            distractors: Distractors::default(),
+        }
+    }
+
+    /// Cover the whole range evenly: a 4 bit mode asks every value once in a shuffled
+    /// order before any comes up again, wider modes ask a value from each sixteenth of
+    /// their range in turn
+    pub fn coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    /// Keep the last `count` answers from coming up again. Never fewer than one, and
+    /// always leaving at least one value of the mode to ask.
+    pub fn no_repeat(mut self, count: usize) -> Self {
+        self.no_repeat = count.max(1);
+        self
+    }
+
+    /// Ask only the answers `filter` allows, the other choices too where there are
+    /// enough of them. In a mode the filter leaves nothing of, it is ignored.
+    pub fn filter(mut self, filter: ValueFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Offer `count` numbers to choose from instead of the mode's usual number
+    pub fn choices(mut self, count: usize) -> Self {
+        self.choices = count;
+        self
+    }
+
+    /// Pick the numbers besides the answer by `distractors`
+    pub fn distractors(mut self, distractors: Distractors) -> Self {
+        self.distractors = distractors;
+        self
+    }
+
+    /// The next question, its answer not among the recent ones
+    pub fn deal(&mut self, rng: &mut impl Rng) -> Question {
+        let allowed = self.filter.patterns(&self.bits, self.number_mode);
+        let mut question = match &allowed {
+            _ if self.coverage => self.deal_covered(allowed.as_deref(), rng),
+            Some(allowed) => self.deal_filtered(allowed, rng),
+            None => loop {
+                let question = Question::generate_with_choices(
+                    self.bits.clone(),
+                    self.number_mode,
+                    self.choices,
+                    rng,
+                );
+                if !self.recent.contains(&question.answer) {
+                    break question;
+                }
+            },
+        };
+        if self.distractors == Distractors::NearMiss {
+            self.offer_near_misses(&mut question, allowed.as_deref(), rng);
+        }
+        let space = allowed.map_or(self.bits.value_space(), |allowed| allowed.len());
+        self.recent.push_back(question.answer);
+        while self.recent.len() > self.no_repeat.min(space - 1) {
+            self.recent.pop_front();
+        }
+        question
+    }
+
+    /// A question about one of the `allowed` patterns that isn't recent
+    fn deal_filtered(&self, allowed: &[u32], rng: &mut impl Rng) -> Question {
+        let free: Vec<u32> = allowed
+            .iter()
+            .copied()
+            .filter(|&p| !self.recent.contains(&value_of(&self.bits, self.number_mode, p)))
+            .collect();
+        let pattern = free.choose(rng).or_else(|| allowed.choose(rng)).copied().unwrap_or(0);
+        self.question_for(pattern, Some(allowed), rng)
+    }
+
+    /// A question about `pattern`. Choices outside the `allowed` patterns would give the
+    /// answer away, so they are all picked from those if there are enough.
+    fn question_for(&self, pattern: u32, allowed: Option<&[u32]>, rng: &mut impl Rng) -> Question {
+        let mut question = Question::with_pattern_and_choices(
+            self.bits.clone(),
+            self.number_mode,
+            pattern,
+            self.choices,
+            rng,
+        );
+        if let Some(allowed) = allowed
+            && allowed.len() >= question.choices.len()
+        {
+            let others: Vec<i32> = allowed
+                .iter()
+                .filter(|&&other| other != pattern)
+                .map(|&other| value_of(&self.bits, self.number_mode, other))
+                .collect();
+            let count = question.choices.len() - 1;
+            question.choices = others.choose_multiple(rng, count).copied().collect();
+            question.choices.push(question.answer);
+            question.choices.shuffle(rng);
+        }
+        question
+    }
+
+    /// Swap the other choices of `question` for near misses that aren't filtered out,
+    /// keeping some of the random ones where there are too few
+    fn offer_near_misses(
+        &self,
+        question: &mut Question,
+        allowed: Option<&[u32]>,
+        rng: &mut impl Rng,
+    ) {
+        let mut misses: Vec<i32> = question
+            .near_misses()
+            .into_iter()
+            .map(|miss| value_of(&self.bits, self.number_mode, miss))
+            .filter(|&value| allowed.is_none() || self.filter.allows(value))
+            .collect();
+        misses.shuffle(rng);
+        let count = question.choices.len() - 1;
+        misses.truncate(count);
+        let answer = question.answer;
+        let random: Vec<i32> = question
+            .choices
+            .iter()
+            .copied()
+            .filter(|choice| *choice != answer && !misses.contains(choice))
+            .collect();
+        misses.extend(random.into_iter().take(count - misses.len()));
+        misses.push(answer);
+        misses.shuffle(rng);
+        question.choices = misses;
+    }
+
+    /// A question from the next band not dealt from yet, starting a new shuffled round
+    /// of bands once all were. Bands whose values are all recent, or filtered out, wait
+    /// their turn.
+    fn deal_covered(&mut self, allowed: Option<&[u32]>, rng: &mut impl Rng) -> Question {
+        let low_bits = self.bits.to_int() - Self::BANDS.trailing_zeros();
+        let free = |band: u32| -> Vec<u32> {
+            (band << low_bits..(band + 1) << low_bits)
+                .filter(|&p| {
+                    let value = value_of(&self.bits, self.number_mode, p);
+                    !self.recent.contains(&value)
+                        && (allowed.is_none() || self.filter.allows(value))
+                })
+                .collect()
+        };
+        let next = self.bands.iter().rposition(|&band| !free(band).is_empty());
+        let band = match next {
+            Some(index) => self.bands.remove(index),
+            None => {
+                let mut bands: Vec<u32> = (0..Self::BANDS).collect();
+                bands.shuffle(rng);
+                let index = bands.iter().rposition(|&band| !free(band).is_empty()).unwrap_or(0);
+                let band = bands.remove(index);
+                self.bands = bands;
+                band
+            },
+        };
+        let pattern = free(band).choose(rng).copied().unwrap_or(band << low_bits);
+        self.question_for(pattern, allowed, rng)
+    }
+
+    /// Deal from another mode from now on, with `choices` numbers to choose from and the
+    /// other options kept
+    pub fn set_mode(&mut self, bits: Bits, number_mode: NumberMode, choices: usize) {
+        self.bits = bits;
+        self.number_mode = number_mode;
+        self.choices = choices;
+        self.reset();
+    }
+
+    /// Forget the recent answers and start a new round of coverage, e.g. to replay a
+    /// seeded run from its start
+    pub fn reset(&mut self) {
+        self.recent.clear();
+        self.bands.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::ValueRule;
+
+    #[test]
+    fn generation_unique_and_scaled() {
+        let q = Question::generate(Bits::FourShift4, NumberMode::Unsigned, &mut rand::rng());
+        let scale = Bits::FourShift4.scale_factor();
+        assert_eq!(q.choices.len(), Bits::FourShift4.suggestion_count());
+        // uniqueness
+        let mut sorted = q.choices.clone();
+        sorted.sort_unstable();
+        for pair in sorted.windows(2) {
+            assert_ne!(pair[0], pair[1]);
+        }
+        // scaling property
+        for &s in &q.choices {
+            assert_eq!(s.unsigned_abs() % scale, 0);
+        }
+        // the answer must be one of the choices and pattern * scale == answer (unsigned)
+        assert!(q.choices.contains(&q.answer));
+        assert_eq!(q.pattern * scale, q.answer.unsigned_abs());
+    }
+
+    #[test]
+    fn pattern_of_reads_values_back_into_bits() {
+        let q = Question::generate(Bits::FourShift4, NumberMode::Unsigned, &mut rand::rng());
+        assert_eq!(q.pattern_of(q.answer), q.pattern);
+        assert_eq!(q.pattern_of(48), 0b0011);
+        let q = Question::generate(Bits::Four, NumberMode::Signed, &mut rand::rng());
+        assert_eq!(q.pattern_of(-1), 0b1111);
+        assert_eq!(q.nibbles_of(q.pattern_of(-8)), ["1000"]);
+    }
+
+    #[test]
+    fn breakdowns_sum_the_set_bits() {
+        let question = |bits, pattern, answer| Question { bits, pattern, choices: vec![], answer };
+        assert_eq!(
+            question(Bits::Eight, 0b1011_0100, 180).breakdown(),
+            "0b1011_0100 = 128+32+16+4 = 180"
+        );
+        assert_eq!(question(Bits::Four, 0b1101, -3).breakdown(), "0b1101 = -8+4+1 = -3");
+        assert_eq!(question(Bits::FourShift4, 0b0011, 48).breakdown(), "0b0011 = 32+16 = 48");
+        assert_eq!(question(Bits::Four, 0b0100, 4).breakdown(), "0b0100 = 4");
+    }
+
+    #[test]
+    fn hints_name_the_highest_bit_then_a_range() {
+        let question = |bits, pattern, answer| Question { bits, pattern, choices: vec![], answer };
+        let q = question(Bits::Eight, 0b1011_0100, 180);
+        assert_eq!(q.hint(0).as_deref(), Some("highest bit: 128"));
+        assert_eq!(q.hint(1).as_deref(), Some("between 176 and 191"));
+        assert_eq!(q.hint(2), None);
+        let q = question(Bits::Four, 0b1101, -3);
+        assert_eq!(q.hint(0).as_deref(), Some("highest bit: -8"));
+        assert_eq!(q.hint(1).as_deref(), Some("between -4 and -1"));
+        let q = question(Bits::FourShift4, 0b0000, 0);
+        assert_eq!(q.hint(0).as_deref(), Some("no bits set"));
+        assert_eq!(q.hint(1).as_deref(), Some("between 0 and 48"));
+    }
+
+    #[test]
+    fn dealers_never_repeat_the_recent_answers() {
+        let mut rng = rand::rng();
+        let mut dealer = Dealer::new(Bits::Four, NumberMode::Unsigned).no_repeat(5);
+        let answers: Vec<i32> = (0..200).map(|_| dealer.deal(&mut rng).answer).collect();
+        for window in answers.windows(6) {
+            assert!(!window[..5].contains(&window[5]), "{window:?} repeats");
+        }
+        // The whole value space can't be held back
+        let mut dealer = Dealer::new(Bits::Four, NumberMode::Signed).no_repeat(100);
+        let answers: Vec<i32> = (0..32).map(|_| dealer.deal(&mut rng).answer).collect();
+        assert_eq!(answers[..16], answers[16..]);
+    }
+
+    #[test]
+    fn coverage_deals_every_band_once_per_round() {
+        let mut rng = rand::rng();
+        let mut dealer = Dealer::new(Bits::Four, NumberMode::Signed).coverage(true);
+        let mut patterns: Vec<u32> = (0..16).map(|_| dealer.deal(&mut rng).pattern).collect();
+        patterns.sort_unstable();
+        assert_eq!(patterns, (0..16).collect::<Vec<_>>());
+
+        let mut dealer = Dealer::new(Bits::Twelve, NumberMode::Unsigned).coverage(true);
+        let mut bands: Vec<u32> = (0..16).map(|_| dealer.deal(&mut rng).pattern >> 8).collect();
+        bands.sort_unstable();
+        assert_eq!(bands, (0..16).collect::<Vec<_>>());
+        let question =
+            Question::with_pattern(Bits::FourShift4, NumberMode::Signed, 0b1110, &mut rng);
+        assert_eq!(question.answer, -32);
+        assert!(question.choices.contains(&-32));
+    }
+
+    #[test]
+    fn dealers_offer_the_number_of_choices_asked_for() {
+        let mut rng = rand::rng();
+        let mut dealer = Dealer::new(Bits::Sixteen, NumberMode::Signed).choices(2);
+        assert_eq!(dealer.deal(&mut rng).choices.len(), 2);
+        dealer.set_mode(Bits::Four, NumberMode::Unsigned, 6);
+        let question = dealer.deal(&mut rng);
+        assert_eq!(question.choices.len(), 6);
+        assert!(question.choices.contains(&question.answer));
+        let mut dealer = Dealer::new(Bits::Eight, NumberMode::Unsigned).choices(5).coverage(true);
+        assert_eq!(dealer.deal(&mut rng).choices.len(), 5);
+    }
+
+    #[test]
+    fn near_misses_are_a_slip_away() {
+        let question =
+            Question { bits: Bits::Eight, pattern: 0b0011_1010, choices: vec![], answer: 58 };
+        let misses = question.near_misses();
+        assert_eq!(misses[..2], [0b0011_1011, 0b0011_1000]);
+        assert!(misses.contains(&0b1010_0011), "nibbles swapped");
+        assert!(misses.contains(&0b0011_1001), "one less");
+        assert_eq!(misses.len(), 8 + 1 + 1);
+
+        let mut rng = rand::rng();
+        let mut dealer =
+            Dealer::new(Bits::Sixteen, NumberMode::Unsigned).distractors(Distractors::NearMiss);
+        for _ in 0..20 {
+            let question = dealer.deal(&mut rng);
+            let misses: Vec<i32> = question.near_misses().iter().map(|&p| p as i32).collect();
+            assert_eq!(question.choices.len(), 6);
+            assert!(question.choices.contains(&question.answer));
+            let others = question.choices.iter().filter(|&&choice| choice != question.answer);
+            assert!(others.clone().all(|choice| misses.contains(choice)), "{question:?}");
+        }
+        assert_eq!(Distractors::parse("Near-Miss"), Some(Distractors::NearMiss));
+    }
+
+    #[test]
+    fn filtered_dealers_ask_and_offer_only_allowed_values() {
+        let mut rng = rand::rng();
+        let filter = ValueFilter::default().only(vec![ValueRule::MultipleOf(32)]);
+        for coverage in [false, true] {
+            let mut dealer =
+                Dealer::new(Bits::Eight, NumberMode::Unsigned).no_repeat(20).filter(filter.clone());
+            dealer = dealer.coverage(coverage);
+            let answers: Vec<i32> = (0..16)
+                .map(|_| dealer.deal(&mut rng))
+                .inspect(|q| assert!(q.choices.iter().all(|choice| choice % 32 == 0), "{q:?}"))
+                .map(|q| q.answer)
+                .collect();
+            // Eight values pass, so the window shrinks to seven
+            for window in answers.windows(8) {
+                assert!(!window[..7].contains(&window[7]), "{window:?} repeats");
+            }
+        }
+        // Nothing of the mode passes, so the filter is ignored
+        let filter = ValueFilter::default().only(vec![ValueRule::Value(1000)]);
+        let mut dealer = Dealer::new(Bits::Four, NumberMode::Unsigned).filter(filter);
+        assert!(dealer.deal(&mut rng).answer < 16);
+    }
+
+    #[test]
+    fn signed_mode_generates_correct_raw_bits_for_negative() {
+        // Generate many questions and check that when we have a negative number,
+        // the pattern has the sign bit set correctly
+        for _ in 0..20 {
+            let q = Question::generate(Bits::Four, NumberMode::Signed, &mut rand::rng());
+
+            if q.answer < 0 {
+                // For negative numbers in 4-bit two's complement, the MSB (bit 3) should be 1
+                // which means the pattern should be >= 8 (0b1000)
+                assert!(
+                    q.pattern >= 8,
+                    "Negative number {} should have raw bits >= 8 (sign bit set), but got {}. Binary: {}",
+                    q.answer,
+                    q.pattern,
+                    q.binary_string()
+                );
+            } else {
+                // For positive numbers (including 0), MSB should be 0
+                // which means the pattern should be < 8
+                assert!(
+                    q.pattern < 8,
+                    "Positive number {} should have raw bits < 8 (sign bit clear), but got {}. Binary: {}",
+                    q.answer,
+                    q.pattern,
+                    q.binary_string()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn choices_are_randomized() {
+        // Verify that choices are properly randomized and the first choice
+        // is not always the answer
+        let mut first_is_correct_count = 0;
+        let trials = 100;
+
+        for _ in 0..trials {
+            let q = Question::generate(Bits::Four, NumberMode::Unsigned, &mut rand::rng());
+            // Check if the first choice happens to be the answer
+            if q.choices[0] == q.answer {
+                first_is_correct_count += 1;
+            }
+        }
+
+        // With 3 choices for 4-bit mode, we expect roughly 33% to be correct by chance
+        // Allow a range of 20-50% (which is generous for 100 trials to account for randomness)
+        // The key point is that it's NOT 100% (which would indicate no randomization)
+        assert!(
+            (20..=50).contains(&first_is_correct_count),
+            "First choice was correct {} times out of {}, expected around 33% (20-50 range). \
+             If this is close to 100%, choices are not randomized!",
+            first_is_correct_count,
+            trials
+        );
+
+        // Also verify that the answer is actually one of the choices
+        for _ in 0..10 {
+            let q = Question::generate(Bits::Eight, NumberMode::Signed, &mut rand::rng());
+            assert!(
+                q.choices.contains(&q.answer),
+                "answer {} must be in choices {:?}",
+                q.answer,
+                q.choices
+            );
+        }
+    }
+}
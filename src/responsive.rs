@@ -0,0 +1,40 @@
+//! One place for deciding between the regular and the compact layout, so
+//! every screen switches the same way when the terminal gets small.
+
+use ratatui::layout::{Rect, Size};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LayoutMode {
+    /// Everything the screen has, including banners and generous spacing
+    Full,
+    /// Banners hidden, spacing shrunk and side-by-side panels stacked
+    Compact,
+}
+
+impl LayoutMode {
+    /// Full when the screen's regular layout of size `full` fits in `area`
+    pub const fn for_area(area: Rect, full: Size) -> Self {
+        if area.width >= full.width && area.height >= full.height {
+            Self::Full
+        } else {
+            Self::Compact
+        }
+    }
+
+    pub const fn is_compact(self) -> bool {
+        matches!(self, Self::Compact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_when_either_dimension_is_short() {
+        let full = Size::new(70, 24);
+        assert_eq!(LayoutMode::for_area(Rect::new(0, 0, 70, 24), full), LayoutMode::Full);
+        assert_eq!(LayoutMode::for_area(Rect::new(0, 0, 69, 40), full), LayoutMode::Compact);
+        assert_eq!(LayoutMode::for_area(Rect::new(0, 0, 120, 23), full), LayoutMode::Compact);
+    }
+}
@@ -0,0 +1,290 @@
+//! Stepping through the questions of a run that were answered wrong, with the bits the
+//! answer got wrong highlighted.
+
+use crate::animation;
+use crate::keybinds;
+use crate::settings;
+use crate::summary::Answer;
+use crate::theme;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::time::Duration;
+
+/// The wrong answers of a run, one at a time
+pub struct MistakeReview {
+    mistakes: Vec<Answer>,
+    index: usize,
+    /// Playing or played for the mistake shown
+    solution: Option<Solution>,
+    closed: bool,
+}
+
+/// Adds up the set bits of the right answer one after another
+struct Solution {
+    /// Animation time of the start
+    started: Duration,
+}
+
+impl Solution {
+    /// Time each bit is added after the one before
+    const STEP: Duration = Duration::from_millis(700);
+
+    fn new() -> Self {
+        Self { started: animation::now() }
+    }
+
+    /// Bits added so far out of `total`; all of them with reduced motion
+    fn added(&self, total: usize) -> usize {
+        if settings::get().reduce_motion {
+            return total;
+        }
+        let elapsed = animation::now().saturating_sub(self.started);
+        #[allow(clippy::cast_possible_truncation)]
+        let steps = (elapsed.as_millis() / Self::STEP.as_millis()) as usize + 1;
+        steps.min(total)
+    }
+}
+
+impl MistakeReview {
+    /// Width of the labels in front of each row
+    const LABEL_WIDTH: usize = 13;
+
+    /// None if every answer was right
+    pub fn new(answers: &[Answer]) -> Option<Self> {
+        let mistakes: Vec<Answer> =
+            answers.iter().filter(|answer| !answer.is_correct()).cloned().collect();
+        (!mistakes.is_empty()).then_some(Self { mistakes, index: 0, solution: None, closed: false })
+    }
+
+    /// Left and right step through the mistakes, select plays the solution of the one shown
+    /// and exit goes back to the summary
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        match key {
+            x if keybinds::is_left(x) => self.show(self.index.saturating_sub(1)),
+            x if keybinds::is_right(x) => self.show((self.index + 1).min(self.mistakes.len() - 1)),
+            x if keybinds::is_select(x) => self.solution = Some(Solution::new()),
+            x if keybinds::is_exit(x) => self.closed = true,
+            _ => {},
+        }
+    }
+
+    fn show(&mut self, index: usize) {
+        if index != self.index {
+            self.index = index;
+            self.solution = None;
+        }
+    }
+
+    /// The solution is still adding bits
+    pub fn is_animating(&self) -> bool {
+        let total = self.mistakes[self.index].question.set_bits().len();
+        self.solution.as_ref().is_some_and(|solution| solution.added(total) < total)
+            && !animation::is_paused()
+    }
+
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// A pattern in groups of four bits, the bits set in `differing` in `highlight`
+    fn bit_spans(
+        answer: &Answer,
+        pattern: u32,
+        differing: u32,
+        highlight: Style,
+    ) -> Vec<Span<'static>> {
+        let text = Style::default().fg(theme::current().text);
+        Self::styled_bits(answer, pattern, |index| {
+            if differing >> index & 1 == 1 { highlight } else { text }
+        })
+    }
+
+    /// A pattern in groups of four bits, each in the style for its index
+    fn styled_bits(
+        answer: &Answer,
+        pattern: u32,
+        style: impl Fn(u32) -> Style,
+    ) -> Vec<Span<'static>> {
+        let mut index = answer.question.bits.to_int();
+        let mut spans = Vec::new();
+        for nibble in answer.question.nibbles_of(pattern) {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" "));
+            }
+            for digit in nibble.chars() {
+                index -= 1;
+                spans.push(Span::styled(digit.to_string(), style(index)));
+            }
+        }
+        spans
+    }
+
+    fn row(label: &str, mut spans: Vec<Span<'static>>) -> Line<'static> {
+        let label = format!("{label:<width$}", width = Self::LABEL_WIDTH);
+        spans.insert(0, Span::styled(label, Style::default().fg(theme::current().muted)));
+        Line::from(spans)
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let answer = &self.mistakes[self.index];
+        let question = &answer.question;
+        let value =
+            |value: i32| Span::styled(format!(" = {value}"), Style::default().fg(theme.text));
+        let mut lines = vec![
+            Line::styled(
+                format!("Mistake {} of {}", self.index + 1, self.mistakes.len()),
+                Style::default().fg(theme.accent).bold(),
+            )
+            .centered(),
+            Line::from(""),
+        ];
+        let guessed = answer.guess.map(|guess| (guess, question.pattern_of(guess)));
+        let differing = guessed.map_or(0, |(_, pattern)| pattern ^ question.pattern);
+        let correct = Style::default().fg(theme.correct).bold();
+        let incorrect = Style::default().fg(theme.incorrect).bold();
+
+        lines.push(Self::row("Question", Self::bit_spans(answer, question.pattern, 0, correct)));
+        lines.push(match guessed {
+            Some((guess, pattern)) => {
+                let mut spans = Self::bit_spans(answer, pattern, differing, incorrect);
+                spans.push(value(guess));
+                Self::row("Your answer", spans)
+            },
+            None => Self::row("Your answer", vec![Span::styled("none, time ran out", incorrect)]),
+        });
+        if let Some(solution) = &self.solution {
+            lines.extend(Self::solution_lines(answer, solution));
+            return lines;
+        }
+        let mut spans = Self::bit_spans(answer, question.pattern, differing, correct);
+        spans.push(value(question.answer));
+        lines.push(Self::row("Correct", spans));
+
+        if differing != 0 {
+            // Labeled like the LEDs of the mode, shifted modes start above bit 0
+            let first_bit = question.bits.scale_factor().trailing_zeros();
+            let bits: Vec<String> = (0..question.bits.to_int())
+                .rev()
+                .filter(|index| differing >> index & 1 == 1)
+                .map(|index| (index + first_bit).to_string())
+                .collect();
+            let label = if bits.len() == 1 { "bit" } else { "bits" };
+            lines.push(Self::row(
+                "Differs in",
+                vec![Span::styled(
+                    format!("{label} {}", bits.join(", ")),
+                    Style::default().fg(theme.text),
+                )],
+            ));
+        }
+        lines
+    }
+}
+
+impl MistakeReview {
+    /// The right answer with the bits added so far lit, the newest one marked, and their sum
+    fn solution_lines(answer: &Answer, solution: &Solution) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let set_bits = answer.question.set_bits();
+        let added = &set_bits[..solution.added(set_bits.len())];
+        let newest = added.last().map(|&(index, _)| index);
+        let bits = Self::styled_bits(answer, answer.question.pattern, |index| {
+            if Some(index) == newest {
+                Style::default().fg(theme.accent).bold().reversed()
+            } else if added.iter().any(|&(added, _)| added == index) {
+                Style::default().fg(theme.correct).bold()
+            } else {
+                Style::default().fg(theme.muted)
+            }
+        });
+        let terms: Vec<String> = added.iter().map(|(_, value)| value.to_string()).collect();
+        let total: i64 = added.iter().map(|(_, value)| value).sum();
+        let done = added.len() == set_bits.len();
+        let sum = match (terms.len(), done) {
+            (0, _) => "0".to_string(),
+            (1, false) => terms.join(""),
+            (_, false) => format!("{} = {total}", terms.join("+")),
+            (_, true) => format!("{} = {}", terms.join("+"), answer.question.answer),
+        };
+        let sum_style = if done { theme.correct } else { theme.text };
+        vec![
+            Self::row("Correct", bits),
+            Self::row("Sum", vec![Span::styled(sum, Style::default().fg(sum_style))]),
+        ]
+    }
+}
+
+impl Widget for &MistakeReview {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let block = Block::bordered()
+            .title(" Mistakes ")
+            .title_alignment(Center)
+            .border_style(Style::default().fg(theme.muted));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines = self.lines();
+        #[allow(clippy::cast_possible_truncation)]
+        let (width, height) =
+            (lines.iter().map(Line::width).max().unwrap_or(0) as u16, lines.len() as u16);
+        let [column] =
+            Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(inner);
+        let [text_area] =
+            Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(column);
+        Paragraph::new(lines).render(text_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binbreak::{Bits, Question};
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn reviews_step_through_mistakes_and_name_the_wrong_bits() {
+        let question =
+            Question { bits: Bits::Eight, pattern: 0b1011_0100, choices: vec![], answer: 180 };
+        let answers = [
+            Answer { question: question.clone(), guess: Some(148), secs: 1.0 },
+            Answer { question: question.clone(), guess: Some(180), secs: 1.0 },
+            Answer { question, guess: None, secs: 8.0 },
+        ];
+        let Some(mut review) = MistakeReview::new(&answers) else { panic!("two are wrong") };
+        let text = |review: &MistakeReview| -> Vec<String> {
+            review.lines().iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(
+            text(&review)[2..],
+            [
+                "Question     1011 0100",
+                "Your answer  1001 0100 = 148",
+                "Correct      1011 0100 = 180",
+                "Differs in   bit 5",
+            ]
+        );
+
+        review.handle_input(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(text(&review)[4..], ["Correct      1011 0100", "Sum          128"]);
+        assert!(review.is_animating());
+        animation::advance(Solution::STEP * 3);
+        assert_eq!(text(&review)[5], "Sum          128+32+16+4 = 180");
+        assert!(!review.is_animating());
+
+        review.handle_input(KeyEvent::from(KeyCode::Right));
+        review.handle_input(KeyEvent::from(KeyCode::Right));
+        assert_eq!(text(&review)[0], "Mistake 2 of 2");
+        assert_eq!(text(&review)[3], "Your answer  none, time ran out");
+        assert!(!review.is_closed());
+        review.handle_input(KeyEvent::from(KeyCode::Esc));
+        assert!(review.is_closed());
+        assert!(MistakeReview::new(&answers[1..2]).is_none());
+    }
+}
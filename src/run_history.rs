@@ -0,0 +1,114 @@
+//! The latest questions of the run in progress and how they went, beside the game on
+//! screens wide enough for it.
+
+use crate::summary::Answer;
+use crate::theme;
+use binbreak::Bits;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Margin, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+/// The last few answers of a run, newest on top
+pub struct RunHistory<'a> {
+    answers: &'a [Answer],
+    bits: &'a Bits,
+}
+
+impl<'a> RunHistory<'a> {
+    /// Answers listed
+    pub const COUNT: usize = 5;
+
+    pub const fn new(answers: &'a [Answer], bits: &'a Bits) -> Self {
+        Self { answers, bits }
+    }
+
+    /// Widest value of the mode, with room for a minus sign
+    fn value_width(&self) -> usize {
+        self.bits.upper_bound().to_string().len() + 1
+    }
+
+    /// A mark, the bits in groups of four and the value, in a border with padding; the
+    /// same for every run of a mode so the game beside it stays put
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn size(&self) -> Size {
+        let width = self.bits.to_int() as usize;
+        let binary_width = width + width / 4 - 1;
+        Size::new((2 + binary_width + 1 + self.value_width()) as u16 + 4, Self::COUNT as u16 + 2)
+    }
+
+    fn line(&self, answer: &Answer) -> Line<'static> {
+        let theme = theme::current();
+        let (mark, color) = match answer.guess {
+            _ if answer.is_correct() => ("✓", theme.correct),
+            Some(_) => ("✗", theme.incorrect),
+            None => ("✗", theme.timeout),
+        };
+        Line::from(vec![
+            Span::styled(format!("{mark} "), Style::default().fg(color).bold()),
+            Span::styled(answer.question.binary_string(), Style::default().fg(theme.text)),
+            Span::styled(
+                format!(" {:>width$}", answer.question.answer, width = self.value_width()),
+                Style::default().fg(color),
+            ),
+        ])
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        if self.answers.is_empty() {
+            return vec![Line::styled("none yet", Style::default().fg(theme::current().muted))];
+        }
+        self.answers.iter().rev().take(Self::COUNT).map(|answer| self.line(answer)).collect()
+    }
+}
+
+impl Widget for &RunHistory<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let block = Block::bordered()
+            .title(" Recent ")
+            .title_alignment(Center)
+            .border_style(Style::default().fg(theme.muted));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(self.lines()).render(inner.inner(Margin::new(1, 0)), buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binbreak::Question;
+
+    #[test]
+    fn history_lists_the_latest_answers_newest_first() {
+        let answer = |pattern: u32, guess| Answer {
+            question: Question {
+                bits: Bits::Eight,
+                pattern,
+                choices: vec![],
+                answer: pattern as i32,
+            },
+            guess,
+            secs: 1.0,
+        };
+        let answers: Vec<Answer> =
+            (1..=6).map(|n| answer(n * 40, Some(40))).chain([answer(7, None)]).collect();
+        let history = RunHistory::new(&answers, &Bits::Eight);
+        let lines: Vec<String> = history.lines().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "✗ 0000 0111    7",
+                "✗ 1111 0000  240",
+                "✗ 1100 1000  200",
+                "✗ 1010 0000  160",
+                "✗ 0111 1000  120",
+            ]
+        );
+        assert_eq!(history.size(), Size::new(2 + 9 + 1 + 4 + 4, 7));
+        assert_eq!(history.line(&answers[0]).to_string(), "✓ 0010 1000   40");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::theme;
+use crate::utils::BitToggleWidget;
+use binbreak::Bits;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::Stylize;
+use ratatui::widgets::{Block, Widget};
+
+/// Free play with the bits of a mode, no questions and no clock
+pub struct SandboxScreen {
+    bits: u32,
+    toggle: BitToggleWidget,
+    exit_intended: bool,
+}
+
+impl SandboxScreen {
+    /// As many bits as the questions of the mode have
+    pub const fn new(bits: &Bits) -> Self {
+        let bits = bits.to_int();
+        Self { bits, toggle: BitToggleWidget::new(bits), exit_intended: false }
+    }
+
+    /// The toggle and a blank line above and below, in a border
+    fn size(&self) -> Size {
+        Size::new(self.toggle.width().max(20) + 4, BitToggleWidget::HEIGHT + 4)
+    }
+}
+
+impl WidgetRef for SandboxScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let size = self.size();
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        let block = Block::bordered()
+            .title(format!(" Sandbox: {} bit ", self.bits))
+            .title_alignment(Center)
+            .fg(theme.muted);
+        let inner = block.inner(frame);
+        block.render(frame, buf);
+
+        let [toggle_row] = Layout::vertical([Constraint::Length(BitToggleWidget::HEIGHT)])
+            .flex(Flex::Center)
+            .areas(inner);
+        self.toggle.render(toggle_row, buf);
+    }
+}
+
+impl MainScreenWidget for SandboxScreen {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if !self.toggle.handle_input(input) && keybinds::is_exit(input) {
+            self.exit_intended = true;
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    fn min_size(&self) -> Size {
+        self.size()
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{EXIT, FLIP, LEFT, RIGHT};
+        vec![
+            KeyHint::new(&[LEFT, RIGHT], "move"),
+            KeyHint::new(&[FLIP], "flip"),
+            KeyHint::new(&[EXIT], "back"),
+        ]
+    }
+}
@@ -0,0 +1,94 @@
+//! Score, streak and lives of a run, and how each answer changes them.
+
+/// Lives a run starts with, more can be earned up to the run's maximum
+pub const STARTING_LIVES: u32 = 3;
+/// Every this many correct answers in a row earn a life back
+pub const LIFE_EVERY_STREAK: u32 = 5;
+
+/// The stats of one run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub score: u32,
+    pub streak: u32,
+    pub max_streak: u32,
+    pub rounds: u32,
+    pub lives: u32,
+    pub max_lives: u32,
+}
+
+impl Run {
+    pub fn new(max_lives: u32) -> Self {
+        Self {
+            score: 0,
+            streak: 0,
+            max_streak: 0,
+            rounds: 0,
+            lives: max_lives.min(STARTING_LIVES),
+            max_lives,
+        }
+    }
+
+    /// Score a correct answer, returns the points it earned
+    pub fn correct(&mut self) -> u32 {
+        self.correct_with_hints(0)
+    }
+
+    /// Score a correct answer given after taking `hints`; each one halves the points
+    pub fn correct_with_hints(&mut self, hints: u32) -> u32 {
+        self.rounds += 1;
+        self.streak += 1;
+        self.max_streak = self.max_streak.max(self.streak);
+        let points = points_for_streak(self.streak).checked_shr(hints).unwrap_or(0);
+        self.score += points;
+        if self.streak.is_multiple_of(LIFE_EVERY_STREAK) && self.lives < self.max_lives {
+            self.lives += 1;
+        }
+        points
+    }
+
+    /// Score a wrong or timed out answer; untimed practice keeps its lives
+    pub fn incorrect(&mut self, lose_life: bool) {
+        self.rounds += 1;
+        self.streak = 0;
+        if lose_life {
+            self.lives = self.lives.saturating_sub(1);
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+}
+
+/// 10 points, plus 2 for every earlier correct answer of the streak
+pub fn points_for_streak(streak: u32) -> u32 {
+    10 + streak.saturating_sub(1) * 2
+}
+
+/// Seconds to answer a question; the clock gets shorter as the streak grows
+pub fn time_limit(streak: u32) -> f64 {
+    10.0 - f64::from(streak.min(8)) * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaks_earn_bonus_points_and_lives() {
+        let mut run = Run::new(3);
+        run.incorrect(true);
+        assert_eq!((run.lives, run.rounds), (2, 1));
+
+        let points: Vec<u32> = (0..5).map(|_| run.correct()).collect();
+        assert_eq!(points, [10, 12, 14, 16, 18]);
+        assert_eq!((run.score, run.max_streak, run.lives), (70, 5, 3));
+
+        run.incorrect(false);
+        assert_eq!((run.streak, run.lives, run.max_streak), (0, 3, 5));
+        assert_eq!(run.correct_with_hints(1), 5);
+        assert_eq!(run.correct_with_hints(2), 3);
+        assert!((time_limit(0) - 10.0).abs() < f64::EPSILON);
+        assert!((time_limit(20) - 6.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::MainScreenWidget;
+use crate::message::Message;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Rect, Size};
+
+/// What the app should do with the screen stack after a screen handled something
+pub enum Transition {
+    Stay,
+    /// Open a screen on top, e.g. a game started from the menu or an overlay
+    Push(Box<dyn Screen>),
+    /// Close this screen and go back to the one below
+    Pop,
+    Exit,
+}
+
+/// A screen on the stack. Only the top screen gets messages; the screens below
+/// keep their state until they are on top again.
+pub trait Screen {
+    /// Change state in response to a message, without drawing anything
+    fn update(&mut self, msg: Message) -> Transition;
+    /// Draw the current state
+    fn view(&mut self, area: Rect, buf: &mut Buffer);
+    /// Actions available right now, shown in the footer
+    fn key_hints(&self) -> Vec<KeyHint>;
+    /// Smallest area the screen can be drawn in without clipping
+    fn min_size(&self) -> Size;
+    /// Something moves, so the app keeps redrawing instead of waiting for input
+    fn is_animating(&self) -> bool {
+        false
+    }
+    /// Only decoration moves (the title animation), so a low frame rate does
+    fn is_idle_animation(&self) -> bool {
+        false
+    }
+    /// Drawn over the screen below (help, pause, settings) instead of replacing it
+    fn is_overlay(&self) -> bool {
+        false
+    }
+    /// Type name shown in the debug overlay
+    fn name(&self) -> &'static str {
+        let path = std::any::type_name::<Self>();
+        path.rsplit("::").next().unwrap_or(path)
+    }
+    /// The screen above was closed and this one is on top again
+    fn resume(&mut self) {}
+    /// The config file was edited while the app runs
+    fn config_changed(&mut self) {}
+    /// The app is about to close: keep what would be lost otherwise
+    fn save(&mut self) {}
+}
+
+/// Full screens close themselves by intending to exit
+impl<T: MainScreenWidget> Screen for T {
+    fn update(&mut self, msg: Message) -> Transition {
+        match msg {
+            Message::Key(key) => self.handle_input(key),
+            Message::KeyRelease(key) => self.handle_key_release(key),
+            Message::Mouse(mouse) => MainScreenWidget::handle_mouse(self, mouse),
+            Message::Paste(text) => self.handle_paste(&text),
+            Message::Tick(dt) => self.run(dt),
+            Message::AnimationFinished => self.animation_finished(),
+            Message::Resize(_) | Message::Focus(_) | Message::Terminate => {},
+        }
+        if self.is_exit_intended() {
+            Transition::Pop
+        } else {
+            Transition::Stay
+        }
+    }
+
+    fn view(&mut self, area: Rect, buf: &mut Buffer) {
+        self.render_ref(area, buf);
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        MainScreenWidget::key_hints(self)
+    }
+
+    fn min_size(&self) -> Size {
+        MainScreenWidget::min_size(self)
+    }
+
+    fn is_animating(&self) -> bool {
+        MainScreenWidget::is_animating(self)
+    }
+
+    fn config_changed(&mut self) {
+        MainScreenWidget::config_changed(self);
+    }
+
+    fn save(&mut self) {
+        MainScreenWidget::save(self);
+    }
+}
+
+/// The open screens, bottom first. The app exits once the last one is closed.
+#[derive(Default)]
+pub struct ScreenStack {
+    screens: Vec<Box<dyn Screen>>,
+    /// Area the screens are drawn in, from the last resize
+    size: Size,
+    /// The top screen was animating after the last message. Animations that run on the
+    /// clock can come to rest between messages, so this is kept rather than asked again.
+    top_animating: bool,
+}
+
+impl ScreenStack {
+    pub fn push(&mut self, screen: Box<dyn Screen>) {
+        self.screens.push(screen);
+        self.top_animating = self.is_animating();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.screens.is_empty()
+    }
+
+    pub fn apply(&mut self, transition: Transition) {
+        match transition {
+            Transition::Stay => {},
+            Transition::Push(screen) => {
+                tracing::info!(screen = screen.name(), "open");
+                self.screens.push(screen);
+            },
+            Transition::Pop => {
+                if let Some(screen) = self.screens.pop() {
+                    tracing::info!(screen = screen.name(), "close");
+                }
+                if let Some(top) = self.screens.last_mut() {
+                    top.resume();
+                }
+            },
+            Transition::Exit => {
+                tracing::info!("exit");
+                for screen in &mut self.screens {
+                    screen.save();
+                }
+                self.screens.clear();
+            },
+        }
+        self.top_animating = self.is_animating();
+    }
+
+    /// Hand a message to the top screen. Time stands still while the screens don't
+    /// fit, so a hidden run is held rather than lost.
+    pub fn update(&mut self, msg: Message) {
+        match msg {
+            Message::Resize(size) => self.size = size,
+            Message::Tick(_) if self.is_too_small() => return,
+            _ => {},
+        }
+        let Some(top) = self.screens.last_mut() else {
+            return;
+        };
+        let mut transition = top.update(msg);
+        if matches!(transition, Transition::Stay) && self.top_animating && !top.is_animating() {
+            transition = top.update(Message::AnimationFinished);
+        }
+        self.apply(transition);
+    }
+
+    pub fn config_changed(&mut self) {
+        for screen in &mut self.screens {
+            screen.config_changed();
+        }
+    }
+
+    /// Index of the screen the visible overlays are drawn over. It changes when a
+    /// full screen opens or closes, not with overlays.
+    pub fn visible_from(&self) -> usize {
+        self.screens.iter().rposition(|screen| !screen.is_overlay()).unwrap_or(0)
+    }
+
+    pub fn view(&mut self, area: Rect, buf: &mut Buffer) {
+        let from = self.visible_from();
+        for screen in &mut self.screens[from..] {
+            screen.view(area, buf);
+        }
+    }
+
+    /// Every visible screen has to fit
+    pub fn min_size(&self) -> Size {
+        self.screens[self.visible_from()..].iter().map(|screen| screen.min_size()).fold(
+            Size::ZERO,
+            |required, size| {
+                Size::new(required.width.max(size.width), required.height.max(size.height))
+            },
+        )
+    }
+
+    /// The screens don't fit the area they are drawn in
+    pub fn is_too_small(&self) -> bool {
+        let required = self.min_size();
+        self.size.width < required.width || self.size.height < required.height
+    }
+
+    /// The open screens by name, bottom first
+    pub fn describe(&self) -> String {
+        self.screens.iter().map(|screen| screen.name()).collect::<Vec<_>>().join(" > ")
+    }
+
+    pub fn key_hints(&self) -> Vec<KeyHint> {
+        self.screens.last().map(|screen| screen.key_hints()).unwrap_or_default()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.screens.last().is_some_and(|screen| screen.is_animating())
+    }
+
+    pub fn is_idle_animation(&self) -> bool {
+        self.screens.last().is_some_and(|screen| screen.is_idle_animation())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Records what happens to it under a name, closes on any key
+    struct Probe {
+        name: &'static str,
+        overlay: bool,
+        /// Ticks until its animation comes to rest
+        animation_ticks: u32,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Probe {
+        fn boxed(name: &'static str, overlay: bool, log: &Rc<RefCell<Vec<String>>>) -> Box<Self> {
+            Box::new(Self { name, overlay, animation_ticks: 0, log: Rc::clone(log) })
+        }
+
+        fn record(&self, what: &str) {
+            self.log.borrow_mut().push(format!("{} {what}", self.name));
+        }
+    }
+
+    impl Screen for Probe {
+        fn update(&mut self, msg: Message) -> Transition {
+            match msg {
+                Message::Key(_) => {
+                    self.record("key");
+                    return Transition::Pop;
+                },
+                Message::Tick(_) => {
+                    self.record("tick");
+                    self.animation_ticks = self.animation_ticks.saturating_sub(1);
+                },
+                Message::AnimationFinished => self.record("finished"),
+                Message::KeyRelease(_)
+                | Message::Mouse(_)
+                | Message::Paste(_)
+                | Message::Resize(_)
+                | Message::Focus(_)
+                | Message::Terminate => {},
+            }
+            Transition::Stay
+        }
+        fn view(&mut self, _area: Rect, _buf: &mut Buffer) {
+            self.record("view");
+        }
+        fn key_hints(&self) -> Vec<KeyHint> {
+            Vec::new()
+        }
+        fn min_size(&self) -> Size {
+            if self.overlay {
+                Size::new(10, 30)
+            } else {
+                Size::new(20, 5)
+            }
+        }
+        fn is_animating(&self) -> bool {
+            self.animation_ticks > 0
+        }
+        fn is_overlay(&self) -> bool {
+            self.overlay
+        }
+        fn resume(&mut self) {
+            self.record("resume");
+        }
+        fn save(&mut self) {
+            self.record("save");
+        }
+    }
+
+    #[test]
+    fn overlays_draw_over_the_screen_below_and_pop_back_to_it() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut stack = ScreenStack::default();
+        stack.push(Probe::boxed("menu", false, &log));
+        stack.push(Probe::boxed("game", false, &log));
+        stack.push(Probe::boxed("help", true, &log));
+
+        let area = Rect::new(0, 0, 40, 40);
+        stack.view(area, &mut Buffer::empty(area));
+        assert_eq!(stack.min_size(), Size::new(20, 30));
+        assert_eq!(stack.describe(), "Probe > Probe > Probe");
+        stack.update(Message::Key(KeyEvent::from(KeyCode::Esc)));
+        assert_eq!(*log.borrow(), ["game view", "help view", "help key", "game resume"]);
+
+        log.borrow_mut().clear();
+        stack.apply(Transition::Exit);
+        assert!(stack.is_empty());
+        assert_eq!(*log.borrow(), ["menu save", "game save"], "everything open is saved");
+    }
+
+    #[test]
+    fn time_holds_while_too_small_and_animations_report_when_they_finish() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut stack = ScreenStack::default();
+        let mut intro = Probe::boxed("intro", false, &log);
+        intro.animation_ticks = 2;
+        stack.push(intro);
+
+        stack.update(Message::Resize(Size::new(10, 5)));
+        stack.update(Message::Tick(0.1));
+        assert!(stack.is_too_small() && log.borrow().is_empty());
+
+        stack.update(Message::Resize(Size::new(80, 24)));
+        stack.update(Message::Tick(0.1));
+        stack.update(Message::Tick(0.1));
+        stack.update(Message::Tick(0.1));
+        assert_eq!(*log.borrow(), ["intro tick", "intro tick", "intro finished", "intro tick"]);
+    }
+}
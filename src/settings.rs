@@ -0,0 +1,259 @@
+use crate::cli::{self, Cli};
+use crate::color::ColorDepth;
+use crate::config::Config;
+use crate::effects::MenuBackground;
+use crate::feedback::Bell;
+use crate::keybinds::{KeyLayout, KeyProfile};
+use crate::leaderboard::Endpoint;
+use crate::terminal_bg::Background;
+use crate::timing::FrameRate;
+use crate::utils::NibbleSeparator;
+use binbreak::filter::ValueRule;
+use binbreak::{Bits, Distractors, ValueFilter};
+use std::cell::{Cell, RefCell};
+
+/// Runtime settings shared by the whole app, resolved once at startup from
+/// command line flags, environment variables and the config file.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // independent switches, not a state machine
+pub struct Settings {
+    /// Render without colors, conveying state through markers and modifiers instead
+    pub no_color: bool,
+    /// Play without reading or writing any profile data (high scores, mastery)
+    pub guest: bool,
+    /// Show still frames instead of animations and effects
+    pub reduce_motion: bool,
+    /// Moving past the last menu item selects the first one and vice versa
+    pub wrap_menus: bool,
+    /// Detected terminal background, picks the default theme
+    pub background: Background,
+    /// Colors the terminal can show, theme colors are downgraded to fit
+    pub color_depth: ColorDepth,
+    /// Redraw rate while something moves
+    pub fps: FrameRate,
+    /// Drawn behind the start menu
+    pub menu_background: MenuBackground,
+    /// Between the nibbles of binary numbers
+    pub nibble_separator: NibbleSeparator,
+    /// After a wrong answer, show how the bits add up to the right one
+    pub explain_mistakes: bool,
+    /// How many of the latest answers a run doesn't ask again, at least the last one
+    pub no_repeat: usize,
+    /// Modes the bit value cheat sheet can be opened in
+    pub cheat_sheet: ModeSet,
+    /// Numbers to choose from in each mode
+    pub choices: ChoiceCounts,
+    /// How the wrong numbers to choose from are picked
+    pub distractors: Distractors,
+    /// Keyboard layout typed on, the keys keep their places
+    pub key_layout: KeyLayout,
+    /// Editor style keys on top of the usual ones
+    pub key_profile: KeyProfile,
+    /// Play sound effects, in builds with the `sound` feature
+    pub sound: bool,
+    /// Ring the terminal bell or flash the screen when a life is lost
+    pub bell: Bell,
+    /// The terminal reports key releases (kitty keyboard protocol), so keys can be held
+    pub key_releases: bool,
+    /// Art is read from the source tree too, and reloaded when it changes
+    pub dev: bool,
+}
+
+impl Settings {
+    /// Resolve settings; command line flags win over the environment and the config file
+    pub fn resolve(cli: &Cli, config: &Config) -> Self {
+        // https://no-color.org: any non-empty value disables color
+        let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+        Self {
+            no_color: cli.no_color || no_color_env || config_flag(config, "no_color"),
+            guest: cli.guest,
+            reduce_motion: cli.reduce_motion || config_flag(config, "reduce_motion"),
+            wrap_menus: config_flag(config, "wrap_menus"),
+            background: Background::default(),
+            color_depth: ColorDepth::detect(config),
+            fps: cli
+                .fps
+                .or_else(|| config.get("fps").and_then(FrameRate::parse))
+                .unwrap_or_default(),
+            menu_background: config
+                .get("menu_background")
+                .and_then(MenuBackground::parse)
+                .unwrap_or_default(),
+            nibble_separator: config
+                .get("nibble_separator")
+                .and_then(NibbleSeparator::parse)
+                .unwrap_or_default(),
+            explain_mistakes: config_flag(config, "explain_mistakes"),
+            no_repeat: config.get("no_repeat").and_then(|n| n.trim().parse().ok()).unwrap_or(1),
+            cheat_sheet: config.get("cheat_sheet").and_then(ModeSet::parse).unwrap_or_default(),
+            choices: config.get("choices").and_then(ChoiceCounts::parse).unwrap_or_default(),
+            distractors: config.get("distractors").and_then(Distractors::parse).unwrap_or_default(),
+            key_layout: config.get("keys").and_then(KeyLayout::parse).unwrap_or_default(),
+            key_profile: config.get("key_profile").and_then(KeyProfile::parse).unwrap_or_default(),
+            // On where it can play, unless the config turns it off
+            sound: cfg!(feature = "sound")
+                && config.get("sound").is_none_or(|_| config_flag(config, "sound")),
+            bell: config.get("bell").and_then(Bell::parse).unwrap_or_default(),
+            key_releases: false,
+            dev: cli.dev,
+        }
+    }
+}
+
+/// Some of the bit modes, named in the config file like `--bits` names them, e.g.
+/// `4, 4x16, 8`; `on` is all of them and `off` none
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ModeSet(u8);
+
+impl ModeSet {
+    const ALL: Self = Self(0b111_1111);
+
+    const fn bit(bits: &Bits) -> u8 {
+        1 << mode_index(bits)
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "on" | "true" | "yes" | "all" => return Some(Self::ALL),
+            "off" | "false" | "no" | "none" => return Some(Self(0)),
+            _ => {},
+        }
+        value
+            .split(',')
+            .try_fold(0, |set, name| cli::parse_bits(name).ok().map(|bits| set | Self::bit(&bits)))
+            .map(Self)
+    }
+
+    pub const fn contains(self, bits: &Bits) -> bool {
+        self.0 & Self::bit(bits) != 0
+    }
+}
+
+impl Default for ModeSet {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// The answers runs may ask, from the `only_values` and `skip_values` keys of the config,
+/// e.g. `only_values = *8`. A list that can't be read is left out with a warning.
+pub fn resolve_value_filter(config: &Config) -> ValueFilter {
+    let rules = |key: &str| -> Vec<ValueRule> {
+        let Some(value) = config.get(key) else { return Vec::new() };
+        ValueFilter::parse_rules(value).unwrap_or_else(|| {
+            tracing::warn!(key, value, "expected numbers, ranges like 16..=31 or *8, ignored");
+            Vec::new()
+        })
+    };
+    ValueFilter::default().only(rules("only_values")).except(rules("skip_values"))
+}
+
+/// How many numbers to choose from, in all modes like `choices = 3` or per mode like
+/// `choices = 4: 2, 16: 6`. Modes left out keep their usual number.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChoiceCounts([u8; 7]);
+
+impl ChoiceCounts {
+    /// Fewer is no choice, more don't fit next to each other
+    const RANGE: std::ops::RangeInclusive<u8> = 2..=6;
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let count = |count: &str| count.trim().parse().ok().filter(|n| Self::RANGE.contains(n));
+        if !value.contains(':') {
+            return count(value).map(|count| Self([count; 7]));
+        }
+        let mut counts = [0; 7];
+        for entry in value.split(',') {
+            let (mode, number) = entry.split_once(':')?;
+            let bits = cli::parse_bits(mode.trim()).ok()?;
+            counts[mode_index(&bits) as usize] = count(number)?;
+        }
+        Some(Self(counts))
+    }
+
+    /// Numbers to choose from in a mode
+    pub fn of(self, bits: &Bits) -> usize {
+        match self.0[mode_index(bits) as usize] {
+            0 => bits.suggestion_count(),
+            count => usize::from(count),
+        }
+    }
+}
+
+/// Position of a mode in the per mode settings
+const fn mode_index(bits: &Bits) -> u8 {
+    match bits {
+        Bits::Four => 0,
+        Bits::FourShift4 => 1,
+        Bits::FourShift8 => 2,
+        Bits::FourShift12 => 3,
+        Bits::Eight => 4,
+        Bits::Twelve => 5,
+        Bits::Sixteen => 6,
+    }
+}
+
+fn config_flag(config: &Config, key: &str) -> bool {
+    config
+        .get(key)
+        .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "yes" | "on" | "1"))
+}
+
+thread_local! {
+    static CURRENT: Cell<Settings> = Cell::new(Settings::default());
+    /// Not Copy like the rest of the settings, so kept apart
+    static VALUE_FILTER: RefCell<ValueFilter> = RefCell::new(ValueFilter::default());
+    static LEADERBOARD: RefCell<Option<Endpoint>> = const { RefCell::new(None) };
+}
+
+pub fn get() -> Settings {
+    CURRENT.with(Cell::get)
+}
+
+pub fn set(settings: Settings) {
+    CURRENT.with(|current| current.set(settings));
+}
+
+pub fn value_filter() -> ValueFilter {
+    VALUE_FILTER.with(|filter| filter.borrow().clone())
+}
+
+pub fn set_value_filter(filter: ValueFilter) {
+    VALUE_FILTER.with(|current| *current.borrow_mut() = filter);
+}
+
+/// Where daily challenge scores are sent, None if they aren't
+pub fn leaderboard() -> Option<Endpoint> {
+    LEADERBOARD.with(|endpoint| endpoint.borrow().clone())
+}
+
+pub fn set_leaderboard(endpoint: Option<Endpoint>) {
+    LEADERBOARD.with(|current| *current.borrow_mut() = endpoint);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_sets_parse_bits_names_or_all_and_none() {
+        let Some(modes) = ModeSet::parse("4, 4x16,8") else { panic!("valid names") };
+        assert!(modes.contains(&Bits::FourShift4) && modes.contains(&Bits::Eight));
+        assert!(!modes.contains(&Bits::Sixteen));
+        assert_eq!(ModeSet::parse("OFF"), Some(ModeSet(0)));
+        assert_eq!(ModeSet::parse("on"), Some(ModeSet::default()));
+        assert_eq!(ModeSet::parse("4, 7"), None);
+    }
+
+    #[test]
+    fn choice_counts_apply_to_all_modes_or_the_ones_named() {
+        let Some(counts) = ChoiceCounts::parse("4: 2, 16:5") else { panic!("valid counts") };
+        assert_eq!(counts.of(&Bits::Four), 2);
+        assert_eq!(counts.of(&Bits::Sixteen), 5);
+        assert_eq!(counts.of(&Bits::Eight), Bits::Eight.suggestion_count());
+        assert_eq!(ChoiceCounts::parse(" 6").map(|counts| counts.of(&Bits::Twelve)), Some(6));
+        assert_eq!(ChoiceCounts::parse("1"), None);
+        assert_eq!(ChoiceCounts::parse("8: 7"), None);
+    }
+}
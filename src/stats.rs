@@ -0,0 +1,260 @@
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::storage;
+use crate::theme;
+use crate::utils::BrailleChart;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Widget};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Answers given on one day
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct DayTally {
+    pub answered: u32,
+    pub correct: u32,
+    /// Answered with the cheat sheet open
+    pub cheated: u32,
+}
+
+impl DayTally {
+    pub fn accuracy(self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            f64::from(self.correct) / f64::from(self.answered)
+        }
+    }
+}
+
+/// Answers per day across all modes, and the answer times of the latest run
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct Stats {
+    /// Keyed by days since the Unix epoch, in UTC like the log files
+    days: BTreeMap<u64, DayTally>,
+    last_run: Vec<f64>,
+}
+
+/// Today as days since the Unix epoch
+pub fn today() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs() / 86_400)
+}
+
+impl Stats {
+    pub const FILE: &'static str = "binbreak_stats.txt";
+
+    /// Load saved stats; lines are `day.<day>=<answered>/<correct>/<cheated>` and
+    /// `run=<seconds>,<seconds>,...`
+    pub fn load() -> Self {
+        storage::active()
+            .read(Self::FILE)
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut stats = Self::default();
+        for line in contents.lines() {
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            if name.trim() == "run" {
+                stats.last_run = value.split(',').filter_map(|t| t.trim().parse().ok()).collect();
+            } else if let Some(day) = name.trim().strip_prefix("day.").and_then(|d| d.parse().ok())
+            {
+                // Files from before the cheat sheet have no third count
+                let mut counts = value.split('/').map(|count| count.trim().parse());
+                if let (Some(Ok(answered)), Some(Ok(correct))) = (counts.next(), counts.next()) {
+                    let cheated = counts.next().and_then(Result::ok).unwrap_or(0);
+                    stats.days.insert(day, DayTally { answered, correct, cheated });
+                }
+            }
+        }
+        stats
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::active().write(Self::FILE, &self.serialize())
+    }
+
+    fn serialize(&self) -> String {
+        let mut data = String::new();
+        for (day, tally) in &self.days {
+            let _ =
+                writeln!(data, "day.{day}={}/{}/{}", tally.answered, tally.correct, tally.cheated);
+        }
+        let times: Vec<String> = self.last_run.iter().map(|t| format!("{t:.2}")).collect();
+        let _ = writeln!(data, "run={}", times.join(","));
+        data
+    }
+
+    /// Count an answer towards `day`, `cheated` if the cheat sheet was open for it;
+    /// `run_times` are the answer times of the run so far
+    pub fn record(&mut self, day: u64, correct: bool, cheated: bool, run_times: &[f64]) {
+        let tally = self.days.entry(day).or_default();
+        tally.answered += 1;
+        tally.correct += u32::from(correct);
+        tally.cheated += u32::from(cheated);
+        self.last_run = run_times.to_vec();
+    }
+
+    /// The latest `count` days something was answered on, oldest first
+    pub fn recent_days(&self, count: usize) -> Vec<(u64, DayTally)> {
+        let mut days: Vec<_> = self.days.iter().rev().take(count).map(|(&d, &t)| (d, t)).collect();
+        days.reverse();
+        days
+    }
+}
+
+/// Labels and upper bounds of the answer time buckets in the end of run histogram
+pub const TIME_BUCKETS: [(&str, f64); 4] =
+    [("<1s", 1.0), ("1-2s", 2.0), ("2-4s", 4.0), (">4s", f64::INFINITY)];
+
+/// How many of `times` fall into each of the `TIME_BUCKETS`
+pub fn time_histogram(times: &[f64]) -> [u64; 4] {
+    let mut counts = [0; 4];
+    for &time in times {
+        if let Some(bucket) = TIME_BUCKETS.iter().position(|&(_, bound)| time < bound) {
+            counts[bucket] += 1;
+        }
+    }
+    counts
+}
+
+/// "today", "yesterday" or "<n>d ago"
+fn days_ago(day: u64, today: u64) -> String {
+    match today.saturating_sub(day) {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        n => format!("{n}d ago"),
+    }
+}
+
+/// Charts of the answer times in the latest run and the accuracy on recent days
+pub struct StatsScreen {
+    stats: Stats,
+    today: u64,
+    exit_intended: bool,
+}
+
+impl StatsScreen {
+    const DAYS: usize = 14;
+    const WIDTH: u16 = 60;
+    const CHART_HEIGHT: u16 = 7;
+
+    pub fn new() -> Self {
+        Self { stats: Stats::load(), today: today(), exit_intended: false }
+    }
+
+    /// A title line above a chart, or a note that there is nothing to plot yet
+    fn render_section(title: &str, chart: Option<BrailleChart>, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let [title_row, chart_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        Line::styled(title, Style::default().fg(theme.text).bold()).render(title_row, buf);
+        match chart {
+            Some(chart) => chart.render(chart_area, buf),
+            None => Line::styled("nothing to show yet", Style::default().fg(theme.muted))
+                .render(chart_area, buf),
+        }
+    }
+}
+
+impl WidgetRef for StatsScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let full = Size::new(Self::WIDTH, 2 * (Self::CHART_HEIGHT + 1) + 3);
+        let size = Size::new(full.width.min(area.width), full.height.min(area.height));
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        let block = Block::bordered().title(" Stats ").title_alignment(Center).fg(theme.muted);
+        let inner = block.inner(frame);
+        block.render(frame, buf);
+        let [times_area, _, accuracy_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1), Constraint::Fill(1)])
+                .areas(inner.inner(Margin::new(1, 0)));
+
+        let times = &self.stats.last_run;
+        let last = times.len().to_string();
+        let times_chart = (!times.is_empty())
+            .then(|| BrailleChart::new(times).unit("s").x_labels("1", &last).color(theme.accent));
+        Self::render_section("Answer time, latest run", times_chart, times_area, buf);
+
+        let days = self.stats.recent_days(Self::DAYS);
+        let accuracy: Vec<f64> = days.iter().map(|(_, tally)| tally.accuracy() * 100.0).collect();
+        let labels = days.first().zip(days.last()).map(|((first, _), (last, _))| {
+            (days_ago(*first, self.today), days_ago(*last, self.today))
+        });
+        let accuracy_chart = labels.as_ref().map(|(first, last)| {
+            BrailleChart::new(&accuracy)
+                .max(100.0)
+                .unit("%")
+                .x_labels(first, last)
+                .color(theme.correct)
+        });
+        let cheated: u32 = days.iter().map(|(_, tally)| tally.cheated).sum();
+        let title = match cheated {
+            0 => "Accuracy, recent days".to_string(),
+            1 => "Accuracy, recent days, 1 answer with the cheat sheet".to_string(),
+            n => format!("Accuracy, recent days, {n} answers with the cheat sheet"),
+        };
+        Self::render_section(&title, accuracy_chart, accuracy_area, buf);
+    }
+}
+
+impl MainScreenWidget for StatsScreen {
+    fn run(&mut self, _dt: f64) {}
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if keybinds::is_exit(input) || keybinds::is_select(input) {
+            self.exit_intended = true;
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    /// Charts of three rows: two of dots and one of labels
+    fn min_size(&self) -> Size {
+        Size::new(34, 2 * 4 + 3)
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        vec![KeyHint::new(&[keybinds::EXIT], "back")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_tally_days_and_round_trip() {
+        let mut stats = Stats::default();
+        stats.record(20_000, true, false, &[1.5]);
+        stats.record(20_000, false, false, &[1.5, 3.25]);
+        stats.record(20_002, true, true, &[0.5]);
+        assert_eq!(
+            stats.recent_days(1),
+            [(20_002, DayTally { answered: 1, correct: 1, cheated: 1 })]
+        );
+        assert!((stats.recent_days(2)[0].1.accuracy() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(Stats::parse(&stats.serialize()), stats);
+        assert_eq!(
+            Stats::parse("day.3=4/2").days[&3],
+            DayTally { answered: 4, correct: 2, cheated: 0 }
+        );
+        assert_eq!(days_ago(20_000, 20_002), "2d ago");
+        assert_eq!(time_histogram(&[0.4, 1.0, 1.9, 2.5, 4.0, 9.0]), [1, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::settings;
+use std::fs;
+use std::io;
+
+/// Where profile data (high scores, mastery, ...) is read from and written to
+pub trait Storage {
+    /// Contents of a named record, None if it doesn't exist or can't be read
+    fn read(&self, name: &str) -> Option<String>;
+    fn write(&self, name: &str, contents: &str) -> io::Result<()>;
+}
+
+/// Plain text files relative to the working directory
+pub struct FileStorage;
+
+impl Storage for FileStorage {
+    fn read(&self, name: &str) -> Option<String> {
+        fs::read_to_string(name).ok()
+    }
+
+    fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        fs::write(name, contents)
+            .inspect_err(|err| tracing::warn!(file = name, "saving failed: {err}"))
+    }
+}
+
+/// Guest mode: nothing is read and every write is silently dropped
+pub struct NoopStorage;
+
+impl Storage for NoopStorage {
+    fn read(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    fn write(&self, _name: &str, _contents: &str) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The storage backend for the current settings
+pub fn active() -> &'static dyn Storage {
+    if settings::get().guest {
+        &NoopStorage
+    } else {
+        &FileStorage
+    }
+}
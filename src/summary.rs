@@ -0,0 +1,273 @@
+//! What a run comes down to, shown once it is over: score and records, accuracy and
+//! answer times, and the quickest and slowest question.
+
+use crate::keybinds;
+
+use crate::stats;
+use crate::theme;
+use crate::utils::{Gradient, GradientText, Typewriter};
+use binbreak::{Question, Run};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Paragraph, Widget};
+
+/// One answered question of a run
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub question: Question,
+    /// The number picked, None if the time ran out or the question was skipped
+    pub guess: Option<i32>,
+    pub secs: f64,
+}
+
+impl Answer {
+    pub fn is_correct(&self) -> bool {
+        self.guess.is_some_and(|guess| self.question.is_correct(guess))
+    }
+}
+
+pub struct RunSummary {
+    score: u32,
+    prev_high_score: u32,
+    new_high_score: bool,
+    rounds: u32,
+    max_streak: u32,
+    lives_lost: bool,
+    correct: usize,
+    answered: usize,
+    average_secs: f64,
+    /// Quickest correct answer
+    fastest: Option<Answer>,
+    /// Longest time taken on any question
+    slowest: Option<Answer>,
+    /// Answers per `stats::TIME_BUCKETS`
+    histogram: [u64; 4],
+}
+
+impl RunSummary {
+    const BAR_WIDTH: u16 = 4;
+    const BAR_GAP: u16 = 1;
+    const HISTOGRAM_WIDTH: u16 = Self::BAR_WIDTH * 4 + Self::BAR_GAP * 3;
+
+    pub fn new(run: &Run, answers: &[Answer], prev_high_score: u32, new_high_score: bool) -> Self {
+        let times: Vec<f64> = answers.iter().map(|answer| answer.secs).collect();
+        let by_time = |a: &&Answer, b: &&Answer| a.secs.total_cmp(&b.secs);
+        #[allow(clippy::cast_precision_loss)]
+        let average_secs = if times.is_empty() {
+            0.0
+        } else {
+            times.iter().sum::<f64>() / times.len() as f64
+        };
+        Self {
+            score: run.score,
+            prev_high_score,
+            new_high_score,
+            rounds: run.rounds,
+            max_streak: run.max_streak,
+            lives_lost: run.lives == 0,
+            correct: answers.iter().filter(|answer| answer.is_correct()).count(),
+            answered: answers.len(),
+            average_secs,
+            fastest: answers.iter().filter(|answer| answer.is_correct()).min_by(by_time).cloned(),
+            slowest: answers.iter().max_by(by_time).cloned(),
+            histogram: stats::time_histogram(&times),
+        }
+    }
+
+    pub const fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// What runs are compared by, labeled, e.g. side by side after a hotseat match
+    pub fn figures(&self) -> [(&'static str, String); 5] {
+        [
+            ("Score", self.score.to_string()),
+            ("Rounds", self.rounds.to_string()),
+            ("Max streak", self.max_streak.to_string()),
+            ("Accuracy", format!("{:.0}%", self.accuracy() * 100.0)),
+            ("Average time", format!("{:.1}s", self.average_secs)),
+        ]
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn accuracy(&self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            self.correct as f64 / self.answered as f64
+        }
+    }
+
+    /// Score, records, accuracy and pace, left of the histogram
+    fn stat_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let line = |text: String, color| Line::from(Span::styled(text, Style::default().fg(color)));
+        vec![
+            line(format!("Final Score: {}", self.score), theme.score),
+            line(format!("Previous High: {}", self.prev_high_score), theme.accent),
+            line(format!("Rounds Played: {}", self.rounds), theme.rounds),
+            line(format!("Max Streak: {}", self.max_streak), theme.streak),
+            line(
+                format!(
+                    "Accuracy: {:.0}% ({}/{})",
+                    self.accuracy() * 100.0,
+                    self.correct,
+                    self.answered
+                ),
+                theme.correct,
+            ),
+            line(format!("Average Time: {:.1}s", self.average_secs), theme.text),
+        ]
+    }
+
+    /// The quickest and the slowest question, how the run ended and the keys to go on
+    fn closing_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let question = |label: &str, answer: &Answer| {
+            let outcome = if answer.is_correct() { "" } else { ", missed" };
+            Line::from(vec![
+                Span::styled(format!("{label}: "), Style::default().fg(theme.muted)),
+                Span::styled(
+                    format!(
+                        "{} = {} in {:.1}s{outcome}",
+                        answer.question.binary_string(),
+                        answer.question.answer,
+                        answer.secs
+                    ),
+                    Style::default().fg(theme.text),
+                ),
+            ])
+        };
+        let mut lines: Vec<Line> = [("Fastest", &self.fastest), ("Slowest", &self.slowest)]
+            .into_iter()
+            .filter_map(|(label, answer)| answer.as_ref().map(|answer| question(label, answer)))
+            .collect();
+        if self.lives_lost {
+            lines.push(Line::styled(
+                "You lost all your lives.",
+                Style::default().fg(theme.incorrect),
+            ));
+        }
+        lines.push(Line::styled(
+            format!(
+                "{} or {} to play again, {} to change mode",
+                keybinds::key_label(keybinds::SELECT),
+                keybinds::key_label(keybinds::RESTART).to_uppercase(),
+                keybinds::key_label(keybinds::EXIT),
+            ),
+            Style::default().fg(theme.accent),
+        ));
+        lines
+    }
+
+    /// Rows needed below a banner: badge, stats, a gap and the closing lines
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn height(&self) -> u16 {
+        u16::from(self.new_high_score)
+            + self.stat_lines().len() as u16
+            + 1
+            + self.closing_lines().len() as u16
+    }
+
+    /// Draw the summary, typed out by `typewriter` if there is one
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn render(&self, typewriter: Option<&Typewriter>, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let stat_lines = self.stat_lines();
+        let stat_count = stat_lines.len();
+        // Measured before typing, so the columns don't move while the lines grow
+        let stats_width = stat_lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let mut lines = stat_lines;
+        lines.extend(self.closing_lines());
+        if let Some(typewriter) = typewriter {
+            lines = typewriter.reveal(lines);
+        }
+        let closing = lines.split_off(stat_count);
+
+        let [area] =
+            Layout::vertical([Constraint::Length(self.height())]).flex(Flex::Center).areas(area);
+        let [badge_row, stats_area, _, closing_area] = Layout::vertical([
+            Constraint::Length(u16::from(self.new_high_score)),
+            Constraint::Length(stat_count as u16),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        if self.new_high_score {
+            Line::from(
+                GradientText::new(
+                    "NEW HIGH SCORE!",
+                    Gradient::horizontal(&theme.highlight_stops()),
+                )
+                .style(Style::default().bold())
+                .spans(),
+            )
+            .centered()
+            .render(badge_row, buf);
+        }
+
+        // Stats and the histogram side by side, as one centered block
+        let [stats_column, _, histogram_column] = Layout::horizontal([
+            Constraint::Length(stats_width),
+            Constraint::Length(3),
+            Constraint::Length(Self::HISTOGRAM_WIDTH),
+        ])
+        .flex(Flex::Center)
+        .areas(stats_area);
+        Paragraph::new(lines).render(stats_column, buf);
+        let typing = typewriter.is_some_and(Typewriter::is_typing);
+        if self.answered > 0 && !typing {
+            self.render_histogram(histogram_column, buf);
+        }
+        Paragraph::new(closing).alignment(Center).render(closing_area, buf);
+    }
+
+    /// One bar per answer time bucket, labeled with the bucket and showing its count
+    fn render_histogram(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let bars: Vec<Bar> = stats::TIME_BUCKETS
+            .iter()
+            .zip(self.histogram)
+            .map(|(&(label, _), count)| Bar::default().value(count).label(Line::from(label)))
+            .collect();
+        BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(Self::BAR_WIDTH)
+            .bar_gap(Self::BAR_GAP)
+            .bar_style(Style::default().fg(theme.accent))
+            .value_style(Style::default().fg(theme.text).bg(theme.accent).bold())
+            .label_style(Style::default().fg(theme.muted))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binbreak::Bits;
+
+    #[test]
+    fn summaries_pick_the_fastest_correct_and_the_slowest_answer() {
+        let question = |pattern: u32| Question {
+            bits: Bits::Four,
+            pattern,
+            choices: vec![pattern as i32, 0],
+            answer: pattern as i32,
+        };
+        let answers = [
+            Answer { question: question(3), guess: Some(3), secs: 1.5 },
+            Answer { question: question(5), guess: Some(0), secs: 0.5 },
+            Answer { question: question(9), guess: Some(9), secs: 0.8 },
+            Answer { question: question(12), guess: None, secs: 6.0 },
+        ];
+        let summary = RunSummary::new(&Run::new(3), &answers, 0, false);
+        assert_eq!((summary.correct, summary.answered), (2, 4));
+        assert!((summary.average_secs - 2.2).abs() < 1e-9);
+        assert_eq!(summary.fastest.map(|answer| answer.question.pattern), Some(9));
+        assert_eq!(summary.slowest.map(|answer| answer.question.pattern), Some(12));
+        assert_eq!(summary.histogram, [2, 1, 0, 1]);
+    }
+}
@@ -0,0 +1,130 @@
+//! Detects whether the terminal has a light or dark background, so the
+//! default theme stays readable on both.
+
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum Background {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// How long to wait for the terminal to answer before assuming it never will
+#[cfg(unix)]
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Ask the terminal for its background color (OSC 11), falling back to the
+/// `COLORFGBG` variable some terminals export. None if neither is available.
+pub fn detect() -> Option<Background> {
+    query_osc11().or_else(|| std::env::var("COLORFGBG").ok().and_then(|v| parse_colorfgbg(&v)))
+}
+
+#[cfg(unix)]
+fn query_osc11() -> Option<Background> {
+    use crossterm::terminal;
+    use std::io::{IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    // Read straight from the terminal rather than through stdin's buffer, which would
+    // keep whatever comes after the reply from the app's input
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    terminal::enable_raw_mode().ok()?;
+
+    // Follow the background query with a device attributes query: every terminal
+    // answers that one, so its reply marks the end even when OSC 11 is unsupported
+    let mut stdout = std::io::stdout();
+    let _ = write!(stdout, "\x1b]11;?\x1b\\\x1b[c");
+    let _ = stdout.flush();
+    let reply = read_reply(&tty, std::time::Instant::now() + QUERY_TIMEOUT);
+    let _ = terminal::disable_raw_mode();
+
+    parse_osc11_reply(&String::from_utf8_lossy(&reply?))
+}
+
+/// The terminal's answer up to the end of the device attributes reply, read a byte at a
+/// time so nothing typed after it is taken. None if it doesn't come by `deadline`.
+#[cfg(unix)]
+fn read_reply(tty: &std::fs::File, deadline: std::time::Instant) -> Option<Vec<u8>> {
+    use rustix::event::{PollFd, PollFlags, Timespec, poll};
+    use std::io::Read;
+
+    let mut reply = Vec::new();
+    loop {
+        let remaining = deadline.checked_duration_since(std::time::Instant::now())?;
+        let timeout = Timespec::try_from(remaining).ok()?;
+        let mut fds = [PollFd::new(tty, PollFlags::IN)];
+        if poll(&mut fds, Some(&timeout)).ok()? == 0 {
+            return None;
+        }
+        let mut byte = [0];
+        if (&*tty).read(&mut byte).ok()? == 0 {
+            return None;
+        }
+        reply.push(byte[0]);
+        if byte[0] == b'c' && reply.windows(3).any(|w| w == b"\x1b[?") {
+            return Some(reply);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+const fn query_osc11() -> Option<Background> {
+    None
+}
+
+/// Parse a reply like `ESC ]11;rgb:ffff/ffff/ffff ESC \`; components have 1 to 4 hex digits
+fn parse_osc11_reply(reply: &str) -> Option<Background> {
+    let start = reply.find("]11;rgb:")? + "]11;rgb:".len();
+    let body = &reply[start..];
+    let body = &body[..body.find(['\x1b', '\x07']).unwrap_or(body.len())];
+
+    let mut channels = [0.0; 3];
+    let mut parts = body.split('/');
+    for channel in &mut channels {
+        let hex = parts.next()?;
+        if hex.is_empty() || hex.len() > 4 {
+            return None;
+        }
+        let max = (1_u32 << (4 * hex.len())) - 1;
+        *channel = f64::from(u32::from_str_radix(hex, 16).ok()?) / f64::from(max);
+    }
+
+    let [r, g, b] = channels;
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// `COLORFGBG` is `fg;bg` (sometimes `fg;default;bg`) with ANSI color indices
+fn parse_colorfgbg(value: &str) -> Option<Background> {
+    let bg = value.rsplit(';').next()?.trim().parse::<u8>().ok()?;
+    Some(if matches!(bg, 7 | 15) {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_osc11_replies() {
+        assert_eq!(parse_osc11_reply("\x1b]11;rgb:ffff/ffff/ffff\x1b\\"), Some(Background::Light));
+        assert_eq!(parse_osc11_reply("\x1b]11;rgb:1e/1e/2e\x07\x1b[?62c"), Some(Background::Dark));
+        assert_eq!(parse_osc11_reply("\x1b[?1;2c"), None);
+        assert_eq!(parse_osc11_reply("\x1b]11;rgb:fffff/0/0\x1b\\"), None);
+    }
+
+    #[test]
+    fn parses_colorfgbg() {
+        assert_eq!(parse_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("15;default;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("garbage"), None);
+    }
+}
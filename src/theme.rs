@@ -0,0 +1,577 @@
+use crate::anim_dsl::{self, DEFAULT_TITLE_CHARS, DEFAULT_TITLE_COLORS};
+use crate::color;
+use crate::config::Config;
+use crate::settings;
+use crate::terminal_bg::Background;
+use binbreak::Bits;
+use ratatui::style::Color;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// Built-in color themes selectable from the start menu
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ThemePreset {
+    Default,
+    Gruvbox,
+    Dracula,
+    Solarized,
+    Light,
+    Plain,
+}
+
+impl ThemePreset {
+    pub const ALL: [Self; 6] =
+        [Self::Default, Self::Gruvbox, Self::Dracula, Self::Solarized, Self::Light, Self::Plain];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Default => "DEFAULT",
+            Self::Gruvbox => "GRUVBOX",
+            Self::Dracula => "DRACULA",
+            Self::Solarized => "SOLARIZED",
+            Self::Light => "LIGHT",
+            Self::Plain => "PLAIN",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.label().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The preset used when the config doesn't pick one
+    pub const fn default_for(background: Background) -> Self {
+        match background {
+            Background::Dark => Self::Default,
+            Background::Light => Self::Light,
+        }
+    }
+
+    pub const fn theme(self) -> Theme {
+        match self {
+            Self::Default => DEFAULT,
+            Self::Gruvbox => GRUVBOX,
+            Self::Dracula => DRACULA,
+            Self::Solarized => SOLARIZED,
+            Self::Light => LIGHT,
+            Self::Plain => PLAIN,
+        }
+    }
+}
+
+/// Every color used by the rendering code. Widgets look colors up here
+/// instead of hard-coding them, so switching presets restyles the whole app.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Theme {
+    pub name: Cow<'static, str>,
+    /// No colors at all: widgets convey state with markers and modifiers instead
+    pub monochrome: bool,
+    /// One color per difficulty, ordered from easiest to hardest
+    pub mode_colors: [Color; 7],
+    pub menu_selected_bg: Color,
+    pub title_dim: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub hotkey: Color,
+    pub selection: Color,
+    pub correct: Color,
+    pub correct_highlight: Color,
+    pub incorrect: Color,
+    pub timeout: Color,
+    pub timer_high: Color,
+    pub timer_mid: Color,
+    pub timer_low: Color,
+    pub score: Color,
+    pub streak: Color,
+    pub max_streak: Color,
+    pub rounds: Color,
+    pub lives: Color,
+    pub accent: Color,
+    /// Color spec for the title animation, see [`crate::anim_dsl`]
+    pub title_colors: Cow<'static, str>,
+    /// Character spec for the title animation, see [`crate::anim_dsl`]
+    pub title_chars: Cow<'static, str>,
+}
+
+impl Theme {
+    /// Stops of the gradient on celebrations: the countdown and new high scores
+    pub const fn highlight_stops(&self) -> [Color; 2] {
+        [self.accent, self.correct_highlight]
+    }
+
+    pub const fn mode_color(&self, bits: &Bits) -> Color {
+        let index = match bits {
+            Bits::Four => 0,
+            Bits::FourShift4 => 1,
+            Bits::FourShift8 => 2,
+            Bits::FourShift12 => 3,
+            Bits::Eight => 4,
+            Bits::Twelve => 5,
+            Bits::Sixteen => 6,
+        };
+        self.mode_colors[index]
+    }
+
+    /// Override a single color by its config key (e.g. `correct`, `mode3`).
+    /// Returns false if the key doesn't name a themable color.
+    pub fn set_color(&mut self, key: &str, color: Color) -> bool {
+        let slot = match key {
+            "menu_selected_bg" | "menu_highlight" => &mut self.menu_selected_bg,
+            "title_dim" => &mut self.title_dim,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "hotkey" => &mut self.hotkey,
+            "selection" => &mut self.selection,
+            "correct" => &mut self.correct,
+            "correct_highlight" => &mut self.correct_highlight,
+            "incorrect" => &mut self.incorrect,
+            "timeout" => &mut self.timeout,
+            "timer_high" => &mut self.timer_high,
+            "timer_mid" => &mut self.timer_mid,
+            "timer_low" => &mut self.timer_low,
+            "score" => &mut self.score,
+            "streak" => &mut self.streak,
+            "max_streak" => &mut self.max_streak,
+            "rounds" => &mut self.rounds,
+            "lives" => &mut self.lives,
+            "accent" => &mut self.accent,
+            _ => match key.strip_prefix("mode").and_then(|n| n.parse::<usize>().ok()) {
+                Some(n @ 1..=7) => &mut self.mode_colors[n - 1],
+                _ => return false,
+            },
+        };
+        *slot = color;
+        true
+    }
+
+    /// Replace every color with the result of `f`, e.g. to fit the terminal's color depth
+    pub fn map_colors(&mut self, f: impl Fn(Color) -> Color) {
+        let slots = [
+            &mut self.menu_selected_bg,
+            &mut self.title_dim,
+            &mut self.text,
+            &mut self.muted,
+            &mut self.hotkey,
+            &mut self.selection,
+            &mut self.correct,
+            &mut self.correct_highlight,
+            &mut self.incorrect,
+            &mut self.timeout,
+            &mut self.timer_high,
+            &mut self.timer_mid,
+            &mut self.timer_low,
+            &mut self.score,
+            &mut self.streak,
+            &mut self.max_streak,
+            &mut self.rounds,
+            &mut self.lives,
+            &mut self.accent,
+        ];
+        for slot in slots.into_iter().chain(self.mode_colors.iter_mut()) {
+            *slot = f(*slot);
+        }
+    }
+
+    /// Pick the timer color for the given remaining-time ratio
+    pub const fn timer_color(&self, ratio: f64) -> Color {
+        if ratio > 0.6 {
+            self.timer_high
+        } else if ratio > 0.3 {
+            self.timer_mid
+        } else {
+            self.timer_low
+        }
+    }
+}
+
+const DEFAULT: Theme = Theme {
+    name: Cow::Borrowed("DEFAULT"),
+    monochrome: false,
+    // Color scheme: progression from easy (green/cyan) to hard (yellow/red)
+    mode_colors: [
+        Color::Rgb(100, 255, 100), // green
+        Color::Rgb(100, 255, 180), // cyan
+        Color::Rgb(100, 220, 255), // light blue
+        Color::Rgb(100, 180, 255), // blue
+        Color::Rgb(150, 120, 255), // royal blue
+        Color::Rgb(200, 100, 255), // purple
+        Color::Rgb(255, 80, 150),  // pink
+    ],
+    menu_selected_bg: Color::Rgb(40, 40, 40),
+    title_dim: Color::DarkGray,
+    text: Color::White,
+    muted: Color::DarkGray,
+    hotkey: Color::LightCyan,
+    selection: Color::LightCyan,
+    correct: Color::Green,
+    correct_highlight: Color::LightGreen,
+    incorrect: Color::Red,
+    timeout: Color::Yellow,
+    timer_high: Color::Green,
+    timer_mid: Color::Yellow,
+    timer_low: Color::Red,
+    score: Color::Green,
+    streak: Color::Cyan,
+    max_streak: Color::Blue,
+    rounds: Color::Magenta,
+    lives: Color::Red,
+    accent: Color::Yellow,
+    title_colors: Cow::Borrowed(DEFAULT_TITLE_COLORS),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+const GRUVBOX: Theme = Theme {
+    name: Cow::Borrowed("GRUVBOX"),
+    monochrome: false,
+    mode_colors: [
+        Color::Rgb(184, 187, 38),  // green
+        Color::Rgb(142, 192, 124), // aqua
+        Color::Rgb(131, 165, 152), // blue
+        Color::Rgb(211, 134, 155), // purple
+        Color::Rgb(250, 189, 47),  // yellow
+        Color::Rgb(254, 128, 25),  // orange
+        Color::Rgb(251, 73, 52),   // red
+    ],
+    menu_selected_bg: Color::Rgb(60, 56, 54),
+    title_dim: Color::Rgb(102, 92, 84),
+    text: Color::Rgb(235, 219, 178),
+    muted: Color::Rgb(124, 111, 100),
+    hotkey: Color::Rgb(142, 192, 124),
+    selection: Color::Rgb(250, 189, 47),
+    correct: Color::Rgb(184, 187, 38),
+    correct_highlight: Color::Rgb(184, 187, 38),
+    incorrect: Color::Rgb(251, 73, 52),
+    timeout: Color::Rgb(250, 189, 47),
+    timer_high: Color::Rgb(184, 187, 38),
+    timer_mid: Color::Rgb(250, 189, 47),
+    timer_low: Color::Rgb(251, 73, 52),
+    score: Color::Rgb(184, 187, 38),
+    streak: Color::Rgb(142, 192, 124),
+    max_streak: Color::Rgb(131, 165, 152),
+    rounds: Color::Rgb(211, 134, 155),
+    lives: Color::Rgb(251, 73, 52),
+    accent: Color::Rgb(254, 128, 25),
+    title_colors: Cow::Borrowed("horizontal_sweep(width=12, fg=highlight, bg=dim)"),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+const DRACULA: Theme = Theme {
+    name: Cow::Borrowed("DRACULA"),
+    monochrome: false,
+    mode_colors: [
+        Color::Rgb(80, 250, 123),  // green
+        Color::Rgb(139, 233, 253), // cyan
+        Color::Rgb(189, 147, 249), // purple
+        Color::Rgb(255, 121, 198), // pink
+        Color::Rgb(241, 250, 140), // yellow
+        Color::Rgb(255, 184, 108), // orange
+        Color::Rgb(255, 85, 85),   // red
+    ],
+    menu_selected_bg: Color::Rgb(68, 71, 90),
+    title_dim: Color::Rgb(98, 114, 164),
+    text: Color::Rgb(248, 248, 242),
+    muted: Color::Rgb(98, 114, 164),
+    hotkey: Color::Rgb(139, 233, 253),
+    selection: Color::Rgb(189, 147, 249),
+    correct: Color::Rgb(80, 250, 123),
+    correct_highlight: Color::Rgb(80, 250, 123),
+    incorrect: Color::Rgb(255, 85, 85),
+    timeout: Color::Rgb(241, 250, 140),
+    timer_high: Color::Rgb(80, 250, 123),
+    timer_mid: Color::Rgb(241, 250, 140),
+    timer_low: Color::Rgb(255, 85, 85),
+    score: Color::Rgb(80, 250, 123),
+    streak: Color::Rgb(139, 233, 253),
+    max_streak: Color::Rgb(189, 147, 249),
+    rounds: Color::Rgb(255, 121, 198),
+    lives: Color::Rgb(255, 85, 85),
+    accent: Color::Rgb(255, 184, 108),
+    title_colors: Cow::Borrowed(DEFAULT_TITLE_COLORS),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+const SOLARIZED: Theme = Theme {
+    name: Cow::Borrowed("SOLARIZED"),
+    monochrome: false,
+    mode_colors: [
+        Color::Rgb(133, 153, 0),   // green
+        Color::Rgb(42, 161, 152),  // cyan
+        Color::Rgb(38, 139, 210),  // blue
+        Color::Rgb(108, 113, 196), // violet
+        Color::Rgb(211, 54, 130),  // magenta
+        Color::Rgb(203, 75, 22),   // orange
+        Color::Rgb(220, 50, 47),   // red
+    ],
+    menu_selected_bg: Color::Rgb(7, 54, 66),
+    title_dim: Color::Rgb(88, 110, 117),
+    text: Color::Rgb(147, 161, 161),
+    muted: Color::Rgb(88, 110, 117),
+    hotkey: Color::Rgb(42, 161, 152),
+    selection: Color::Rgb(38, 139, 210),
+    correct: Color::Rgb(133, 153, 0),
+    correct_highlight: Color::Rgb(133, 153, 0),
+    incorrect: Color::Rgb(220, 50, 47),
+    timeout: Color::Rgb(181, 137, 0),
+    timer_high: Color::Rgb(133, 153, 0),
+    timer_mid: Color::Rgb(181, 137, 0),
+    timer_low: Color::Rgb(220, 50, 47),
+    score: Color::Rgb(133, 153, 0),
+    streak: Color::Rgb(42, 161, 152),
+    max_streak: Color::Rgb(38, 139, 210),
+    rounds: Color::Rgb(211, 54, 130),
+    lives: Color::Rgb(220, 50, 47),
+    accent: Color::Rgb(181, 137, 0),
+    title_colors: Cow::Borrowed(DEFAULT_TITLE_COLORS),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+/// Dark colors for terminals with a light background
+const LIGHT: Theme = Theme {
+    name: Cow::Borrowed("LIGHT"),
+    monochrome: false,
+    mode_colors: [
+        Color::Rgb(0, 140, 60),   // green
+        Color::Rgb(0, 135, 125),  // teal
+        Color::Rgb(0, 115, 185),  // light blue
+        Color::Rgb(30, 80, 200),  // blue
+        Color::Rgb(95, 60, 200),  // royal blue
+        Color::Rgb(145, 40, 180), // purple
+        Color::Rgb(200, 30, 100), // pink
+    ],
+    menu_selected_bg: Color::Rgb(225, 225, 225),
+    title_dim: Color::Rgb(175, 175, 175),
+    text: Color::Black,
+    muted: Color::Rgb(120, 120, 120),
+    hotkey: Color::Rgb(0, 110, 160),
+    selection: Color::Rgb(0, 110, 160),
+    correct: Color::Rgb(0, 140, 60),
+    correct_highlight: Color::Rgb(0, 110, 40),
+    incorrect: Color::Rgb(200, 30, 30),
+    timeout: Color::Rgb(175, 115, 0),
+    timer_high: Color::Rgb(0, 140, 60),
+    timer_mid: Color::Rgb(175, 115, 0),
+    timer_low: Color::Rgb(200, 30, 30),
+    score: Color::Rgb(0, 140, 60),
+    streak: Color::Rgb(0, 135, 125),
+    max_streak: Color::Rgb(30, 80, 200),
+    rounds: Color::Rgb(145, 40, 180),
+    lives: Color::Rgb(200, 30, 30),
+    accent: Color::Rgb(200, 100, 0),
+    title_colors: Cow::Borrowed(DEFAULT_TITLE_COLORS),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+/// Only the 16 standard ANSI colors, for terminals without truecolor support
+const PLAIN: Theme = Theme {
+    name: Cow::Borrowed("PLAIN"),
+    monochrome: false,
+    mode_colors: [
+        Color::Green,
+        Color::Cyan,
+        Color::LightBlue,
+        Color::Blue,
+        Color::LightMagenta,
+        Color::Magenta,
+        Color::Red,
+    ],
+    menu_selected_bg: Color::Black,
+    title_dim: Color::DarkGray,
+    text: Color::White,
+    muted: Color::DarkGray,
+    hotkey: Color::Cyan,
+    selection: Color::Cyan,
+    correct: Color::Green,
+    correct_highlight: Color::LightGreen,
+    incorrect: Color::Red,
+    timeout: Color::Yellow,
+    timer_high: Color::Green,
+    timer_mid: Color::Yellow,
+    timer_low: Color::Red,
+    score: Color::Green,
+    streak: Color::Cyan,
+    max_streak: Color::Blue,
+    rounds: Color::Magenta,
+    lives: Color::Red,
+    accent: Color::Yellow,
+    title_colors: Cow::Borrowed(DEFAULT_TITLE_COLORS),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+/// All themes the user can switch between: the built-in presets, plus a
+/// `CUSTOM` theme when the config file overrides any colors.
+pub struct ThemeRegistry {
+    themes: Vec<Theme>,
+}
+
+impl ThemeRegistry {
+    pub fn builtin() -> Self {
+        Self { themes: ThemePreset::ALL.iter().map(|p| p.theme()).collect() }
+    }
+
+    /// Build the registry from config and return it with the theme the config selects.
+    ///
+    /// `theme = <name>` picks the base preset (by default the one matching the
+    /// terminal background), `color.<key> = <color>` overrides
+    /// individual colors and `title.colors` / `title.chars` set the title animation.
+    /// Unknown keys and values that don't parse are ignored with a warning.
+    pub fn from_config(config: &Config) -> (Self, Theme) {
+        let mut registry = Self::builtin();
+        let base = config
+            .get("theme")
+            .and_then(ThemePreset::from_name)
+            .unwrap_or_else(|| ThemePreset::default_for(settings::get().background))
+            .theme();
+
+        let mut custom = base.clone();
+        let mut customized = false;
+        for (key, value) in config.section("color") {
+            match Color::from_str(value) {
+                Ok(color) if custom.set_color(key, color) => customized = true,
+                Ok(_) => tracing::warn!(key, "unknown color key, ignored"),
+                Err(_) => tracing::warn!(key, value, "expected a color name or #rrggbb, ignored"),
+            }
+        }
+        if let Some(spec) = config.get("title.colors") {
+            match anim_dsl::color_fn(spec, 1, 1) {
+                Ok(_) => {
+                    custom.title_colors = Cow::Owned(spec.to_string());
+                    customized = true;
+                },
+                Err(err) => tracing::warn!(spec, "title.colors: {err}, ignored"),
+            }
+        }
+        if let Some(spec) = config.get("title.chars") {
+            match anim_dsl::char_fn(spec, 1, 1) {
+                Ok(_) => {
+                    custom.title_chars = Cow::Owned(spec.to_string());
+                    customized = true;
+                },
+                Err(err) => tracing::warn!(spec, "title.chars: {err}, ignored"),
+            }
+        }
+
+        if !customized {
+            return (registry, base);
+        }
+        custom.name = Cow::Borrowed("CUSTOM");
+        registry.themes.push(custom.clone());
+        (registry, custom)
+    }
+
+    /// The theme after the named one, wrapping around
+    pub fn next_after(&self, name: &str) -> Theme {
+        let index = self.themes.iter().position(|t| t.name == name).map_or(0, |i| i + 1);
+        self.themes[index % self.themes.len()].clone()
+    }
+
+    fn map_colors(&mut self, f: impl Fn(Color) -> Color + Copy) {
+        for theme in &mut self.themes {
+            theme.map_colors(f);
+        }
+    }
+}
+
+/// Every color is the terminal default, used for `NO_COLOR` / `--no-color`
+const MONOCHROME: Theme = Theme {
+    name: Cow::Borrowed("MONOCHROME"),
+    monochrome: true,
+    mode_colors: [Color::Reset; 7],
+    menu_selected_bg: Color::Reset,
+    title_dim: Color::Reset,
+    text: Color::Reset,
+    muted: Color::Reset,
+    hotkey: Color::Reset,
+    selection: Color::Reset,
+    correct: Color::Reset,
+    correct_highlight: Color::Reset,
+    incorrect: Color::Reset,
+    timeout: Color::Reset,
+    timer_high: Color::Reset,
+    timer_mid: Color::Reset,
+    timer_low: Color::Reset,
+    score: Color::Reset,
+    streak: Color::Reset,
+    max_streak: Color::Reset,
+    rounds: Color::Reset,
+    lives: Color::Reset,
+    accent: Color::Reset,
+    title_colors: Cow::Borrowed("solid(fg=reset)"),
+    title_chars: Cow::Borrowed(DEFAULT_TITLE_CHARS),
+};
+
+thread_local! {
+    static CURRENT: RefCell<Rc<Theme>> = RefCell::new(Rc::new(DEFAULT));
+    static REGISTRY: RefCell<ThemeRegistry> = RefCell::new(ThemeRegistry::builtin());
+}
+
+/// The theme all rendering code should consult
+pub fn current() -> Rc<Theme> {
+    CURRENT.with(|theme| Rc::clone(&theme.borrow()))
+}
+
+pub fn set(theme: Theme) {
+    CURRENT.with(|current| *current.borrow_mut() = Rc::new(theme));
+}
+
+/// Switch to the next theme in the registry
+pub fn cycle() {
+    let next = REGISTRY.with(|registry| registry.borrow().next_after(&current().name));
+    set(next);
+}
+
+/// Rebuild the registry from config and activate the theme it selects.
+/// With colors disabled the monochrome theme is the only choice, otherwise
+/// colors are downgraded to what the terminal supports.
+pub fn apply_config(config: &Config) {
+    let (registry, selected) = if settings::get().no_color {
+        (ThemeRegistry { themes: vec![MONOCHROME] }, MONOCHROME)
+    } else {
+        let depth = settings::get().color_depth;
+        let (mut registry, mut selected) = ThemeRegistry::from_config(config);
+        registry.map_colors(|c| color::resolve(c, depth));
+        selected.map_colors(|c| color::resolve(c, depth));
+        (registry, selected)
+    };
+    REGISTRY.with(|r| *r.borrow_mut() = registry);
+    set(selected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_overrides_create_custom_theme() {
+        let config = Config::parse(indoc::indoc! {"
+            # base preset plus overrides
+            theme = dracula
+            color.correct = #00ff00
+            color.mode2 = red
+            color.not_a_color = blue
+            color.timeout = not-a-color
+        "});
+        let (registry, theme) = ThemeRegistry::from_config(&config);
+        assert_eq!(theme.name, "CUSTOM");
+        assert_eq!(theme.correct, Color::Rgb(0, 255, 0));
+        assert_eq!(theme.mode_colors[1], Color::Red);
+        assert_eq!(theme.timeout, ThemePreset::Dracula.theme().timeout);
+        assert_eq!(registry.themes.len(), ThemePreset::ALL.len() + 1);
+        assert_eq!(registry.next_after("CUSTOM").name, "DEFAULT");
+    }
+
+    #[test]
+    fn light_background_defaults_to_light_preset() {
+        settings::set(settings::Settings { background: Background::Light, ..settings::get() });
+        let (_, theme) = ThemeRegistry::from_config(&Config::default());
+        settings::set(settings::Settings { background: Background::Dark, ..settings::get() });
+        assert_eq!(theme, ThemePreset::Light.theme());
+    }
+
+    #[test]
+    fn preset_only_config_selects_preset() {
+        let (registry, theme) = ThemeRegistry::from_config(&Config::parse("theme = Gruvbox"));
+        assert_eq!(theme, ThemePreset::Gruvbox.theme());
+        assert_eq!(registry.next_after("PLAIN").name, "DEFAULT");
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Updates owed after a stall are capped, so a suspended terminal doesn't fast-forward a run
+const MAX_CATCH_UP_STEPS: u32 = 10;
+
+/// How often the screen is redrawn while something moves
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FrameRate(u16);
+
+impl FrameRate {
+    pub const MAX: u16 = 240;
+    /// Enough for the title animation, which only changes every 50 ms
+    pub const IDLE: Self = Self(20);
+
+    /// Frames per second from the command line or config file, 1 to 240
+    pub fn parse(value: &str) -> Option<Self> {
+        value.trim().parse().ok().filter(|fps| (1..=Self::MAX).contains(fps)).map(Self)
+    }
+
+    pub fn frame_duration(self) -> Duration {
+        Duration::from_secs(1) / u32::from(self.0)
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self(30)
+    }
+}
+
+/// Turns ticks of any length into game updates of a fixed length: a slow frame runs
+/// several updates to catch up, a quick one may run none. Game logic then behaves
+/// the same however fast the terminal draws.
+pub struct FixedStep {
+    step: Duration,
+    accumulated: f64,
+}
+
+impl FixedStep {
+    pub const fn new(step: Duration) -> Self {
+        Self { step, accumulated: 0.0 }
+    }
+
+    /// Seconds covered by one update
+    pub const fn step(&self) -> f64 {
+        self.step.as_secs_f64()
+    }
+
+    pub const fn step_duration(&self) -> Duration {
+        self.step
+    }
+
+    /// Bank `dt` seconds and return how many updates are due
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn advance(&mut self, dt: f64) -> u32 {
+        let step = self.step();
+        let max = step * f64::from(MAX_CATCH_UP_STEPS);
+        self.accumulated = (self.accumulated + dt).min(max);
+        let steps = (self.accumulated / step).floor();
+        self.accumulated -= steps * step;
+        steps as u32
+    }
+}
+
+/// Frame timings for the debug overlay
+#[derive(Default)]
+pub struct FrameStats {
+    /// When the frames of the last second were finished
+    recent: VecDeque<Instant>,
+    /// How long the last frame took to draw
+    pub frame_time: Duration,
+    /// How long the last input waited before it was handled
+    pub event_latency: Duration,
+}
+
+impl FrameStats {
+    pub fn frame_drawn(&mut self, started: Instant, finished: Instant) {
+        self.frame_time = finished - started;
+        self.recent.push_back(finished);
+        while self.recent.front().is_some_and(|&frame| finished - frame > Duration::from_secs(1)) {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Frames drawn in the second up to the last one
+    pub fn fps(&self) -> usize {
+        self.recent.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_become_whole_steps_and_stalls_are_capped() {
+        let mut clock = FixedStep::new(Duration::from_millis(10));
+        assert_eq!(clock.advance(0.004), 0);
+        assert_eq!(clock.advance(0.004), 0);
+        assert_eq!(clock.advance(0.004), 1, "the remainder carries over");
+        assert_eq!(clock.advance(0.025), 2);
+        assert_eq!(clock.advance(60.0), MAX_CATCH_UP_STEPS);
+        assert_eq!(clock.advance(0.0), 0);
+    }
+
+    #[test]
+    fn frame_rates_stay_in_range() {
+        assert_eq!(
+            FrameRate::parse(" 60 ").map(FrameRate::frame_duration),
+            Some(Duration::from_secs(1) / 60)
+        );
+        assert_eq!(FrameRate::parse("0"), None);
+        assert_eq!(FrameRate::parse("1000"), None);
+        assert_eq!(FrameRate::parse("fast"), None);
+        assert_eq!(FrameRate::default().frame_duration(), Duration::from_secs(1) / 30);
+    }
+
+    #[test]
+    fn fps_counts_the_frames_of_the_last_second() {
+        let mut stats = FrameStats::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        for ms in (0..1000).step_by(100) {
+            stats.frame_drawn(at(ms), at(ms + 5));
+        }
+        assert_eq!((stats.fps(), stats.frame_time), (10, Duration::from_millis(5)));
+        stats.frame_drawn(at(2000), at(2020));
+        assert_eq!((stats.fps(), stats.frame_time), (1, Duration::from_millis(20)));
+    }
+}
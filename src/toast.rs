@@ -0,0 +1,126 @@
+//! Short notices that slide in at the bottom right corner and go away on their own,
+//! e.g. a new personal best or a reloaded config. Any screen can `show` one; the app
+//! collects them into its `Toasts` and draws them over everything else.
+
+use crate::animation;
+use crate::settings;
+use crate::theme;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+thread_local! {
+    /// Shown since the app last collected them
+    static PENDING: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Queue a notice, it appears with the next frame
+pub fn show(text: impl Into<String>) {
+    PENDING.with(|pending| pending.borrow_mut().push(text.into()));
+}
+
+struct Toast {
+    text: String,
+    /// Animation time it was collected at
+    shown_at: Duration,
+}
+
+/// The notices on screen, newest at the bottom
+#[derive(Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Time a toast stays up
+    const DURATION: Duration = Duration::from_secs(3);
+    /// Time it takes to slide in
+    const SLIDE: Duration = Duration::from_millis(200);
+    /// Older toasts make room when there are more
+    const MAX_VISIBLE: usize = 3;
+
+    /// Take the toasts shown since the last call and drop the ones whose time is up
+    pub fn update(&mut self) {
+        let now = animation::now();
+        for text in PENDING.with(RefCell::take) {
+            tracing::info!(text, "toast");
+            self.queue.push_back(Toast { text, shown_at: now });
+        }
+        self.queue.retain(|toast| now.saturating_sub(toast.shown_at) < Self::DURATION);
+        while self.queue.len() > Self::MAX_VISIBLE {
+            self.queue.pop_front();
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Columns a toast of `width` still has to slide in from the right edge
+    fn slide_offset(toast: &Toast, width: u16) -> u16 {
+        if settings::get().reduce_motion {
+            return 0;
+        }
+        let elapsed = animation::now().saturating_sub(toast.shown_at);
+        let left = 1.0 - (elapsed.as_secs_f32() / Self::SLIDE.as_secs_f32()).min(1.0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let offset = (f32::from(width) * left * left).round() as u16;
+        offset
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let mut bottom = area.bottom();
+        for toast in self.queue.iter().rev() {
+            let line = Line::styled(toast.text.as_str(), Style::default().fg(theme.text));
+            #[allow(clippy::cast_possible_truncation)]
+            let width = (line.width() as u16 + 4).min(area.width);
+            if bottom < area.y + 3 {
+                break;
+            }
+            let x = area.right().saturating_sub(width) + Toasts::slide_offset(toast, width);
+            let toast_area = Rect::new(x, bottom - 3, width, 3).intersection(area);
+            Clear.render(toast_area, buf);
+            Paragraph::new(line)
+                .centered()
+                .block(Block::bordered().border_style(Style::default().fg(theme.accent)))
+                .render(toast_area, buf);
+            bottom -= 3;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_slide_in_and_leave_after_their_time() {
+        let mut toasts = Toasts::default();
+        show("Config reloaded");
+        toasts.update();
+        assert!(toasts.is_active());
+
+        let area = Rect::new(0, 0, 30, 4);
+        let row = |buf: &Buffer| (0..30).map(|x| buf[(x, 2)].symbol()).collect::<String>();
+        let mut buf = Buffer::empty(area);
+        toasts.render(area, &mut buf);
+        assert_eq!(row(&buf).trim(), "", "starts outside the area");
+
+        animation::advance(Toasts::SLIDE);
+        let mut buf = Buffer::empty(area);
+        toasts.render(area, &mut buf);
+        assert_eq!(row(&buf), "           │ Config reloaded │");
+
+        animation::advance(Toasts::DURATION);
+        toasts.update();
+        assert!(!toasts.is_active());
+    }
+}
@@ -0,0 +1,384 @@
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::responsive::LayoutMode;
+use crate::settings;
+use crate::storage;
+use crate::theme;
+use crate::utils::Typewriter;
+use binbreak::Bits;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+/// Scripted steps of the tutorial, shown in order
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Step {
+    Welcome,
+    PlaceValues,
+    Question,
+    Done,
+}
+
+impl Step {
+    const fn next(self) -> Self {
+        match self {
+            Self::Welcome => Self::PlaceValues,
+            Self::PlaceValues => Self::Question,
+            Self::Question | Self::Done => Self::Done,
+        }
+    }
+}
+
+/// Guided first-run introduction: explains place values with an animated
+/// example, then walks through one 4-bit question with hints.
+pub struct TutorialScreen {
+    step: Step,
+    elapsed: f64,
+    selected: usize,
+    wrong_guesses: usize,
+    exit_intended: bool,
+    /// Types out the text of the steps that are only text
+    typewriter: Typewriter,
+}
+
+impl TutorialScreen {
+    const FILE: &'static str = "binbreak_tutorial.txt";
+    /// Place-value walkthrough example: 1011 = 8 + 2 + 1
+    const EXAMPLE: [u8; 4] = [1, 0, 1, 1];
+    /// The practice question: 0110 = 6
+    const QUESTION: [u8; 4] = [0, 1, 1, 0];
+    const SUGGESTIONS: [u32; 3] = [5, 6, 9];
+    const ANSWER: u32 = 6;
+    /// Seconds each bit stays highlighted in the place-value animation
+    const BIT_STEP_SECS: f64 = 0.8;
+    const WIDTH: u16 = 56;
+    const TYPING_CHARS_PER_SEC: f32 = 60.0;
+
+    pub fn new() -> Self {
+        Self {
+            step: Step::Welcome,
+            elapsed: 0.0,
+            selected: 0,
+            wrong_guesses: 0,
+            exit_intended: false,
+            typewriter: Self::typewriter(),
+        }
+    }
+
+    fn typewriter() -> Typewriter {
+        Typewriter::new(Self::TYPING_CHARS_PER_SEC).with_cursor(true)
+    }
+
+    /// Welcome and the closing words are typed out, the steps in between show examples
+    const fn is_typed(&self) -> bool {
+        matches!(self.step, Step::Welcome | Step::Done)
+    }
+
+    /// True until the tutorial was finished or skipped once; guests are never prompted
+    pub fn should_autostart() -> bool {
+        !settings::get().guest && storage::active().read(Self::FILE).is_none()
+    }
+
+    fn mark_seen() {
+        let _ = storage::active().write(Self::FILE, "seen=1\n");
+    }
+
+    /// Move on to the next step and start typing out its text
+    fn advance(&mut self) {
+        self.step = self.step.next();
+        self.elapsed = 0.0;
+        self.typewriter = Self::typewriter();
+    }
+
+    fn answer(&mut self) {
+        if Self::SUGGESTIONS[self.selected] == Self::ANSWER {
+            self.advance();
+        } else {
+            self.wrong_guesses += 1;
+        }
+    }
+
+    /// Number of example bits revealed so far, all of them once the animation finished
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn revealed_bits(&self) -> usize {
+        if settings::get().reduce_motion {
+            return Self::EXAMPLE.len();
+        }
+        ((self.elapsed / Self::BIT_STEP_SECS) as usize + 1).min(Self::EXAMPLE.len())
+    }
+
+    fn bit_spans(bits: &[u8], highlighted: Option<usize>) -> Vec<Span<'static>> {
+        let theme = theme::current();
+        let mode_color = theme.mode_color(&Bits::Four);
+        bits.iter()
+            .enumerate()
+            .map(|(i, bit)| {
+                let style = if highlighted == Some(i) {
+                    Style::default().fg(theme.selection).add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default().fg(mode_color)
+                };
+                Span::styled(format!(" {bit}  "), style.bold())
+            })
+            .collect()
+    }
+
+    fn place_value_line() -> Line<'static> {
+        let muted = theme::current().muted;
+        Line::from(
+            [8, 4, 2, 1]
+                .iter()
+                .map(|v| Span::styled(format!("{v:^4}"), Style::default().fg(muted)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn welcome_lines() -> Vec<Line<'static>> {
+        let theme = theme::current();
+        vec![
+            Line::from(Span::styled(
+                "Welcome to binbreak!",
+                Style::default().fg(theme.accent).bold(),
+            )),
+            Line::from(""),
+            Line::from("Every round shows a binary number."),
+            Line::from("Your job is to pick its decimal value"),
+            Line::from("before the timer runs out."),
+            Line::from(""),
+            Line::from("Let's see how binary numbers work."),
+        ]
+    }
+
+    fn place_value_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let revealed = self.revealed_bits();
+        let highlighted = (revealed < Self::EXAMPLE.len()).then(|| revealed - 1);
+
+        let terms: Vec<String> = Self::EXAMPLE[..revealed]
+            .iter()
+            .zip([8, 4, 2, 1])
+            .map(|(bit, place)| (u32::from(*bit) * place).to_string())
+            .collect();
+        let mut sum_line = terms.join(" + ");
+        if revealed == Self::EXAMPLE.len() {
+            sum_line.push_str(" = 11");
+        }
+
+        vec![
+            Line::from("Each bit is worth twice the bit to its right."),
+            Line::from("Add up the places where the bit is 1:"),
+            Line::from(""),
+            Line::from(Self::bit_spans(&Self::EXAMPLE, highlighted)),
+            Self::place_value_line(),
+            Line::from(""),
+            Line::from(Span::styled(sum_line, Style::default().fg(theme.text).bold())),
+        ]
+    }
+
+    fn question_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let suggestions: Vec<Span> = Self::SUGGESTIONS
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if i == self.selected {
+                    Span::styled(
+                        format!(" > {value} < "),
+                        Style::default().fg(theme.selection).bold(),
+                    )
+                } else {
+                    Span::styled(format!("   {value}   "), Style::default().fg(theme.text))
+                }
+            })
+            .collect();
+
+        // Each wrong guess reveals a bit more of the solution
+        let hint = match self.wrong_guesses {
+            0 => "Hint: which places hold a 1?",
+            1 => "Not quite. The 1s sit at places 4 and 2.",
+            _ => "Almost there: 4 + 2 = ?",
+        };
+        let hint_color = if self.wrong_guesses == 0 {
+            theme.muted
+        } else {
+            theme.incorrect
+        };
+
+        vec![
+            Line::from("Your turn! What is this number in decimal?"),
+            Line::from(""),
+            Line::from(Self::bit_spans(&Self::QUESTION, None)),
+            Self::place_value_line(),
+            Line::from(""),
+            Line::from(suggestions),
+            Line::from(""),
+            Line::from(Span::styled(hint, Style::default().fg(hint_color))),
+        ]
+    }
+
+    fn done_lines() -> Vec<Line<'static>> {
+        let theme = theme::current();
+        vec![
+            Line::from(Span::styled(
+                "Correct: 4 + 2 = 6!",
+                Style::default().fg(theme.correct).bold(),
+            )),
+            Line::from(""),
+            Line::from("That's all there is to it. Bigger modes just"),
+            Line::from("have more places: 16, 32, 64, 128, ..."),
+            Line::from(""),
+            Line::from("Start with the 4 bit mode and work your way up."),
+        ]
+    }
+
+    /// Fixed width for every step, with a cell of padding inside the border
+    fn full_size(&self) -> Size {
+        #[allow(clippy::cast_possible_truncation)]
+        let height = self.content().1.len() as u16 + 4;
+        Size::new(Self::WIDTH, height)
+    }
+
+    /// Box title and text of the current step
+    fn content(&self) -> (&'static str, Vec<Line<'static>>) {
+        match self.step {
+            Step::Welcome => (" Tutorial 1/4 ", Self::welcome_lines()),
+            Step::PlaceValues => (" Tutorial 2/4 ", self.place_value_lines()),
+            Step::Question => (" Tutorial 3/4 ", self.question_lines()),
+            Step::Done => (" Tutorial 4/4 ", Self::done_lines()),
+        }
+    }
+}
+
+impl WidgetRef for TutorialScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let (title, mut lines) = self.content();
+        if self.is_typed() {
+            lines = self.typewriter.reveal(lines);
+        }
+
+        // Compact drops the padding and shrinks the box to the text
+        let (size, padding) = if LayoutMode::for_area(area, self.full_size()).is_compact() {
+            (self.min_size(), 0)
+        } else {
+            (self.full_size(), 1)
+        };
+        let [column] =
+            Layout::horizontal([Constraint::Length(size.width)]).flex(Flex::Center).areas(area);
+        let [frame] =
+            Layout::vertical([Constraint::Length(size.height)]).flex(Flex::Center).areas(column);
+        let block = Block::bordered().title(title).title_alignment(Center).fg(theme.muted);
+        let inner = block.inner(frame).inner(ratatui::layout::Margin::new(padding, padding));
+        block.render(frame, buf);
+        Paragraph::new(lines).alignment(Center).fg(theme.text).render(inner, buf);
+    }
+}
+
+impl MainScreenWidget for TutorialScreen {
+    fn run(&mut self, dt: f64) {
+        self.elapsed += dt;
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_exit(x) => {
+                Self::mark_seen();
+                self.exit_intended = true;
+            },
+            // The first press shows the rest of the text being typed
+            x if keybinds::is_select(x) && self.is_typed() && self.typewriter.is_typing() => {
+                self.typewriter.skip();
+            },
+            x if keybinds::is_select(x) => match self.step {
+                Step::Question => self.answer(),
+                Step::Done => {
+                    Self::mark_seen();
+                    self.exit_intended = true;
+                },
+                Step::Welcome | Step::PlaceValues => self.advance(),
+            },
+            x if self.step == Step::Question && keybinds::is_left(x) => {
+                self.selected = self.selected.saturating_sub(1);
+            },
+            x if self.step == Step::Question && keybinds::is_right(x) => {
+                self.selected = (self.selected + 1).min(Self::SUGGESTIONS.len() - 1);
+            },
+            _ => {},
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    /// Whether the current step animates and needs frequent redraws
+    fn is_animating(&self) -> bool {
+        (self.step == Step::PlaceValues && !settings::get().reduce_motion)
+            || (self.is_typed() && self.typewriter.is_animating())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn min_size(&self) -> Size {
+        let lines = self.content().1;
+        let widest = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        Size::new(widest + 2, lines.len() as u16 + 2)
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        use keybinds::{EXIT, LEFT, RIGHT, SELECT};
+        match self.step {
+            Step::Welcome | Step::PlaceValues => {
+                vec![KeyHint::new(&[SELECT], "next"), KeyHint::new(&[EXIT], "skip")]
+            },
+            Step::Question => vec![
+                KeyHint::new(&[LEFT, RIGHT], "choose"),
+                KeyHint::new(&[SELECT], "answer"),
+                KeyHint::new(&[EXIT], "skip"),
+            ],
+            Step::Done => vec![KeyHint::new(&[SELECT], "to the menu")],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn press(screen: &mut TutorialScreen, code: KeyCode) {
+        screen.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn walks_through_steps_with_hints() {
+        settings::set(settings::Settings { guest: true, ..settings::get() });
+        let mut screen = TutorialScreen::new();
+        let area = Rect::new(0, 0, 80, 24);
+        screen.render_ref(area, &mut Buffer::empty(area));
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!(screen.step, Step::Welcome, "the first press finishes the typing");
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!(screen.step, Step::PlaceValues);
+        assert_eq!(screen.revealed_bits(), 1);
+        screen.run(TutorialScreen::BIT_STEP_SECS * 10.0);
+        assert_eq!(screen.revealed_bits(), 4);
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!(screen.step, Step::Question);
+
+        // 5 is wrong, the hint gets more specific
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!((screen.step, screen.wrong_guesses), (Step::Question, 1));
+        press(&mut screen, KeyCode::Right);
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!(screen.step, Step::Done);
+
+        press(&mut screen, KeyCode::Enter);
+        assert!(screen.is_exit_intended());
+        settings::set(settings::Settings { guest: false, ..settings::get() });
+    }
+}
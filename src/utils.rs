@@ -1,29 +1,89 @@
+use crate::animation;
+use crate::color;
+use crate::keybinds;
+use crate::keybinds::KeyHint;
+use crate::settings;
+use crate::theme;
+use crate::timing::FrameStats;
+use crossterm::event::KeyEvent;
 use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use std::time::{Duration, Instant};
+use ratatui::widgets::{
+    Block, Clear, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+    ScrollbarState, Wrap,
+};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::time::Duration;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Type alias for the color function used in procedural animations
-type ColorFn = Box<dyn Fn(usize, usize, f32, usize, Color) -> Color>;
+pub type ColorFn = Box<dyn Fn(usize, usize, f32, usize, Color) -> Color>;
 
 /// Type alias for the character transformation function
-type CharFn = Box<dyn Fn(usize, usize, f32, usize, char) -> char>;
+pub type CharFn = Box<dyn Fn(usize, usize, f32, usize, char) -> char>;
+
+/// The glyphs of one frame as drawn: character and color, in the order of the art
+type Frame = Vec<(char, Color)>;
+
+/// Columns from one tab stop to the next in art containing tabs
+const TAB_WIDTH: u16 = 4;
+
+/// A visible character of the art and the terminal cell it starts in
+struct Glyph {
+    x: u16,
+    y: u16,
+    ch: char,
+    /// Columns the character covers: 2 for wide characters (CJK, emoji), 1 otherwise
+    width: u16,
+}
+
+/// Place the characters of the art by display column rather than by index, so wide
+/// characters and tabs don't shift the rest of their line. Returns the visible
+/// characters and the width of the widest line.
+fn layout_art(art: &str) -> (Vec<Glyph>, u16) {
+    let mut glyphs = Vec::new();
+    let mut width = 0;
+    for (y, line) in art.lines().enumerate() {
+        let mut x: u16 = 0;
+        for ch in line.chars() {
+            let advance = if ch == '\t' {
+                TAB_WIDTH - x % TAB_WIDTH
+            } else {
+                // Zero width characters (combining marks, variation selectors) have no cell
+                let char_width = ch.width().unwrap_or(0) as u16;
+                if char_width > 0 && ch != ' ' {
+                    glyphs.push(Glyph { x, y: y as u16, ch, width: char_width });
+                }
+                char_width
+            };
+            x += advance;
+        }
+        width = width.max(x);
+    }
+    (glyphs, width)
+}
 
 /// A procedural animation widget that calculates colors on-the-fly
-/// This is much more memory efficient than storing multiple frames
+/// This is much more memory efficient than storing multiple frames, see `precomputed`
+/// for trading memory for speed
 pub struct ProceduralAnimationWidget {
-    art: String,
+    glyphs: Vec<Glyph>,
     width: u16,
     height: u16,
     num_frames: usize,
     frame_duration: Duration,
     pause_at_end: Duration,
-    start_time: Instant,
+    /// Animation time of the start, see `animation::now`
+    start_time: Duration,
     paused: bool,
     paused_progress: f32,
     paused_cycle: usize,
     highlight_color: Color,  // The color for the animated strip
     color_fn: ColorFn,       // (x, y, progress, cycle, highlight_color) -> Color
     char_fn: Option<CharFn>, // (x, y, progress, cycle, original_char) -> char
+    /// Every frame of two cycles, when precomputed
+    frames: Option<Vec<Frame>>,
 }
 
 impl ProceduralAnimationWidget {
@@ -33,24 +93,24 @@ impl ProceduralAnimationWidget {
         frame_duration: Duration,
         color_fn: impl Fn(usize, usize, f32, usize, Color) -> Color + 'static,
     ) -> Self {
-        let art_lines: Vec<&str> = art.lines().collect();
-        let height = art_lines.len() as u16;
-        let width = art_lines.iter().map(|line| line.len()).max().unwrap_or(0) as u16;
+        let height = art.lines().count() as u16;
+        let (glyphs, width) = layout_art(&art);
 
         Self {
-            art,
+            glyphs,
             width,
             height,
             num_frames,
             frame_duration,
             pause_at_end: Duration::ZERO,
-            start_time: Instant::now(),
+            start_time: animation::now(),
             paused: false,
             paused_progress: 0.0,
             paused_cycle: 0,
             highlight_color: Color::LightGreen, // Default color
             color_fn: Box::new(color_fn),
             char_fn: None,
+            frames: None,
         }
     }
 
@@ -67,6 +127,27 @@ impl ProceduralAnimationWidget {
         self
     }
 
+    /// Run the color and char functions for every frame up front, so drawing a frame
+    /// only copies it. Progress then advances frame by frame, and the animation
+    /// repeats every two cycles, as the presets do (`binary_flip` alternates).
+    /// Call last, the frames are made from what was configured before.
+    pub fn precomputed(mut self, precomputed: bool) -> Self {
+        self.frames = precomputed.then(|| self.compute_frames());
+        self
+    }
+
+    /// Frame `n` of a cycle shows progress `n / num_frames`, the last one is the pause
+    fn compute_frames(&self) -> Vec<Frame> {
+        let frames_per_cycle = self.num_frames.max(1);
+        (0..2)
+            .flat_map(|cycle| (0..=frames_per_cycle).map(move |frame| (cycle, frame)))
+            .map(|(cycle, frame)| {
+                let progress = frame as f32 / frames_per_cycle as f32;
+                self.glyphs.iter().map(|glyph| self.cell(glyph, progress, cycle)).collect()
+            })
+            .collect()
+    }
+
     pub fn pause(&mut self) {
         if !self.paused {
             let (progress, cycle) = self.get_animation_progress_and_cycle();
@@ -86,7 +167,7 @@ impl ProceduralAnimationWidget {
                     + self.paused_progress * animation_duration.as_millis() as f32)
                     as u64,
             );
-            self.start_time = Instant::now() - elapsed_at_pause;
+            self.start_time = animation::now().saturating_sub(elapsed_at_pause);
             self.paused = false;
         }
     }
@@ -103,6 +184,11 @@ impl ProceduralAnimationWidget {
         self.paused
     }
 
+    /// Whether the animation is currently moving, i.e. needs frequent redraws
+    pub fn is_animating(&self) -> bool {
+        !self.paused && !settings::get().reduce_motion && !animation::is_paused()
+    }
+
     pub fn get_width(&self) -> u16 {
         self.width
     }
@@ -114,16 +200,27 @@ impl ProceduralAnimationWidget {
     /// Set the highlight color for the animation
     pub fn set_highlight_color(&mut self, color: Color) {
         self.highlight_color = color;
+        if self.frames.is_some() {
+            self.frames = Some(self.compute_frames());
+        }
+    }
+
+    /// One pass of the animation plus the pause after it
+    pub fn cycle_duration(&self) -> Duration {
+        self.frame_duration * self.num_frames as u32 + self.pause_at_end
     }
 
     fn get_animation_progress_and_cycle(&self) -> (f32, usize) {
         if self.paused {
             return (self.paused_progress, self.paused_cycle);
         }
+        self.progress_at(animation::now().saturating_sub(self.start_time))
+    }
 
-        let elapsed = self.start_time.elapsed();
+    /// Progress through the current cycle and the cycles completed, `elapsed` after the start
+    fn progress_at(&self, elapsed: Duration) -> (f32, usize) {
         let animation_duration = self.frame_duration * self.num_frames as u32;
-        let total_cycle_duration = animation_duration + self.pause_at_end;
+        let total_cycle_duration = self.cycle_duration();
 
         let cycle = (elapsed.as_millis() / total_cycle_duration.as_millis()) as usize;
         let cycle_time = elapsed.as_millis() % total_cycle_duration.as_millis();
@@ -139,10 +236,53 @@ impl ProceduralAnimationWidget {
     }
 
     pub fn render_to_buffer(&self, area: Rect, buf: &mut Buffer) {
+        if settings::get().reduce_motion {
+            self.render_still(area, buf);
+            return;
+        }
         let (progress, cycle) = self.get_animation_progress_and_cycle();
+        self.render_frame(area, buf, progress, cycle);
+    }
+
+    /// Draw the animation as it looks `elapsed` after its start, for timing kept elsewhere
+    pub fn render_after(&self, elapsed: Duration, area: Rect, buf: &mut Buffer) {
+        if settings::get().reduce_motion {
+            self.render_still(area, buf);
+            return;
+        }
+        let (progress, cycle) = self.progress_at(elapsed);
+        self.render_frame(area, buf, progress, cycle);
+    }
+
+    /// Draw the last frame of the first cycle, where an animation played once stops
+    pub fn render_end(&self, area: Rect, buf: &mut Buffer) {
+        if settings::get().reduce_motion {
+            self.render_still(area, buf);
+            return;
+        }
+        self.render_frame(area, buf, 1.0, 0);
+    }
+
+    fn render_frame(&self, area: Rect, buf: &mut Buffer, progress: f32, cycle: usize) {
+        if let Some(frames) = &self.frames {
+            let frames_per_cycle = self.num_frames.max(1);
+            let frame = ((progress * frames_per_cycle as f32) as usize).min(frames_per_cycle);
+            let cells = &frames[cycle % 2 * (frames_per_cycle + 1) + frame];
+            for (glyph, &(ch, color)) in self.glyphs.iter().zip(cells) {
+                put_glyph(area, buf, glyph, ch, color);
+            }
+            return;
+        }
         self.render_to_buffer_at_progress(area, buf, progress, cycle);
     }
 
+    /// The plain art in the highlight color, for reduced motion
+    fn render_still(&self, area: Rect, buf: &mut Buffer) {
+        for glyph in &self.glyphs {
+            put_glyph(area, buf, glyph, glyph.ch, self.highlight_color);
+        }
+    }
+
     pub fn render_to_buffer_at_progress(
         &self,
         area: Rect,
@@ -150,32 +290,1221 @@ impl ProceduralAnimationWidget {
         progress: f32,
         cycle: usize,
     ) {
-        for (y, line) in self.art.lines().enumerate() {
-            for (x, ch) in line.chars().enumerate() {
-                if ch == ' ' {
-                    continue; // Skip spaces
+        for glyph in &self.glyphs {
+            let (display_char, color) = self.cell(glyph, progress, cycle);
+            put_glyph(area, buf, glyph, display_char, color);
+        }
+    }
+
+    /// How a glyph looks at a point of the animation
+    fn cell(&self, glyph: &Glyph, progress: f32, cycle: usize) -> (char, Color) {
+        let (x, y) = (usize::from(glyph.x), usize::from(glyph.y));
+        let color = (self.color_fn)(x, y, progress, cycle, self.highlight_color);
+
+        // Apply character transformation if char_fn is provided
+        let display_char = if let Some(ref char_fn) = self.char_fn {
+            char_fn(x, y, progress, cycle, glyph.ch)
+        } else {
+            glyph.ch
+        };
+        (display_char, color)
+    }
+}
+
+/// Draw a glyph of the art, clearing the cells a wide character covers
+fn put_glyph(area: Rect, buf: &mut Buffer, glyph: &Glyph, ch: char, color: Color) {
+    let position = Position::new(glyph.x + area.x, glyph.y + area.y);
+    if !area.contains(position) {
+        return;
+    }
+    buf[position].set_char(ch).set_fg(color);
+    for covered in 1..glyph.width {
+        let position = Position::new(position.x + covered, position.y);
+        if area.contains(position) {
+            buf[position].reset();
+        }
+    }
+}
+
+/// One-line bar of key hints, e.g. `↑↓ select · Enter start · Esc quit`
+pub struct KeyHintFooter<'a> {
+    hints: &'a [KeyHint],
+}
+
+impl<'a> KeyHintFooter<'a> {
+    pub const fn new(hints: &'a [KeyHint]) -> Self {
+        Self { hints }
+    }
+}
+
+impl Widget for KeyHintFooter<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let mut spans = Vec::new();
+        for (i, hint) in self.hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" · ", Style::default().fg(theme.muted)));
+            }
+            spans.push(Span::styled(hint.keys(), Style::default().fg(theme.hotkey)));
+            spans.push(Span::styled(format!(" {}", hint.action), Style::default().fg(theme.muted)));
+        }
+        Line::from(spans).centered().render(area, buf);
+    }
+}
+
+/// Text drawn with a 5 row block font, for countdowns and other big numbers.
+/// Characters without a glyph render as blanks.
+pub struct BigText<'a> {
+    text: &'a str,
+    color: Color,
+    /// Replaces the color when set
+    gradient: Option<Gradient>,
+}
+
+impl<'a> BigText<'a> {
+    pub const HEIGHT: u16 = 5;
+
+    pub const fn new(text: &'a str, color: Color) -> Self {
+        Self { text, color, gradient: None }
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Rendered width in cells, including one column between glyphs
+    pub fn width(&self) -> u16 {
+        #[allow(clippy::cast_possible_truncation)]
+        let chars = self.text.chars().count() as u16;
+        (chars * 6).saturating_sub(1)
+    }
+
+    /// Draw in the middle of `area`
+    pub fn render_centered(self, area: Rect, buf: &mut Buffer) {
+        let [row] =
+            Layout::vertical([Constraint::Length(Self::HEIGHT)]).flex(Flex::Center).areas(area);
+        let [cell] =
+            Layout::horizontal([Constraint::Length(self.width())]).flex(Flex::Center).areas(row);
+        self.render(cell, buf);
+    }
+
+    /// Filled cells of a glyph, row by row
+    fn glyph(c: char) -> [[bool; 5]; 5] {
+        BIG_FONT.with(|font| font.get(&c).copied().unwrap_or_default())
+    }
+}
+
+thread_local! {
+    static BIG_FONT: HashMap<char, [[bool; 5]; 5]> = parse_big_font(include_str!("../assets/font/big.txt"));
+}
+
+/// Glyphs of the big font: a `--- ` line naming the character, then its 5 rows where
+/// `#` marks a filled cell. Lines before the first glyph are comments.
+fn parse_big_font(src: &str) -> HashMap<char, [[bool; 5]; 5]> {
+    let mut font = HashMap::new();
+    let mut lines = src.lines();
+    while let Some(line) = lines.next() {
+        let Some(c) = line.strip_prefix("--- ").and_then(|name| name.chars().next()) else {
+            continue;
+        };
+        let mut glyph = [[false; 5]; 5];
+        for row in &mut glyph {
+            for (cell, ch) in row.iter_mut().zip(lines.next().unwrap_or_default().chars()) {
+                *cell = ch == '#';
+            }
+        }
+        font.insert(c, glyph);
+    }
+    font
+}
+
+impl Widget for BigText<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = self.width();
+        for (i, c) in self.text.chars().enumerate() {
+            for (dy, row) in Self::glyph(c.to_ascii_uppercase()).iter().enumerate() {
+                for (dx, &filled) in row.iter().enumerate() {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let position = Position::new(area.x + (i * 6 + dx) as u16, area.y + dy as u16);
+                    if filled
+                        && area.contains(position)
+                        && let Some(target) = buf.cell_mut(position)
+                    {
+                        let color = self.gradient.as_ref().map_or(self.color, |gradient| {
+                            gradient.color_at(
+                                position.x - area.x,
+                                position.y - area.y,
+                                width,
+                                Self::HEIGHT,
+                            )
+                        });
+                        target.set_char('█').set_fg(color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Digits drawn like a 3 row seven-segment display, for clocks and timers.
+/// A `:` takes one column and can be switched off to blink; other characters render as blanks.
+pub struct SevenSegment<'a> {
+    text: &'a str,
+    style: Style,
+    colon: bool,
+}
+
+impl<'a> SevenSegment<'a> {
+    pub const HEIGHT: u16 = 3;
+
+    /// Segments a to g of each digit, a in the lowest bit: top, top right, bottom right,
+    /// bottom, bottom left, top left, middle
+    const DIGITS: [u8; 10] = [
+        0b011_1111, 0b000_0110, 0b101_1011, 0b100_1111, 0b110_0110, 0b110_1101, 0b111_1101,
+        0b000_0111, 0b111_1111, 0b110_1111,
+    ];
+
+    pub fn new(text: &'a str) -> Self {
+        Self { text, style: Style::default(), colon: true }
+    }
+
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Show the colons, off for the dark half of a blink
+    pub const fn colon(mut self, visible: bool) -> Self {
+        self.colon = visible;
+        self
+    }
+
+    /// Rendered width in cells: 3 per digit, 1 per colon, with a space between characters
+    pub fn width(&self) -> u16 {
+        let cells: usize = self.text.chars().map(|c| if c == ':' { 1 } else { 3 }).sum();
+        #[allow(clippy::cast_possible_truncation)]
+        let gaps = self.text.chars().count().saturating_sub(1) as u16;
+        #[allow(clippy::cast_possible_truncation)]
+        let width = cells as u16 + gaps;
+        width
+    }
+
+    /// The rows of one character
+    fn glyph(&self, c: char) -> [&'static str; 3] {
+        if c == ':' {
+            return if self.colon { [" ", "·", "·"] } else { [" "; 3] };
+        }
+        let Some(segments) = c.to_digit(10).map(|digit| Self::DIGITS[digit as usize]) else {
+            return ["   "; 3];
+        };
+        let on = |segment: u8| segments & (1 << segment) != 0;
+        let row = |left: u8, middle: u8, right: u8| -> &'static str {
+            match (on(left), on(middle), on(right)) {
+                (false, false, false) => "   ",
+                (false, false, true) => "  |",
+                (false, true, false) => " _ ",
+                (false, true, true) => " _|",
+                (true, false, false) => "|  ",
+                (true, false, true) => "| |",
+                (true, true, false) => "|_ ",
+                (true, true, true) => "|_|",
+            }
+        };
+        let top = if on(0) { " _ " } else { "   " };
+        [top, row(5, 6, 1), row(4, 3, 2)]
+    }
+}
+
+impl Widget for SevenSegment<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut x = area.x;
+        for c in self.text.chars() {
+            let rows = self.glyph(c);
+            for (dy, row) in (0..).zip(rows) {
+                if dy < area.height {
+                    buf.set_stringn(
+                        x,
+                        area.y + dy,
+                        row,
+                        usize::from(area.right().saturating_sub(x)),
+                        self.style,
+                    );
                 }
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let advance = rows[0].chars().count() as u16 + 1;
+            x = x.saturating_add(advance);
+        }
+    }
+}
+
+/// A bit pattern as a row of LEDs, ● for 1 and ○ for 0, most significant bit first and
+/// grouped by nibble. Bit indices can be labeled underneath.
+pub struct LedRow {
+    pattern: u32,
+    bits: u32,
+    /// Index of the lowest bit in the labels, for patterns that are a shifted nibble
+    first_bit: u32,
+    /// Colors of the even and the odd nibbles, counted from the lowest
+    colors: [Color; 2],
+    labels: bool,
+    /// One nibble in another color, counted from the lowest
+    highlight: Option<(u32, Color)>,
+}
+
+impl LedRow {
+    pub const fn new(pattern: u32, bits: u32) -> Self {
+        Self {
+            pattern,
+            bits,
+            first_bit: 0,
+            colors: [Color::Reset; 2],
+            labels: false,
+            highlight: None,
+        }
+    }
+
+    pub const fn first_bit(mut self, index: u32) -> Self {
+        self.first_bit = index;
+        self
+    }
+
+    pub const fn colors(mut self, even: Color, odd: Color) -> Self {
+        self.colors = [even, odd];
+        self
+    }
+
+    pub const fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub const fn highlight_nibble(mut self, nibble: u32, color: Color) -> Self {
+        self.highlight = Some((nibble, color));
+        self
+    }
+
+    pub const fn height(&self) -> u16 {
+        if self.labels { 2 } else { 1 }
+    }
+
+    /// Columns per LED: room for the widest label and a space
+    const fn slot(&self) -> u16 {
+        let highest = self.first_bit + self.bits.saturating_sub(1);
+        if self.labels && highest >= 10 { 3 } else { 2 }
+    }
+
+    /// Column of the LED for bit `index` (0 is the lowest), relative to the left edge
+    #[allow(clippy::cast_possible_truncation)]
+    const fn x_of(&self, index: u32) -> u16 {
+        let from_left = self.bits - 1 - index;
+        from_left as u16 * self.slot() + (from_left / 4) as u16
+    }
+
+    /// Rendered width in cells, one extra column between nibbles
+    pub const fn width(&self) -> u16 {
+        let last_label = if self.labels && self.first_bit >= 10 { 2 } else { 1 };
+        if self.bits == 0 { 0 } else { self.x_of(0) + last_label }
+    }
+}
+
+impl Widget for LedRow {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let muted = theme::current().muted;
+        for index in 0..self.bits {
+            let x = area.x + self.x_of(index);
+            if x >= area.right() {
+                continue;
+            }
+            let lit = self.pattern >> index & 1 == 1;
+            let color = match self.highlight {
+                Some((nibble, color)) if nibble == index / 4 => color,
+                _ => self.colors[(index / 4 % 2) as usize],
+            };
+            buf.set_string(x, area.y, if lit { "●" } else { "○" }, Style::default().fg(color));
+            if self.labels && area.height > 1 {
+                let label = (index + self.first_bit).to_string();
+                let room = usize::from(area.right() - x);
+                buf.set_stringn(x, area.y + 1, label, room, Style::default().fg(muted));
+            }
+        }
+    }
+}
+
+/// Bits the player sets one by one: a cursor moves over an LED row, the bit under it
+/// flips, and the value is read out in decimal and hex underneath
+pub struct BitToggleWidget {
+    bits: u32,
+    value: u32,
+    /// Index of the bit under the cursor, 0 is the lowest
+    cursor: u32,
+}
+
+impl BitToggleWidget {
+    pub const HEIGHT: u16 = 4;
+
+    /// All bits off, the cursor on the highest one
+    pub const fn new(bits: u32) -> Self {
+        Self { bits, value: 0, cursor: bits.saturating_sub(1) }
+    }
+
+    pub const fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Move the cursor or flip the bit under it. Returns whether the key was used.
+    pub fn handle_input(&mut self, key: KeyEvent) -> bool {
+        match key {
+            x if keybinds::is_left(x) => self.cursor = (self.cursor + 1).min(self.bits - 1),
+            x if keybinds::is_right(x) => self.cursor = self.cursor.saturating_sub(1),
+            x if keybinds::pressed(x, keybinds::FLIP) => self.value ^= 1 << self.cursor,
+            _ => return false,
+        }
+        true
+    }
+
+    fn leds(&self) -> LedRow {
+        let theme = theme::current();
+        LedRow::new(self.value, self.bits)
+            .colors(theme.text, theme.accent)
+            .labels(true)
+            .highlight_nibble(self.cursor / 4, theme.selection)
+    }
+
+    pub fn width(&self) -> u16 {
+        self.leds().width()
+    }
+}
+
+impl Widget for &BitToggleWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let leds = self.leds();
+        let [led_row, cursor_row, readout_row] = Layout::vertical([
+            Constraint::Length(leds.height()),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        // The bits are centered like the readout, which can be the wider one
+        let [led_row] = Layout::horizontal([Constraint::Length(leds.width())])
+            .flex(Flex::Center)
+            .areas(led_row);
+        let cursor_x = led_row.x + leds.x_of(self.cursor);
+        leds.render(led_row, buf);
+        if cursor_x < led_row.right() {
+            buf.set_string(cursor_x, cursor_row.y, "▲", Style::default().fg(theme.selection));
+        }
+
+        let nibbles = self.bits.div_ceil(4) as usize;
+        Line::from(vec![
+            Span::styled(format!("= {}", self.value), Style::default().fg(theme.text).bold()),
+            Span::styled(
+                format!("  0x{:0nibbles$X}", self.value),
+                Style::default().fg(theme.accent),
+            ),
+        ])
+        .centered()
+        .render(readout_row, buf);
+    }
+}
+
+/// What goes between the nibbles of a binary number, see `binary_spans`
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum NibbleSeparator {
+    /// `0110 1011`
+    #[default]
+    Space,
+    /// `0110_1011`, like a Rust literal
+    Underscore,
+    /// `01101011`, the nibbles told apart by color alone
+    None,
+}
+
+impl NibbleSeparator {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "space" | " " => Some(Self::Space),
+            "underscore" | "_" => Some(Self::Underscore),
+            "none" | "off" => Some(Self::None),
+            _ => None,
+        }
+    }
 
-                let color = (self.color_fn)(x, y, progress, cycle, self.highlight_color);
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Space => " ",
+            Self::Underscore => "_",
+            Self::None => "",
+        }
+    }
+}
 
-                // Apply character transformation if char_fn is provided
-                let display_char = if let Some(ref char_fn) = self.char_fn {
-                    char_fn(x, y, progress, cycle, ch)
+/// Nibbles of a binary number, highest first, joined by the configured separator and
+/// colored alternately. Colors are counted from the lowest nibble, like [`LedRow`]'s.
+pub fn binary_spans(nibbles: &[String], colors: [Color; 2]) -> Vec<Span<'static>> {
+    let theme = theme::current();
+    let separator = settings::get().nibble_separator.as_str();
+    let mut spans = Vec::new();
+    for (i, nibble) in nibbles.iter().enumerate() {
+        if i > 0 && !separator.is_empty() {
+            spans.push(Span::styled(separator, Style::default().fg(theme.muted)));
+        }
+        let from_lowest = nibbles.len() - 1 - i;
+        spans.push(Span::styled(nibble.clone(), Style::default().fg(colors[from_lowest % 2])));
+    }
+    spans
+}
+
+/// Colors blended across an area through evenly spaced stops
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    stops: Vec<Color>,
+    /// Runs from the top left to the bottom right corner instead of left to right
+    diagonal: bool,
+}
+
+impl Gradient {
+    pub fn horizontal(stops: &[Color]) -> Self {
+        Self { stops: stops.to_vec(), diagonal: false }
+    }
+
+    pub fn diagonal(stops: &[Color]) -> Self {
+        Self { stops: stops.to_vec(), diagonal: true }
+    }
+
+    /// Color of cell (x, y) in an area of `width` by `height` cells
+    pub fn color_at(&self, x: u16, y: u16, width: u16, height: u16) -> Color {
+        let (position, extent) = if self.diagonal {
+            (x + y, (width + height).saturating_sub(2))
+        } else {
+            (x, width.saturating_sub(1))
+        };
+        let segments = self.stops.len().saturating_sub(1);
+        if segments == 0 || extent == 0 {
+            return self.stops.first().copied().unwrap_or(Color::Reset);
+        }
+        let scaled = (f32::from(position) / f32::from(extent)).min(1.0) * segments as f32;
+        let index = (scaled as usize).min(segments - 1);
+        let depth = settings::get().color_depth;
+        color::blend(self.stops[index], self.stops[index + 1], scaled - index as f32, depth)
+    }
+}
+
+/// A line of text colored along a gradient, for headings and scores
+pub struct GradientText<'a> {
+    text: &'a str,
+    gradient: Gradient,
+    /// Kept under the gradient, e.g. bold
+    style: Style,
+}
+
+impl<'a> GradientText<'a> {
+    pub fn new(text: &'a str, gradient: Gradient) -> Self {
+        Self { text, gradient, style: Style::default() }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// One span per character, to use as or within a line
+    pub fn spans(&self) -> Vec<Span<'a>> {
+        let width = self.text.width() as u16;
+        let mut x = 0;
+        self.text
+            .char_indices()
+            .map(|(i, ch)| {
+                let color = self.gradient.color_at(x, 0, width, 1);
+                x += ch.width().unwrap_or(0) as u16;
+                Span::styled(&self.text[i..i + ch.len_utf8()], self.style.fg(color))
+            })
+            .collect()
+    }
+}
+
+/// Reveals lines a character at a time as if typed, for messages worth reading slowly.
+/// Only the timing is kept; the screen hands over its lines on every frame.
+pub struct Typewriter {
+    /// Animation time typing started
+    started: Duration,
+    chars_per_sec: f32,
+    /// A block follows the last character typed
+    cursor: bool,
+    skipped: bool,
+    /// Characters in the lines revealed last, to know when typing is over
+    total: Cell<usize>,
+}
+
+impl Typewriter {
+    const CURSOR: &'static str = "▌";
+
+    pub fn new(chars_per_sec: f32) -> Self {
+        Self {
+            started: animation::now(),
+            chars_per_sec,
+            cursor: false,
+            skipped: false,
+            total: Cell::new(0),
+        }
+    }
+
+    pub fn with_cursor(mut self, cursor: bool) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Show everything at once, e.g. on a key press
+    pub fn skip(&mut self) {
+        self.skipped = true;
+    }
+
+    /// Characters typed so far; everything with reduced motion
+    fn typed(&self) -> usize {
+        if self.skipped || settings::get().reduce_motion {
+            return usize::MAX;
+        }
+        let elapsed = animation::now().saturating_sub(self.started);
+        (elapsed.as_secs_f32() * self.chars_per_sec) as usize
+    }
+
+    /// Some of the lines revealed last are still hidden
+    pub fn is_typing(&self) -> bool {
+        self.typed() < self.total.get()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.is_typing() && !animation::is_paused()
+    }
+
+    /// The part of `lines` typed so far. Lines not reached yet are kept empty, so
+    /// the text doesn't move up and down while it is typed.
+    pub fn reveal<'a>(&self, lines: Vec<Line<'a>>) -> Vec<Line<'a>> {
+        self.total.set(
+            lines
+                .iter()
+                .flat_map(|line| &line.spans)
+                .map(|span| span.content.chars().count())
+                .sum(),
+        );
+        let mut left = self.typed();
+        let mut cursor = self.cursor && self.is_typing();
+        lines
+            .into_iter()
+            .map(|line| {
+                let mut spans = Vec::new();
+                for span in line.spans {
+                    let length = span.content.chars().count();
+                    if length <= left {
+                        left -= length;
+                        spans.push(span);
+                        continue;
+                    }
+                    let typed: String = span.content.chars().take(left).collect();
+                    left = 0;
+                    if cursor {
+                        spans.push(Span::styled(typed, span.style));
+                        spans.push(Span::styled(Self::CURSOR, span.style));
+                        cursor = false;
+                    } else if !typed.is_empty() {
+                        spans.push(Span::styled(typed, span.style));
+                    }
+                    break;
+                }
+                Line { spans, ..line }
+            })
+            .collect()
+    }
+}
+
+/// One line of text that scrolls sideways when it is wider than its area, like a
+/// news ticker. Text that fits, or doesn't scroll, is shown still and cut off.
+pub struct Marquee<'a> {
+    text: &'a str,
+    style: Style,
+    chars_per_sec: f32,
+    /// Blank cells between the end of the text and its next pass
+    gap: u16,
+    scrolling: bool,
+}
+
+impl<'a> Marquee<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Self { text, style: Style::new(), chars_per_sec: 8.0, gap: 6, scrolling: true }
+    }
+
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub const fn speed(mut self, chars_per_sec: f32) -> Self {
+        self.chars_per_sec = chars_per_sec;
+        self
+    }
+
+    pub const fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Hold the text still, e.g. while animations are paused
+    pub const fn scrolling(mut self, scrolling: bool) -> Self {
+        self.scrolling = scrolling;
+        self
+    }
+}
+
+impl Widget for Marquee<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text_width = self.text.width();
+        if text_width <= usize::from(area.width) {
+            Line::styled(self.text, self.style).centered().render(area, buf);
+            return;
+        }
+        if !self.scrolling || settings::get().reduce_motion {
+            let [text, ellipsis] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+            Span::styled(self.text, self.style).render(text, buf);
+            Span::styled("…", self.style).render(ellipsis, buf);
+            return;
+        }
+        // Columns of one pass: the text and the gap after it
+        let mut columns: Vec<Option<char>> = Vec::with_capacity(text_width);
+        for ch in self.text.chars() {
+            let width = ch.width().unwrap_or(0);
+            if width > 0 {
+                columns.push(Some(ch));
+                columns.extend(std::iter::repeat_n(None, width - 1));
+            }
+        }
+        columns.extend(std::iter::repeat_n(Some(' '), usize::from(self.gap)));
+        let offset = (animation::now().as_secs_f32() * self.chars_per_sec) as usize;
+        for x in 0..area.width {
+            let column = columns[(offset + usize::from(x)) % columns.len()];
+            // The second half of a wide character is drawn by its first half
+            if let Some(ch) = column
+                && let Some(cell) = buf.cell_mut(Position::new(area.x + x, area.y))
+            {
+                cell.set_char(ch).set_style(self.style);
+            }
+        }
+    }
+}
+
+/// Frame timings and the open screens, in the top right corner over everything else
+pub struct DebugOverlay<'a> {
+    stats: &'a FrameStats,
+    screens: String,
+}
+
+impl<'a> DebugOverlay<'a> {
+    pub const fn new(stats: &'a FrameStats, screens: String) -> Self {
+        Self { stats, screens }
+    }
+}
+
+impl Widget for DebugOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let entry = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<9}"), Style::default().fg(theme.muted)),
+                Span::styled(value, Style::default().fg(theme.text)),
+            ])
+        };
+        let millis = |duration: Duration| format!("{:.1} ms", duration.as_secs_f64() * 1000.0);
+        let lines = vec![
+            entry("fps", self.stats.fps().to_string()),
+            entry("frame", millis(self.stats.frame_time)),
+            entry("latency", millis(self.stats.event_latency)),
+            entry("screens", self.screens),
+        ];
+        #[allow(clippy::cast_possible_truncation)]
+        let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4).min(area.width);
+        let [column] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::End).areas(area);
+        let [overlay] = Layout::vertical([Constraint::Length(6)]).areas(column);
+
+        Clear.render(overlay, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered().title(" debug ").border_style(Style::default().fg(theme.accent)),
+            )
+            .render(overlay, buf);
+    }
+}
+
+/// A modal with a title, optional body text and a row of buttons, centered over whatever
+/// was rendered below it. Each button is a key choice; the focused one also answers to
+/// Enter, see [`DialogFocus`].
+pub struct Dialog<'a> {
+    title: &'a str,
+    body: &'a [&'a str],
+    choices: &'a [KeyHint],
+    focused: usize,
+}
+
+impl<'a> Dialog<'a> {
+    /// The buttons of `confirm`
+    pub const CONFIRM_CHOICES: &'static [KeyHint] =
+        &[KeyHint::new(&[keybinds::CONFIRM], "yes"), KeyHint::new(&[keybinds::CANCEL], "no")];
+
+    pub const fn new(title: &'a str, choices: &'a [KeyHint]) -> Self {
+        Self { title, body: &[], choices, focused: 0 }
+    }
+
+    /// A yes/no question
+    pub const fn confirm(prompt: &'a str) -> Self {
+        Self::new(prompt, Self::CONFIRM_CHOICES)
+    }
+
+    /// Lines of text between the title and the buttons
+    pub const fn body(mut self, body: &'a [&'a str]) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub const fn focus(mut self, focus: DialogFocus) -> Self {
+        self.focused = focus.0;
+        self
+    }
+}
+
+impl Widget for Dialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let mut buttons = Vec::new();
+        for (i, choice) in self.choices.iter().enumerate() {
+            if i > 0 {
+                buttons.push(Span::raw("  "));
+            }
+            let focused = i == self.focused;
+            let style = if focused {
+                Style::default().bg(theme.menu_selected_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let marker = if focused && theme.monochrome { ">" } else { " " };
+            buttons.push(Span::styled(marker, style));
+            buttons.push(Span::styled(choice.keys(), style.fg(theme.hotkey)));
+            buttons.push(Span::styled(format!(" {} ", choice.action), style.fg(theme.text)));
+        }
+
+        let mut lines = vec![Line::from(Span::styled(self.title, theme.text).bold())];
+        lines.extend(self.body.iter().map(|line| Line::styled(*line, theme.text)));
+        lines.push(Line::from(""));
+        lines.push(Line::from(buttons));
+        #[allow(clippy::cast_possible_truncation)]
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 6;
+        #[allow(clippy::cast_possible_truncation)]
+        let height = lines.len() as u16 + 2;
+        let [row] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+        let [dialog] =
+            Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(row);
+
+        Clear.render(dialog, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::bordered().border_style(Style::default().fg(theme.accent)))
+            .render(dialog, buf);
+    }
+}
+
+/// The button of a [`Dialog`] that Enter presses, moved with left and right
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct DialogFocus(usize);
+
+impl DialogFocus {
+    /// Move the focus on left and right, which returns `None`. Enter becomes the key of the
+    /// focused choice, other keys are returned as they are.
+    pub fn handle_input(&mut self, key: KeyEvent, choices: &[KeyHint]) -> Option<KeyEvent> {
+        let last = choices.len().saturating_sub(1);
+        match key {
+            x if keybinds::is_left(x) => self.0 = self.0.saturating_sub(1),
+            x if keybinds::is_right(x) => self.0 = (self.0 + 1).min(last),
+            x if keybinds::is_select(x) => {
+                let code = choices.get(self.0)?.bindings.first()?.first()?;
+                return Some(KeyEvent::from(*code));
+            },
+            x => return Some(x),
+        }
+        None
+    }
+}
+
+/// A list that scrolls to keep the selection in view and shows a scrollbar in its last
+/// column once it has more items than rows
+pub struct ScrollList<'a> {
+    items: Vec<ListItem<'a>>,
+}
+
+impl<'a> ScrollList<'a> {
+    pub const fn new(items: Vec<ListItem<'a>>) -> Self {
+        Self { items }
+    }
+
+    /// Whether `rows` are too few for `items`, so the list needs a column for the scrollbar
+    pub const fn overflows(items: usize, rows: u16) -> bool {
+        items > rows as usize
+    }
+}
+
+impl StatefulWidget for ScrollList<'_> {
+    type State = ListState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let len = self.items.len();
+        if !Self::overflows(len, area.height) {
+            StatefulWidget::render(List::new(self.items), area, buf, state);
+            return;
+        }
+        let [list_area, bar_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        StatefulWidget::render(List::new(self.items), list_area, buf, state);
+        let mut bar = ScrollbarState::new(len.saturating_sub(usize::from(area.height)))
+            .viewport_content_length(usize::from(area.height))
+            .position(state.offset());
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_style(Style::default().fg(theme::current().muted))
+            .render(bar_area, buf, &mut bar);
+    }
+}
+
+/// Shown instead of a screen that doesn't fit the terminal, rather than clipping it
+pub struct TooSmall {
+    required: Size,
+    actual: Size,
+}
+
+impl TooSmall {
+    pub const fn new(required: Size, actual: Size) -> Self {
+        Self { required, actual }
+    }
+}
+
+impl Widget for TooSmall {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let style_for =
+            |fits: bool| Style::default().fg(if fits { theme.correct } else { theme.incorrect });
+        let lines = vec![
+            Line::from(Span::styled(
+                "Terminal too small",
+                Style::default().fg(theme.accent).bold(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("resize to at least {}x{}", self.required.width, self.required.height),
+                Style::default().fg(theme.text),
+            )),
+            Line::from(vec![
+                Span::styled("current size: ", Style::default().fg(theme.muted)),
+                Span::styled(
+                    self.actual.width.to_string(),
+                    style_for(self.actual.width >= self.required.width),
+                ),
+                Span::styled("x", Style::default().fg(theme.muted)),
+                Span::styled(
+                    self.actual.height.to_string(),
+                    style_for(self.actual.height >= self.required.height),
+                ),
+            ]),
+        ];
+        #[allow(clippy::cast_possible_truncation)]
+        let [row] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+            .flex(Flex::Center)
+            .areas(area);
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(row, buf);
+    }
+}
+
+/// A one-cell border whose perimeter fills clockwise from the top-left corner
+/// as `ratio` goes from 0.0 to 1.0, for ambient progress display
+pub struct ProgressBorder {
+    ratio: f64,
+    filled_color: Color,
+    empty_color: Color,
+}
+
+impl ProgressBorder {
+    pub const fn new(ratio: f64, filled_color: Color, empty_color: Color) -> Self {
+        Self { ratio, filled_color, empty_color }
+    }
+
+    /// Perimeter cells in clockwise order, each with its light and heavy glyph
+    fn perimeter(area: Rect) -> Vec<(Position, &'static str, &'static str)> {
+        let (left, top) = (area.x, area.y);
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        let mut cells = Vec::with_capacity(2 * (area.width + area.height) as usize);
+
+        for x in left..=right {
+            let glyphs = match x {
+                _ if x == left => ("┌", "┏"),
+                _ if x == right => ("┐", "┓"),
+                _ => ("─", "━"),
+            };
+            cells.push((Position::new(x, top), glyphs.0, glyphs.1));
+        }
+        for y in top + 1..bottom {
+            cells.push((Position::new(right, y), "│", "┃"));
+        }
+        for x in (left..=right).rev() {
+            let glyphs = match x {
+                _ if x == right => ("┘", "┛"),
+                _ if x == left => ("└", "┗"),
+                _ => ("─", "━"),
+            };
+            cells.push((Position::new(x, bottom), glyphs.0, glyphs.1));
+        }
+        for y in (top + 1..bottom).rev() {
+            cells.push((Position::new(left, y), "│", "┃"));
+        }
+        cells
+    }
+}
+
+impl Widget for ProgressBorder {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let cells = Self::perimeter(area);
+        #[allow(clippy::cast_sign_loss)]
+        #[allow(clippy::cast_possible_truncation)]
+        let filled = (cells.len() as f64 * self.ratio.clamp(0.0, 1.0)).round() as usize;
+
+        for (i, (position, light, heavy)) in cells.into_iter().enumerate() {
+            // Filled cells use heavy glyphs so progress stays visible without color
+            let (symbol, color) = if i < filled {
+                (heavy, self.filled_color)
+            } else {
+                (light, self.empty_color)
+            };
+            if let Some(cell) = buf.cell_mut(position) {
+                cell.set_symbol(symbol).set_fg(color);
+            }
+        }
+    }
+}
+
+/// A one row bar filled from the left to `ratio`, in eighths of a cell. Cells past the
+/// end are left as they are, so the bar can run along a border.
+pub struct TimerBar {
+    ratio: f64,
+    color: Color,
+}
+
+impl TimerBar {
+    /// Left aligned blocks of one to seven eighths of a cell
+    const PARTIAL: [&'static str; 7] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    pub const fn new(ratio: f64, color: Color) -> Self {
+        Self { ratio, color }
+    }
+}
+
+impl Widget for TimerBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        #[allow(clippy::cast_sign_loss)]
+        #[allow(clippy::cast_possible_truncation)]
+        let eighths = (f64::from(area.width) * 8.0 * self.ratio.clamp(0.0, 1.0)).round() as u16;
+        let (full, partial) = (eighths / 8, eighths % 8);
+        for x in 0..area.width.min(full + u16::from(partial > 0)) {
+            let symbol = if x < full {
+                "█"
+            } else {
+                Self::PARTIAL[usize::from(partial - 1)]
+            };
+            if let Some(cell) = buf.cell_mut((area.x + x, area.y)) {
+                cell.set_symbol(symbol).set_fg(self.color);
+            }
+        }
+    }
+}
+
+/// A line chart in braille dots, 2×4 per cell. The range of values is labeled on the left,
+/// the first and the last point underneath.
+pub struct BrailleChart<'a> {
+    values: &'a [f64],
+    max: f64,
+    unit: &'a str,
+    x_labels: [&'a str; 2],
+    color: Color,
+}
+
+impl<'a> BrailleChart<'a> {
+    /// Dot bits of a braille cell by column, then row
+    const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    /// Values run from 0 to their maximum unless a `max` is given
+    pub fn new(values: &'a [f64]) -> Self {
+        let max = values.iter().copied().fold(0.0, f64::max);
+        Self { values, max, unit: "", x_labels: ["", ""], color: Color::Reset }
+    }
+
+    pub const fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Appended to the value labels, e.g. `s` or `%`
+    pub const fn unit(mut self, unit: &'a str) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub const fn x_labels(mut self, first: &'a str, last: &'a str) -> Self {
+        self.x_labels = [first, last];
+        self
+    }
+
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn value_label(&self, value: f64) -> String {
+        if value.fract() == 0.0 {
+            format!("{value:.0}{}", self.unit)
+        } else {
+            format!("{value:.1}{}", self.unit)
+        }
+    }
+
+    /// Dot row of every value, 0 at the top, in a plot `rows` dots high
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn dot_rows(&self, rows: usize) -> Vec<usize> {
+        let bottom = rows.saturating_sub(1) as f64;
+        self.values
+            .iter()
+            .map(|&value| {
+                let ratio = if self.max > 0.0 {
+                    (value / self.max).clamp(0.0, 1.0)
                 } else {
-                    ch
+                    0.0
                 };
+                ((1.0 - ratio) * bottom).round() as usize
+            })
+            .collect()
+    }
+}
 
-                let position = Position::new(x as u16 + area.x, y as u16 + area.y);
+impl Widget for BrailleChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = theme::current();
+        let muted = Style::default().fg(theme.muted);
+        let labels = [self.value_label(self.max), self.value_label(0.0)];
+        #[allow(clippy::cast_possible_truncation)]
+        let gutter = labels.iter().map(|label| label.width()).max().unwrap_or(0) as u16 + 1;
+        if area.width <= gutter || area.height < 2 {
+            return;
+        }
+        let plot = Rect::new(area.x + gutter, area.y, area.width - gutter, area.height - 1);
+        for y in plot.top()..plot.bottom() {
+            let label = match y {
+                y if y == plot.top() => labels[0].as_str(),
+                y if y == plot.bottom() - 1 => labels[1].as_str(),
+                _ => "",
+            };
+            let axis = if label.is_empty() { "│" } else { "┤" };
+            buf.set_string(plot.x - 1 - label.width() as u16, y, label, muted);
+            buf.set_string(plot.x - 1, y, axis, muted);
+        }
+        let [first, last] = self.x_labels;
+        buf.set_stringn(plot.x, plot.bottom(), first, usize::from(plot.width), muted);
+        let last_x = plot.right().saturating_sub(last.width() as u16).max(plot.x);
+        buf.set_stringn(last_x, plot.bottom(), last, usize::from(plot.width), muted);
 
-                if area.contains(position) {
-                    #[allow(clippy::expect_used)]
-                    buf.cell_mut(position)
-                        .expect("Failed to get cell at position")
-                        .set_char(display_char)
-                        .set_fg(color);
-                }
+        // Join the points with straight lines, dot by dot
+        let (columns, rows) = (usize::from(plot.width) * 2, usize::from(plot.height) * 4);
+        let mut cells = vec![0u8; usize::from(plot.width) * usize::from(plot.height)];
+        let mut dot = |x: usize, y: usize| {
+            cells[y / 4 * usize::from(plot.width) + x / 2] |= Self::DOTS[x % 2][y % 4];
+        };
+        let dot_rows = self.dot_rows(rows);
+        let step = (columns - 1) as f64 / (dot_rows.len().max(2) - 1) as f64;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let x_of = |index: usize| (index as f64 * step).round() as usize;
+        if let [only] = dot_rows[..] {
+            dot(0, only);
+        }
+        for (index, pair) in dot_rows.windows(2).enumerate() {
+            let (x0, x1) = (x_of(index), x_of(index + 1));
+            let (y0, y1) = (pair[0], pair[1]);
+            let steps = (x1 - x0).max(y0.abs_diff(y1)).max(1);
+            for i in 0..=steps {
+                let t = i as f64 / steps as f64;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let lerp =
+                    |a: usize, b: usize| (a as f64 + (b as f64 - a as f64) * t).round() as usize;
+                dot(lerp(x0, x1), lerp(y0, y1));
             }
         }
+
+        let style = Style::default().fg(self.color);
+        for (index, &bits) in cells.iter().enumerate() {
+            if bits == 0 {
+                continue;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let (x, y) = (
+                (index % usize::from(plot.width)) as u16,
+                (index / usize::from(plot.width)) as u16,
+            );
+            let symbol = char::from_u32(0x2800 + u32::from(bits)).unwrap_or(' ');
+            buf[(plot.x + x, plot.y + y)].set_char(symbol).set_style(style);
+        }
+    }
+}
+
+/// Quarter-segment ring glyph for a completion ratio, for compact progress badges
+pub fn progress_ring(ratio: f64) -> char {
+    const RING: [char; 5] = ['○', '◔', '◑', '◕', '●'];
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    let index = (ratio.clamp(0.0, 1.0) * 4.0).floor() as usize;
+    // Only a complete ring means 100%
+    if ratio < 1.0 { RING[index.min(3)] } else { RING[4] }
+}
+
+/// Shade block glyph for a density ratio, for heat-map style segments
+pub fn shade_glyph(ratio: f64) -> char {
+    const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+    if ratio <= 0.0 {
+        return SHADES[0];
+    }
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    let index = 1 + (ratio.clamp(0.0, 1.0) * 3.0).round() as usize;
+    SHADES[index.min(4)]
+}
+
+/// Index after `current` in a list of `len` items; stays on the last item unless `wrap`
+pub const fn next_index(current: usize, len: usize, wrap: bool) -> usize {
+    if current + 1 < len {
+        current + 1
+    } else if wrap {
+        0
+    } else {
+        current
+    }
+}
+
+/// Index before `current` in a list of `len` items; stays on the first item unless `wrap`
+pub const fn previous_index(current: usize, len: usize, wrap: bool) -> usize {
+    if current > 0 {
+        current - 1
+    } else if wrap {
+        len.saturating_sub(1)
+    } else {
+        current
     }
 }
 
@@ -202,3 +1531,250 @@ impl<T> When for T {
         if condition { action(self) } else { self }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn art_is_laid_out_by_display_column() {
+        let (glyphs, width) = layout_art("界a\n\tb e\u{301}");
+        let placed: Vec<_> = glyphs.iter().map(|g| (g.x, g.y, g.ch, g.width)).collect();
+        assert_eq!(placed, [(0, 0, '界', 2), (2, 0, 'a', 1), (4, 1, 'b', 1), (6, 1, 'e', 1)]);
+        assert_eq!(width, 7);
+    }
+
+    #[test]
+    fn gradients_run_through_their_stops() {
+        use crate::color::ColorDepth;
+        crate::settings::set(crate::settings::Settings {
+            color_depth: ColorDepth::TrueColor,
+            ..crate::settings::get()
+        });
+        let (black, white) = (Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255));
+        let across = Gradient::horizontal(&[black, white]);
+        assert_eq!(across.color_at(0, 3, 5, 4), black);
+        assert_eq!(across.color_at(2, 0, 5, 4), Color::Rgb(128, 128, 128));
+        assert_eq!(across.color_at(4, 0, 5, 4), white);
+        let down = Gradient::diagonal(&[black, white]);
+        assert_eq!(down.color_at(2, 2, 3, 3), white);
+        assert_eq!(Gradient::horizontal(&[white]).color_at(3, 0, 5, 1), white);
+
+        let spans = GradientText::new("a界b", across).spans();
+        let colors: Vec<_> = spans.iter().map(|span| span.style.fg).collect();
+        assert_eq!(colors, [Some(black), Some(Color::Rgb(85, 85, 85)), Some(white)]);
+    }
+
+    #[test]
+    fn typewriter_types_across_lines_and_skips_to_the_end() {
+        let text = || vec![Line::from(vec!["ab".bold(), "c".into()]), Line::from("de")];
+        let plain = |lines: Vec<Line>| -> Vec<String> {
+            lines
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        let mut typewriter = Typewriter::new(10.0).with_cursor(true);
+        animation::advance(Duration::from_millis(150));
+        let typed = typewriter.reveal(text());
+        assert_eq!(plain(typed.clone()), ["a▌", ""]);
+        assert!(typed[0].spans[1].style.add_modifier.contains(Modifier::BOLD), "cursor in style");
+        assert!(typewriter.is_typing());
+
+        animation::advance(Duration::from_millis(300));
+        assert_eq!(plain(typewriter.reveal(text())), ["abc", "d▌"]);
+        typewriter.skip();
+        assert_eq!(plain(typewriter.reveal(text())), ["abc", "de"]);
+        assert!(!typewriter.is_typing());
+    }
+
+    #[test]
+    fn big_font_has_every_digit_and_draws_it() {
+        for c in "0123456789-GO!".chars() {
+            assert_ne!(BigText::glyph(c), [[false; 5]; 5], "{c}");
+        }
+        assert_eq!(BigText::glyph('?'), [[false; 5]; 5]);
+
+        let text = BigText::new("-1", Color::Red);
+        let area = Rect::new(0, 0, text.width(), BigText::HEIGHT);
+        let mut buf = Buffer::empty(area);
+        text.render(area, &mut buf);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            ["        █  ", "       ██  ", "█████   █  ", "        █  ", "       ███ "]
+        );
+    }
+
+    #[test]
+    fn seven_segment_draws_digits_and_blinks_the_colon() {
+        let draw = |digits: SevenSegment| {
+            let area = Rect::new(0, 0, digits.width(), SevenSegment::HEIGHT);
+            let mut buf = Buffer::empty(area);
+            digits.render(area, &mut buf);
+            (0..area.height)
+                .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            draw(SevenSegment::new("12:34")),
+            ["     _     _     ", "  |  _| ·  _| |_|", "  | |_  ·  _|   |"]
+        );
+        assert_eq!(
+            draw(SevenSegment::new("0:7").colon(false)),
+            [" _     _ ", "| |     |", "|_|     |"]
+        );
+    }
+
+    #[test]
+    fn led_rows_group_nibbles_and_label_bits() {
+        let draw = |leds: LedRow| {
+            let area = Rect::new(0, 0, leds.width(), leds.height());
+            let mut buf = Buffer::empty(area);
+            leds.render(area, &mut buf);
+            (0..area.height)
+                .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(LedRow::new(0b1010_0011, 8)), ["● ○ ● ○  ○ ○ ● ●"]);
+        assert_eq!(
+            draw(LedRow::new(0b0101, 4).first_bit(8).labels(true)),
+            ["○  ●  ○  ●", "11 10 9  8"]
+        );
+    }
+
+    #[test]
+    fn bit_toggle_moves_its_cursor_and_flips_bits() {
+        use crossterm::event::KeyCode;
+        let mut toggle = BitToggleWidget::new(8);
+        let press = |toggle: &mut BitToggleWidget, code| toggle.handle_input(KeyEvent::from(code));
+        assert!(press(&mut toggle, KeyCode::Char(' ')));
+        for _ in 0..3 {
+            press(&mut toggle, KeyCode::Right);
+        }
+        press(&mut toggle, KeyCode::Char(' '));
+        assert!(press(&mut toggle, KeyCode::Left));
+        assert!(!press(&mut toggle, KeyCode::Enter));
+        assert_eq!(toggle.value(), 0b1001_0000);
+
+        let area = Rect::new(0, 0, 20, BitToggleWidget::HEIGHT);
+        let mut buf = Buffer::empty(area);
+        toggle.render(area, &mut buf);
+        let row = |y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(0), "  ● ○ ○ ●  ○ ○ ○ ○  ");
+        assert_eq!(row(2), "      ▲             ");
+        assert_eq!(row(3), "    = 144  0x90     ");
+    }
+
+    #[test]
+    fn binary_spans_join_nibbles_and_alternate_colors() {
+        let nibbles = ["0110".to_string(), "1011".to_string(), "0001".to_string()];
+        let text = |separator| {
+            crate::settings::set(crate::settings::Settings {
+                nibble_separator: separator,
+                ..crate::settings::get()
+            });
+            binary_spans(&nibbles, [Color::Red, Color::Blue])
+        };
+        let spans = text(NibbleSeparator::Underscore);
+        assert_eq!(Line::from(spans.clone()).to_string(), "0110_1011_0001");
+        let colors: Vec<_> = spans.iter().step_by(2).map(|span| span.style.fg).collect();
+        assert_eq!(colors, [Some(Color::Red), Some(Color::Blue), Some(Color::Red)]);
+        assert_eq!(Line::from(text(NibbleSeparator::None)).to_string(), "011010110001");
+        assert_eq!(NibbleSeparator::parse(" Underscore"), Some(NibbleSeparator::Underscore));
+    }
+
+    #[test]
+    fn timer_bars_fill_in_eighths() {
+        let draw = |ratio| {
+            let area = Rect::new(0, 0, 4, 1);
+            let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("═"));
+            TimerBar::new(ratio, Color::Green).render(area, &mut buf);
+            buf.content.iter().map(ratatui::buffer::Cell::symbol).collect::<String>()
+        };
+        assert_eq!(draw(0.0), "════");
+        assert_eq!(draw(0.5), "██══");
+        assert_eq!(draw(0.6), "██▍═");
+        assert_eq!(draw(1.5), "████");
+    }
+
+    #[test]
+    fn braille_charts_join_points_and_label_their_axes() {
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buf = Buffer::empty(area);
+        BrailleChart::new(&[1.0, 4.0, 2.0]).unit("s").x_labels("1", "3").render(area, &mut buf);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["4s┤ ⢀⡠⠔⠊⠒⠤⣀ ", "0s┤⠊⠁      ⠉", "   1       3"]);
+    }
+
+    #[test]
+    fn scroll_lists_keep_the_selection_in_view_beside_a_scrollbar() {
+        let items = || (0..6).map(|i| ListItem::new(format!("item {i}"))).collect::<Vec<_>>();
+        let area = Rect::new(0, 0, 8, 3);
+        let mut state = ListState::default().with_selected(Some(5));
+        let mut buf = Buffer::empty(area);
+        ScrollList::new(items()).render(area, &mut buf, &mut state);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["item 3 ║", "item 4 █", "item 5 █"]);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 6));
+        ScrollList::new(items()).render(buf.area, &mut buf, &mut ListState::default());
+        assert_eq!(buf[(7, 0)].symbol(), " ", "no scrollbar when everything fits");
+    }
+
+    #[test]
+    fn marquee_scrolls_text_that_does_not_fit() {
+        let area = Rect::new(0, 0, 4, 1);
+        let draw = |marquee: Marquee| {
+            let mut buf = Buffer::empty(area);
+            marquee.render(area, &mut buf);
+            buf.content.iter().map(ratatui::buffer::Cell::symbol).collect::<String>()
+        };
+        assert_eq!(draw(Marquee::new("ab")), " ab ");
+        assert_eq!(draw(Marquee::new("abcdef").scrolling(false)), "abc…");
+
+        let start = (animation::now().as_secs_f32() * 10.0) as usize;
+        animation::advance(Duration::from_millis(400));
+        let frame = draw(Marquee::new("abcdef").speed(10.0).gap(2));
+        let pass = "abcdef  ";
+        let expected: String = pass.chars().cycle().skip((start + 4) % 8).take(4).collect();
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn precomputed_frames_match_the_computed_ones() {
+        let animation = |precomputed| {
+            let mut widget = ProceduralAnimationWidget::new(
+                "ab\ncd".to_string(),
+                10,
+                Duration::from_millis(50),
+                |x, _, progress, cycle, highlight| {
+                    if (x as f32) < progress * 2.0 {
+                        highlight
+                    } else {
+                        Color::Indexed((cycle % 2) as u8)
+                    }
+                },
+            )
+            .with_char_fn(|_, y, _, cycle, ch| if (y + cycle) % 2 == 0 { ch } else { '1' })
+            .precomputed(precomputed);
+            widget.set_highlight_color(Color::Red);
+            (widget.paused, widget.paused_progress, widget.paused_cycle) = (true, 0.5, 3);
+            widget
+        };
+        let area = Rect::new(0, 0, 2, 2);
+        let mut computed = Buffer::empty(area);
+        animation(false).render_to_buffer(area, &mut computed);
+        let mut cached = Buffer::empty(area);
+        animation(true).render_to_buffer(area, &mut cached);
+        assert_eq!(cached, computed);
+        assert_eq!(cached[(0, 0)].fg, Color::Red, "rebuilt for the new highlight");
+        assert_eq!(cached[(1, 1)].fg, Color::Indexed(1), "cycle 3 looks like cycle 1");
+    }
+}
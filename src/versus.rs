@@ -0,0 +1,591 @@
+//! Racing another player over the network: one side hosts on a TCP port, the other joins,
+//! and both answer the same seeded questions while seeing how far the other got. Races
+//! are dealt by the default settings, so the configs of the two players don't matter.
+//!
+//! The two sides talk in lines of text. The host opens with the race, e.g.
+//! `hello 1 42 8 unsigned 20` (protocol version, seed, mode, numbers, questions), and
+//! both report their progress after every answer, e.g. `progress 7 340 0` (answered,
+//! score, finished). Reading happens on a thread of its own, like terminal input.
+
+use crate::binary_numbers::BinaryNumbersGame;
+use crate::cli;
+use crate::keybinds::{self, KeyHint};
+use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
+use crate::playlist::Playlist;
+use crate::theme;
+use crate::toast;
+use binbreak::{Bits, NumberMode};
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect, Size};
+use ratatui::prelude::Alignment::Center;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Questions in a race
+const RACE_LENGTH: usize = 20;
+/// Longest race taken from a host
+const MAX_RACE_LENGTH: usize = 100;
+/// Bumped when the lines change, so different versions don't race
+const PROTOCOL_VERSION: u32 = 1;
+
+/// How far one side of the race got
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Progress {
+    answered: usize,
+    score: u32,
+    /// The run is over, by the last question or the last life
+    finished: bool,
+}
+
+/// A line sent between the two sides
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Wire {
+    /// From the host as soon as the other side is connected: the race to run
+    Hello {
+        seed: u64,
+        bits: Bits,
+        number_mode: NumberMode,
+        length: usize,
+    },
+    Progress(Progress),
+}
+
+impl Wire {
+    fn parse(line: &str) -> Option<Self> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["hello", version, seed, bits, numbers, length] => {
+                if version.parse() != Ok(PROTOCOL_VERSION) {
+                    return None;
+                }
+                let number_mode = match *numbers {
+                    "unsigned" => NumberMode::Unsigned,
+                    "signed" => NumberMode::Signed,
+                    _ => return None,
+                };
+                Some(Self::Hello {
+                    seed: seed.parse().ok()?,
+                    bits: cli::parse_bits(bits).ok()?,
+                    number_mode,
+                    length: length.parse().ok().filter(|n| (1..=MAX_RACE_LENGTH).contains(n))?,
+                })
+            },
+            ["progress", answered, score, finished] => Some(Self::Progress(Progress {
+                answered: answered.parse().ok()?,
+                score: score.parse().ok()?,
+                finished: *finished == "1",
+            })),
+            _ => None,
+        }
+    }
+
+    fn line(&self) -> String {
+        match self {
+            Self::Hello { seed, bits, number_mode, length } => {
+                let numbers = match number_mode {
+                    NumberMode::Unsigned => "unsigned",
+                    NumberMode::Signed => "signed",
+                };
+                let bits = bits.label().replace(" bit", "").replace('*', "x");
+                format!("hello {PROTOCOL_VERSION} {seed} {bits} {numbers} {length}")
+            },
+            Self::Progress(progress) => format!(
+                "progress {} {} {}",
+                progress.answered,
+                progress.score,
+                u8::from(progress.finished)
+            ),
+        }
+    }
+}
+
+/// What the network thread reports
+enum LinkEvent {
+    /// The other side is there; the stream is the half to write to
+    Connected(TcpStream),
+    Received(Wire),
+    /// The connection failed or was closed
+    Closed(String),
+}
+
+/// The connection to the other side, opened in the background
+struct Link {
+    events: Receiver<LinkEvent>,
+    /// Set once connected, dropped when writing fails
+    writer: Option<TcpStream>,
+}
+
+impl Link {
+    /// Listen on `port` for one player to join. Returns the port listened on, which is
+    /// picked by the system for port 0.
+    fn host(port: u16) -> io::Result<(Self, u16)> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let port = listener.local_addr()?.port();
+        let (sender, events) = mpsc::channel();
+        thread::spawn(move || match listener.accept() {
+            Ok((stream, peer)) => {
+                tracing::info!(%peer, "versus: joined");
+                Self::serve(stream, &sender);
+            },
+            Err(err) => {
+                let _ = sender.send(LinkEvent::Closed(err.to_string()));
+            },
+        });
+        Ok((Self { events, writer: None }, port))
+    }
+
+    /// Connect to a host at `address`, e.g. `192.168.1.20:4321`
+    fn join(address: String) -> Self {
+        let (sender, events) = mpsc::channel();
+        thread::spawn(move || match TcpStream::connect(&address) {
+            Ok(stream) => {
+                tracing::info!(address, "versus: connected");
+                Self::serve(stream, &sender);
+            },
+            Err(err) => {
+                let _ = sender.send(LinkEvent::Closed(format!("{address}: {err}")));
+            },
+        });
+        Self { events, writer: None }
+    }
+
+    /// Hand the writing half over and pass on every line read, until the other side
+    /// goes away
+    fn serve(stream: TcpStream, sender: &Sender<LinkEvent>) {
+        let _ = stream.set_nodelay(true);
+        let connected = stream.try_clone().map(LinkEvent::Connected);
+        let Ok(connected) =
+            connected.map_err(|err| sender.send(LinkEvent::Closed(err.to_string())))
+        else {
+            return;
+        };
+        if sender.send(connected).is_err() {
+            return;
+        }
+        for line in BufReader::new(stream).lines() {
+            let event = match line {
+                Ok(line) => match Wire::parse(&line) {
+                    Some(wire) => LinkEvent::Received(wire),
+                    None => {
+                        tracing::warn!(line, "versus: unexpected line");
+                        continue;
+                    },
+                },
+                Err(err) => LinkEvent::Closed(err.to_string()),
+            };
+            if sender.send(event).is_err() {
+                return;
+            }
+        }
+        let _ = sender.send(LinkEvent::Closed("the other side left".to_string()));
+    }
+
+    fn send(&mut self, wire: &Wire) {
+        let Some(writer) = &mut self.writer else { return };
+        if let Err(err) = writeln!(writer, "{}", wire.line()) {
+            tracing::warn!("versus: sending failed: {err}");
+            let _ = writer.shutdown(Shutdown::Both);
+            self.writer = None;
+        }
+    }
+
+    /// The next thing the network thread reported, if anything
+    fn poll(&self) -> Option<LinkEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+/// The reading thread holds a handle of its own, so the connection is shut down rather
+/// than just dropped: the other side sees it closed, and the thread stops reading
+impl Drop for Link {
+    fn drop(&mut self) {
+        let connected = self.events.try_iter().filter_map(|event| match event {
+            LinkEvent::Connected(stream) => Some(stream),
+            _ => None,
+        });
+        for stream in self.writer.take().into_iter().chain(connected) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// The race the host runs, sent to the other side when it joins
+#[derive(Clone, Debug)]
+pub struct Race {
+    pub bits: Bits,
+    pub number_mode: NumberMode,
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Stage {
+    /// For the other side to join, or for the race from the host
+    Waiting,
+    Racing,
+    /// The connection couldn't be made
+    Failed(String),
+}
+
+/// A race against a player on another computer
+pub struct Versus {
+    link: Link,
+    /// Where the other side can be found, or is expected
+    waiting_text: String,
+    /// The race to send, on the hosting side
+    race: Option<Race>,
+    /// The own run, a stand-in until the race is known
+    game: BinaryNumbersGame,
+    stage: Stage,
+    /// Questions in the race
+    length: usize,
+    /// Last sent
+    mine: Progress,
+    /// Last received, None until the other side reports
+    theirs: Option<Progress>,
+    /// The other side went away during the race
+    rival_left: bool,
+    exit_intended: bool,
+}
+
+impl Versus {
+    /// Host `race` on `port` and wait for a player to join
+    pub fn host(port: u16, race: Race) -> io::Result<Self> {
+        let (link, port) = Link::host(port)?;
+        let waiting_text = format!("Waiting for a rival on port {port}");
+        Ok(Self::new(link, waiting_text, Some(race)))
+    }
+
+    /// Join the race hosted at `address`
+    pub fn join(address: String) -> Self {
+        let waiting_text = format!("Joining {address}");
+        Self::new(Link::join(address), waiting_text, None)
+    }
+
+    fn new(link: Link, waiting_text: String, race: Option<Race>) -> Self {
+        Self {
+            link,
+            waiting_text,
+            race,
+            game: BinaryNumbersGame::new(Bits::Eight, NumberMode::Unsigned),
+            stage: Stage::Waiting,
+            length: RACE_LENGTH,
+            mine: Progress::default(),
+            theirs: None,
+            rival_left: false,
+            exit_intended: false,
+        }
+    }
+
+    fn start_race(&mut self, seed: u64, bits: Bits, number_mode: NumberMode, length: usize) {
+        tracing::info!(seed, mode = bits.label(), length, "versus: race starts");
+        self.game = BinaryNumbersGame::new(bits.clone(), number_mode)
+            .with_playlist(Playlist::repeat(length, bits, number_mode))
+            .with_standard_questions()
+            .with_seed(seed)
+            .with_countdown()
+            // Both sides race the same questions once, a restart would start over alone
+            .with_restart(false);
+        self.stage = Stage::Racing;
+        self.length = length;
+    }
+
+    /// Handle what the network thread reported since the last call
+    fn poll_link(&mut self) {
+        while let Some(event) = self.link.poll() {
+            match event {
+                LinkEvent::Connected(writer) => {
+                    self.link.writer = Some(writer);
+                    if let Some(race) = self.race.clone() {
+                        let seed = race.seed.unwrap_or_else(rand::random);
+                        let (bits, number_mode) = (race.bits, race.number_mode);
+                        let hello = Wire::Hello {
+                            seed,
+                            bits: bits.clone(),
+                            number_mode,
+                            length: RACE_LENGTH,
+                        };
+                        self.link.send(&hello);
+                        self.start_race(seed, bits, number_mode, RACE_LENGTH);
+                    }
+                },
+                LinkEvent::Received(Wire::Hello { seed, bits, number_mode, length }) => {
+                    if self.race.is_none() && self.stage == Stage::Waiting {
+                        self.start_race(seed, bits, number_mode, length);
+                    }
+                },
+                LinkEvent::Received(Wire::Progress(progress)) => {
+                    // No further than the race goes, whatever the other side claims
+                    let progress =
+                        Progress { answered: progress.answered.min(self.length), ..progress };
+                    if progress.finished && !self.theirs.is_some_and(|theirs| theirs.finished) {
+                        toast::show(format!("Your rival finished with {}", progress.score));
+                    }
+                    self.theirs = Some(progress);
+                },
+                LinkEvent::Closed(reason) => {
+                    tracing::info!(reason, "versus: closed");
+                    if self.stage == Stage::Racing {
+                        self.rival_left = !self.theirs.is_some_and(|theirs| theirs.finished);
+                        if self.rival_left {
+                            toast::show("Your rival left the race");
+                        }
+                    } else {
+                        self.stage = Stage::Failed(reason);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Tell the other side when the own run moved on
+    fn report_progress(&mut self) {
+        let progress = Progress {
+            answered: self.game.answered(),
+            score: self.game.score(),
+            finished: self.game.is_over(),
+        };
+        if progress != self.mine {
+            self.mine = progress;
+            self.link.send(&Wire::Progress(progress));
+        }
+    }
+
+    fn after_game_update(&mut self) {
+        self.exit_intended = self.game.is_exit_intended();
+        self.report_progress();
+    }
+
+    /// The other side's progress, or the result once both are done
+    fn rival_line(&self) -> Line<'static> {
+        let theme = theme::current();
+        let theirs = self.theirs.unwrap_or_default();
+        if self.mine.finished && theirs.finished {
+            let (text, color) = match self.mine.score.cmp(&theirs.score) {
+                std::cmp::Ordering::Greater => ("You win", theme.correct),
+                std::cmp::Ordering::Less => ("Your rival wins", theme.incorrect),
+                std::cmp::Ordering::Equal => ("A draw", theme.accent),
+            };
+            let text = format!(" {text}, {} to {} ", self.mine.score, theirs.score);
+            return Line::styled(text, Style::default().fg(color).bold());
+        }
+        if self.rival_left {
+            return Line::styled(" Your rival left ", Style::default().fg(theme.muted));
+        }
+        const BAR: usize = 20;
+        let filled = (theirs.answered * BAR / self.length.max(1)).min(BAR);
+        Line::from(vec![
+            Span::styled(" Rival ", Style::default().fg(theme.accent).bold()),
+            Span::styled("█".repeat(filled), Style::default().fg(theme.accent)),
+            Span::styled("░".repeat(BAR - filled), Style::default().fg(theme.muted)),
+            Span::styled(
+                format!(" {}/{} {} ", theirs.answered, self.length, theirs.score),
+                Style::default().fg(theme.text),
+            ),
+        ])
+    }
+
+    fn waiting_lines(&self) -> Vec<Line<'static>> {
+        let theme = theme::current();
+        let (headline, detail) = match &self.stage {
+            Stage::Failed(reason) => ("Couldn't race".to_string(), reason.clone()),
+            _ if self.race.is_some() => (
+                self.waiting_text.clone(),
+                "Others join with binbreak --join <this computer's address>:<port>".to_string(),
+            ),
+            _ => (self.waiting_text.clone(), "Waiting for the race to start".to_string()),
+        };
+        vec![
+            Line::styled(headline, Style::default().fg(theme.accent).bold()),
+            Line::styled(detail, Style::default().fg(theme.muted)),
+            Line::from(""),
+            Line::styled(
+                format!("{} to leave", keybinds::key_label(keybinds::EXIT)),
+                Style::default().fg(theme.muted),
+            ),
+        ]
+    }
+}
+
+impl WidgetRef for Versus {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if self.stage == Stage::Racing {
+            self.game.render_ref(area, buf);
+            // Over the top edge of the game
+            self.rival_line().centered().render(Rect { height: 1, ..area }, buf);
+            return;
+        }
+        let theme = theme::current();
+        let block = Block::bordered()
+            .title(" Versus ")
+            .title_alignment(Center)
+            .border_style(Style::default().fg(theme.muted));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let lines = self.waiting_lines();
+        #[allow(clippy::cast_possible_truncation)]
+        let (width, height) =
+            (lines.iter().map(Line::width).max().unwrap_or(0) as u16, lines.len() as u16);
+        let [column] =
+            Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(inner);
+        let [text_area] =
+            Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(column);
+        Paragraph::new(lines).alignment(Center).render(text_area, buf);
+    }
+}
+
+/// Once the race runs, everything goes to the own run
+impl MainScreenWidget for Versus {
+    fn run(&mut self, dt: f64) {
+        self.poll_link();
+        if self.stage == Stage::Racing {
+            self.game.run(dt);
+            self.after_game_update();
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        if self.stage != Stage::Racing {
+            self.exit_intended = keybinds::is_exit(input);
+            return;
+        }
+        self.game.handle_input(input);
+        self.after_game_update();
+    }
+
+    fn handle_key_release(&mut self, input: KeyEvent) {
+        if self.stage == Stage::Racing {
+            self.game.handle_key_release(input);
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.stage == Stage::Racing {
+            self.game.handle_mouse(mouse);
+            self.after_game_update();
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) {
+        if self.stage == Stage::Racing {
+            self.game.handle_paste(text);
+            self.after_game_update();
+        }
+    }
+
+    fn is_exit_intended(&self) -> bool {
+        self.exit_intended
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        if self.stage != Stage::Racing {
+            return vec![KeyHint::new(&[keybinds::EXIT], "leave")];
+        }
+        MainScreenWidget::key_hints(&self.game)
+    }
+
+    fn min_size(&self) -> Size {
+        MainScreenWidget::min_size(&self.game)
+    }
+
+    /// Racing keeps the app ticking, and so does waiting, to notice the other side
+    fn is_animating(&self) -> bool {
+        self.stage != Stage::Racing
+            || MainScreenWidget::is_animating(&self.game)
+            || !self.mine.finished
+    }
+
+    fn animation_finished(&mut self) {
+        if self.stage == Stage::Racing {
+            self.game.animation_finished();
+        }
+    }
+
+    fn config_changed(&mut self) {
+        MainScreenWidget::config_changed(&mut self.game);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings;
+    use crossterm::event::KeyCode;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn lines_read_back_as_they_were_sent() {
+        let hello = Wire::Hello {
+            seed: 42,
+            bits: Bits::FourShift4,
+            number_mode: NumberMode::Signed,
+            length: 20,
+        };
+        assert_eq!(hello.line(), "hello 1 42 4x16 signed 20");
+        assert_eq!(Wire::parse(&hello.line()), Some(hello));
+        let progress = Wire::Progress(Progress { answered: 7, score: 340, finished: true });
+        assert_eq!(Wire::parse(&progress.line()), Some(progress));
+        assert_eq!(Wire::parse("hello 2 42 8 unsigned 20"), None, "another protocol version");
+        assert_eq!(Wire::parse("progress x 1 0"), None);
+        assert_eq!(Wire::parse("hello 1 42 8 unsigned 0"), None, "a race without questions");
+        assert_eq!(Wire::parse("hello 1 42 8 unsigned 100000"), None, "an endless race");
+    }
+
+    #[test]
+    fn both_sides_race_the_same_questions_and_see_each_other() {
+        let before = settings::get();
+        settings::set(settings::Settings { guest: true, ..before });
+        let race = Race { bits: Bits::Eight, number_mode: NumberMode::Unsigned, seed: Some(7) };
+        let Ok(mut host) = Versus::host(0, race) else { panic!("listening on loopback") };
+        let port = host.waiting_text.rsplit(' ').next().unwrap_or_default().to_string();
+        let mut guest = Versus::join(format!("127.0.0.1:{port}"));
+
+        let started = Instant::now();
+        let pump =
+            |host: &mut Versus, guest: &mut Versus, until: &dyn Fn(&Versus, &Versus) -> bool| {
+                while !until(host, guest) {
+                    assert!(started.elapsed() < Duration::from_secs(5), "the race didn't go on");
+                    host.run(0.0);
+                    guest.run(0.0);
+                    thread::sleep(Duration::from_millis(5));
+                }
+            };
+        pump(&mut host, &mut guest, &|host, guest| {
+            host.stage == Stage::Racing && guest.stage == Stage::Racing
+        });
+        let drawn = |versus: &Versus| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 80, 30));
+            versus.game.render_ref(buf.area, &mut buf);
+            buf
+        };
+        // Past the countdown
+        host.run(5.0);
+        guest.run(5.0);
+        assert_eq!(drawn(&host), drawn(&guest), "the same question");
+
+        guest.handle_input(KeyEvent::from(KeyCode::Char('1')));
+        pump(&mut host, &mut guest, &|host, _| {
+            host.theirs.is_some_and(|theirs| theirs.answered == 1)
+        });
+        assert!(host.rival_line().to_string().starts_with(" Rival █░"));
+
+        // The pause dialog has nothing to start the race over with
+        for key in [KeyCode::Char('p'), KeyCode::Right, KeyCode::Enter, KeyCode::Char('r')] {
+            guest.handle_input(KeyEvent::from(key));
+        }
+        assert_eq!(guest.game.answered(), 1);
+
+        drop(guest);
+        while !host.rival_left {
+            assert!(started.elapsed() < Duration::from_secs(5), "leaving went unnoticed");
+            host.run(0.0);
+            thread::sleep(Duration::from_millis(5));
+        }
+        settings::set(before);
+    }
+}
@@ -0,0 +1,224 @@
+//! Printable questions for `binbreak gen`, dealt by the same generator as the game
+//! but without a terminal UI, e.g. to hand out worksheets. The CSV can be edited and read
+//! back with `--questions` to play exactly those questions.
+
+use binbreak::question::value_of;
+use binbreak::{Bits, NumberMode, Question};
+use clap::ValueEnum;
+use std::io::Write;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Format {
+    /// `question,choices,answer` rows with a header, for spreadsheets
+    Csv,
+    /// A numbered worksheet with the answers listed at the end
+    Text,
+}
+
+/// The bit pattern in groups of four, with the multiplier of scaled modes
+fn question_label(question: &Question) -> String {
+    match question.bits.scale_factor() {
+        1 => question.binary_string(),
+        scale => format!("{} x{scale}", question.binary_string()),
+    }
+}
+
+fn choices_label(question: &Question) -> String {
+    question.choices.iter().map(i32::to_string).collect::<Vec<_>>().join(" ")
+}
+
+/// The most choices a question from a file can offer, more don't fit next to each other
+const MAX_CHOICES: usize = 8;
+
+/// A question read from a file, with the number mode its answer reads the bits in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileQuestion {
+    pub question: Question,
+    pub number_mode: NumberMode,
+}
+
+/// Questions prepared in a file, played in order instead of dealt at random
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuestionList {
+    questions: Vec<FileQuestion>,
+}
+
+impl QuestionList {
+    /// Questions in the CSV format `gen` writes: `question,choices,answer` rows, the header
+    /// optional. The answer may be left out for the unsigned reading of the bits, the
+    /// choices for random ones. Blank lines and lines starting with `#` are skipped.
+    pub fn read(contents: &str) -> Result<Self, String> {
+        let mut questions = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("question,") {
+                continue;
+            }
+            let question = read_row(line).map_err(|err| format!("line {}: {err}", number + 1))?;
+            questions.push(question);
+        }
+        if questions.is_empty() {
+            return Err("the file has no questions".to_string());
+        }
+        Ok(Self { questions })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+        Self::read(&contents)
+    }
+
+    /// Questions in the list
+    pub fn total(&self) -> usize {
+        self.questions.len()
+    }
+
+    /// The question at `index`, None past the end of the list
+    pub fn get(&self, index: usize) -> Option<&FileQuestion> {
+        self.questions.get(index)
+    }
+}
+
+fn read_row(line: &str) -> Result<FileQuestion, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let (bits, pattern) = read_question_label(fields[0])?;
+    let number = |text: &str| text.parse::<i32>().map_err(|_| format!("`{text}` is no number"));
+    let answer = match fields.get(2).filter(|answer| !answer.is_empty()) {
+        Some(answer) => number(answer)?,
+        None => value_of(&bits, NumberMode::Unsigned, pattern),
+    };
+    let number_mode = [NumberMode::Unsigned, NumberMode::Signed]
+        .into_iter()
+        .find(|&mode| value_of(&bits, mode, pattern) == answer)
+        .ok_or_else(|| format!("{} is not {answer}, signed or unsigned", fields[0]))?;
+    let choices = fields.get(1).map_or("", |choices| *choices);
+    let question = if choices.is_empty() {
+        Question::with_pattern(bits, number_mode, pattern, &mut rand::rng())
+    } else {
+        let choices = choices.split_whitespace().map(number).collect::<Result<Vec<_>, _>>()?;
+        if !choices.contains(&answer) {
+            return Err(format!("the choices don't include the answer {answer}"));
+        }
+        if choices.len() > MAX_CHOICES {
+            return Err(format!("at most {MAX_CHOICES} choices fit on the screen"));
+        }
+        Question { bits, pattern, choices, answer }
+    };
+    Ok(FileQuestion { question, number_mode })
+}
+
+/// The mode and bit pattern of a label like `1011 0100` or `0011 x16`
+fn read_question_label(label: &str) -> Result<(Bits, u32), String> {
+    let (digits, scale) = match label.split_once('x') {
+        Some((digits, scale)) => (digits, scale.trim()),
+        None => (label, "1"),
+    };
+    let digits: String = digits.chars().filter(|c| !matches!(c, ' ' | '_')).collect();
+    let pattern = u32::from_str_radix(&digits, 2)
+        .map_err(|_| format!("`{label}` is not bits in groups of four"))?;
+    let bits = match (digits.len(), scale) {
+        (4, "1") => Bits::Four,
+        (4, "16") => Bits::FourShift4,
+        (4, "256") => Bits::FourShift8,
+        (4, "4096") => Bits::FourShift12,
+        (8, "1") => Bits::Eight,
+        (12, "1") => Bits::Twelve,
+        (16, "1") => Bits::Sixteen,
+        _ => {
+            return Err(format!(
+                "`{label}` is no mode: 4, 8, 12 or 16 bits, or 4 bits x16, x256 or x4096"
+            ));
+        },
+    };
+    Ok((bits, pattern))
+}
+
+pub fn write(questions: &[Question], format: Format, out: &mut impl Write) -> std::io::Result<()> {
+    match format {
+        Format::Csv => {
+            writeln!(out, "question,choices,answer")?;
+            for q in questions {
+                writeln!(out, "{},{},{}", question_label(q), choices_label(q), q.answer)?;
+            }
+        },
+        Format::Text => {
+            let number_width = questions.len().to_string().len();
+            for (i, q) in questions.iter().enumerate() {
+                let n = i + 1;
+                writeln!(
+                    out,
+                    "{n:>number_width$}. {}  =  ____   ({})",
+                    question_label(q),
+                    choices_label(q)
+                )?;
+            }
+            writeln!(out)?;
+            writeln!(out, "Answers")?;
+            for (i, q) in questions.iter().enumerate() {
+                let n = i + 1;
+                writeln!(out, "{n:>number_width$}. {}", q.answer)?;
+            }
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binbreak::{Bits, NumberMode};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn csv_rows_match_their_answers() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let questions: Vec<_> = (0..10)
+            .map(|_| Question::generate(Bits::FourShift4, NumberMode::Signed, &mut rng))
+            .collect();
+        let mut out = Vec::new();
+        write(&questions, Format::Csv, &mut out).unwrap_or_else(|e| panic!("{e}"));
+        let csv = String::from_utf8_lossy(&out);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("question,choices,answer"));
+
+        for line in lines {
+            let fields: Vec<&str> = line.split(',').collect();
+            let Some((pattern, scale)) = fields[0].split_once(" x") else {
+                panic!("scaled modes name their multiplier: {line}");
+            };
+            let raw = i32::from_str_radix(&pattern.replace(' ', ""), 2).unwrap_or(-1);
+            let signed = if raw >= 8 { raw - 16 } else { raw };
+            assert_eq!(scale, "16");
+            assert_eq!(fields[2], (signed * 16).to_string());
+            assert!(fields[1].split(' ').any(|choice| choice == fields[2]));
+        }
+        let Ok(read_back) = QuestionList::read(&csv) else { panic!("gen output reads back") };
+        let read_back: Vec<Question> =
+            read_back.questions.into_iter().map(|q| q.question).collect();
+        assert_eq!(read_back, questions);
+    }
+
+    #[test]
+    fn question_files_infer_the_mode_and_check_the_answer() {
+        let read = QuestionList::read;
+        let Ok(list) = read("# quiz\n1011 0100,180 148 52,180\n1111,,-1\n0011 x16\n") else {
+            panic!("valid file");
+        };
+        let questions = list.questions;
+        assert_eq!(questions[0].question.choices, [180, 148, 52]);
+        assert_eq!(questions[1].number_mode, NumberMode::Signed);
+        assert_eq!(questions[1].question.choices.len(), 3);
+        assert_eq!(
+            (questions[2].question.bits.clone(), questions[2].question.answer),
+            (Bits::FourShift4, 48)
+        );
+
+        assert_eq!(
+            read("1011,1 2,11"),
+            Err("line 1: the choices don't include the answer 11".to_string())
+        );
+        assert!(read("101,,5").is_err());
+        assert!(read("1011,,12").is_err());
+    }
+}